@@ -0,0 +1,476 @@
+//! Procedural macros for [passay-rs](https://github.com/mojtab23/passay-rs). They are re-exported by `passay_rs`
+//! when its `macros` feature is enabled.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    Expr, ExprLit, ExprRange, Ident, Lit, LitInt, LitStr, RangeLimits, Token, braced, bracketed,
+    parenthesized,
+};
+
+/// Same as the minimum sequence length of `RepeatCharacterRule` and `IllegalSequenceRule` in Passay.
+const MINIMUM_SEQUENCE_LENGTH: usize = 3;
+const DEFAULT_SEQUENCE_LENGTH: usize = 5;
+
+/// Declares a `PasswordValidator`. Parameters are checked while compiling, so building the validator cannot fail.
+///
+/// Rules are separated by commas:
+///
+/// - `length: 8..65`, `length: 8..=64`, `length: 8..` or `length: 12` for a `LengthRule`. Ranges follow Rust
+///   semantics, `8..64` allows at most 63 characters.
+/// - `digit >= 1`, `upper >= 1`, `lower >= 1`, `alpha >= 1` or `special >= 1` for a `CharacterRule` of the
+///   corresponding English character data.
+/// - `characteristics(3, [digit >= 1, upper >= 1, lower >= 1, special >= 1])` for `CharacterCharacteristics`.
+/// - `no_sequences(qwerty, alpha, numeric, 5)` for one `IllegalSequenceRule` per sequence, the length is optional.
+/// - `no_username(backwards, ignore_case)` for a `UsernameRule`, both flags are optional.
+/// - `no_repeat(4)` for a `RepeatCharacterRule`.
+/// - `no_whitespace`, `history` and `source` for the rules with their default settings.
+/// - `allowed("abc")` and `illegal("xyz")` for `AllowedCharacter` and `IllegalCharacterRule`.
+/// - `length_complexity { 0..12 => [...], 12..65 => [...] }` for a `LengthComplexityRule` with non-overlapping ranges.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///  use passay_rs_macros::policy;
+///
+///  let validator = policy! {
+///      length: 8..=64,
+///      digit >= 1,
+///      upper >= 1,
+///      no_sequences(qwerty, alpha, 5),
+///      no_username(backwards, ignore_case),
+///  };
+///  let password = |p: &str| PasswordData::with_password_and_user(p.to_string(), Some("alice".to_string()));
+///  assert!(validator.validate(&password("Tr0ub4dor&3")).valid());
+///  assert!(!validator.validate(&password("Tr0ub4dor&ECILA")).valid());
+///  assert!(!validator.validate(&password("Tr0ub4dor&qwert")).valid());
+/// ```
+///
+/// Invalid parameters do not compile:
+///
+/// ```compile_fail
+///  let validator = passay_rs_macros::policy! { digit >= 0 };
+/// ```
+///
+/// ```compile_fail
+///  let validator = passay_rs_macros::policy! {
+///      length_complexity {
+///          0..12 => [digit >= 1],
+///          10..64 => [digit >= 1, upper >= 1],
+///      }
+///  };
+/// ```
+#[proc_macro]
+pub fn policy(input: TokenStream) -> TokenStream {
+    let policy = syn::parse_macro_input!(input as Policy);
+    let rules = policy.rules.iter().flat_map(Item::rules);
+    quote! {
+        ::passay_rs::rule::password_validator::PasswordValidator::new(::std::vec![#(#rules),*])
+    }
+    .into()
+}
+
+struct Policy {
+    rules: Vec<Item>,
+}
+
+impl Parse for Policy {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let rules = Punctuated::<Item, Token![,]>::parse_terminated(input)?;
+        Ok(Policy {
+            rules: rules.into_iter().collect(),
+        })
+    }
+}
+
+enum Item {
+    Length {
+        min: usize,
+        max: Option<usize>,
+    },
+    Character(CharacterItem),
+    Characteristics {
+        num: usize,
+        rules: Vec<CharacterItem>,
+    },
+    NoSequences {
+        sequences: Vec<Ident>,
+        length: usize,
+    },
+    NoUsername {
+        backwards: bool,
+        ignore_case: bool,
+    },
+    NoRepeat(usize),
+    NoWhitespace,
+    History,
+    Source,
+    Allowed(LitStr),
+    Illegal(LitStr),
+    LengthComplexity(Vec<(usize, usize, Vec<Item>)>),
+}
+
+struct CharacterItem {
+    class: Ident,
+    minimum: usize,
+}
+
+impl Parse for CharacterItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let class: Ident = input.parse()?;
+        character_data(&class)?;
+        input.parse::<Token![>=]>()?;
+        let literal: LitInt = input.parse()?;
+        let minimum = literal.base10_parse()?;
+        if minimum == 0 {
+            return Err(syn::Error::new(
+                literal.span(),
+                "the minimum number of characters must be greater than zero",
+            ));
+        }
+        Ok(CharacterItem { class, minimum })
+    }
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![>=]) {
+            return Ok(Item::Character(input.parse()?));
+        }
+        let name: Ident = input.parse()?;
+        match name.to_string().as_str() {
+            "length" => {
+                input.parse::<Token![:]>()?;
+                let expr: Expr = input.parse()?;
+                let (min, max) = match &expr {
+                    Expr::Range(range) => range_bounds(range)?,
+                    expr => {
+                        let length = integer(expr)?;
+                        (length, Some(length))
+                    }
+                };
+                Ok(Item::Length { min, max })
+            }
+            "characteristics" => {
+                let content;
+                parenthesized!(content in input);
+                let literal: LitInt = content.parse()?;
+                let num = literal.base10_parse()?;
+                content.parse::<Token![,]>()?;
+                let list;
+                bracketed!(list in content);
+                let rules: Vec<CharacterItem> =
+                    Punctuated::<CharacterItem, Token![,]>::parse_terminated(&list)?
+                        .into_iter()
+                        .collect();
+                if num == 0 || num > rules.len() {
+                    return Err(syn::Error::new(
+                        literal.span(),
+                        format!(
+                            "the number of characteristics must be between 1 and {}",
+                            rules.len()
+                        ),
+                    ));
+                }
+                Ok(Item::Characteristics { num, rules })
+            }
+            "no_sequences" => {
+                let content;
+                parenthesized!(content in input);
+                let mut sequences = vec![];
+                let mut length = DEFAULT_SEQUENCE_LENGTH;
+                while !content.is_empty() {
+                    if content.peek(LitInt) {
+                        let literal: LitInt = content.parse()?;
+                        length = literal.base10_parse()?;
+                        if length < MINIMUM_SEQUENCE_LENGTH {
+                            return Err(syn::Error::new(
+                                literal.span(),
+                                format!(
+                                    "the sequence length must be at least {MINIMUM_SEQUENCE_LENGTH}"
+                                ),
+                            ));
+                        }
+                    } else {
+                        let sequence: Ident = content.parse()?;
+                        sequence_data(&sequence)?;
+                        sequences.push(sequence);
+                    }
+                    if !content.is_empty() {
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+                if sequences.is_empty() {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "expected at least one of `qwerty`, `alpha` or `numeric`",
+                    ));
+                }
+                Ok(Item::NoSequences { sequences, length })
+            }
+            "no_username" => {
+                let (mut backwards, mut ignore_case) = (false, false);
+                if input.peek(syn::token::Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    for flag in Punctuated::<Ident, Token![,]>::parse_terminated(&content)? {
+                        match flag.to_string().as_str() {
+                            "backwards" => backwards = true,
+                            "ignore_case" => ignore_case = true,
+                            _ => {
+                                return Err(syn::Error::new(
+                                    flag.span(),
+                                    "expected `backwards` or `ignore_case`",
+                                ));
+                            }
+                        }
+                    }
+                }
+                Ok(Item::NoUsername {
+                    backwards,
+                    ignore_case,
+                })
+            }
+            "no_repeat" => {
+                let content;
+                parenthesized!(content in input);
+                let literal: LitInt = content.parse()?;
+                let length = literal.base10_parse()?;
+                if length < MINIMUM_SEQUENCE_LENGTH {
+                    return Err(syn::Error::new(
+                        literal.span(),
+                        format!("the sequence length must be at least {MINIMUM_SEQUENCE_LENGTH}"),
+                    ));
+                }
+                Ok(Item::NoRepeat(length))
+            }
+            "no_whitespace" => Ok(Item::NoWhitespace),
+            "history" => Ok(Item::History),
+            "source" => Ok(Item::Source),
+            "allowed" | "illegal" => {
+                let content;
+                parenthesized!(content in input);
+                let characters: LitStr = content.parse()?;
+                if characters.value().is_empty() {
+                    return Err(syn::Error::new(
+                        characters.span(),
+                        "expected at least one character",
+                    ));
+                }
+                Ok(match name.to_string().as_str() {
+                    "allowed" => Item::Allowed(characters),
+                    _ => Item::Illegal(characters),
+                })
+            }
+            "length_complexity" => {
+                let content;
+                braced!(content in input);
+                let mut ranges: Vec<(usize, usize, Vec<Item>)> = vec![];
+                while !content.is_empty() {
+                    let range: ExprRange = content.parse()?;
+                    let (start, end) = match range_bounds(&range)? {
+                        (start, Some(max)) => (start, max + 1),
+                        (_, None) => {
+                            return Err(syn::Error::new_spanned(
+                                &range,
+                                "expected a range with an end",
+                            ));
+                        }
+                    };
+                    if let Some((s, e, _)) = ranges.iter().find(|(s, e, _)| start < *e && *s < end)
+                    {
+                        return Err(syn::Error::new_spanned(
+                            &range,
+                            format!("range {start}..{end} overlaps range {s}..{e}"),
+                        ));
+                    }
+                    content.parse::<Token![=>]>()?;
+                    let list;
+                    bracketed!(list in content);
+                    let rules: Vec<Item> = Punctuated::<Item, Token![,]>::parse_terminated(&list)?
+                        .into_iter()
+                        .collect();
+                    if rules.is_empty() {
+                        return Err(syn::Error::new_spanned(&range, "rules cannot be empty"));
+                    }
+                    ranges.push((start, end, rules));
+                    if !content.is_empty() {
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+                Ok(Item::LengthComplexity(ranges))
+            }
+            _ => Err(syn::Error::new(
+                name.span(),
+                format!("unknown rule `{name}`"),
+            )),
+        }
+    }
+}
+
+impl Item {
+    /// Returns the expressions of the boxed rules this item expands to.
+    fn rules(&self) -> Vec<TokenStream2> {
+        let rule = match self {
+            Item::Length { min, max } => {
+                let max = match max {
+                    Some(max) => quote!(#max),
+                    None => quote!(usize::MAX),
+                };
+                quote!(::passay_rs::rule::length::LengthRule::new(#min, #max))
+            }
+            Item::Character(item) => item.rule(),
+            Item::Characteristics { num, rules } => {
+                let rules = rules.iter().map(CharacterItem::rule);
+                quote! {
+                    ::passay_rs::rule::character_characteristics::CharacterCharacteristics::with_rules_and_characteristics(
+                        ::std::vec![#(#rules),*],
+                        #num,
+                    )
+                    .expect("validated by policy!")
+                }
+            }
+            Item::NoSequences { sequences, length } => {
+                return sequences
+                    .iter()
+                    .map(|sequence| {
+                        let data = sequence_data(sequence).expect("validated while parsing");
+                        boxed(quote! {
+                            ::passay_rs::rule::illegal_sequence::IllegalSequenceRule::with_warp(#data, #length, false)
+                        })
+                    })
+                    .collect();
+            }
+            Item::NoUsername {
+                backwards,
+                ignore_case,
+            } => quote! {
+                ::passay_rs::rule::username::UsernameRule::with_match_backwards_and_ignore_case(#backwards, #ignore_case)
+            },
+            Item::NoRepeat(length) => quote! {
+                ::passay_rs::rule::repeat_character::RepeatCharacterRule::with_sequence_len(#length)
+                    .expect("validated by policy!")
+            },
+            Item::NoWhitespace => {
+                quote!(<::passay_rs::rule::whitespace::WhitespaceRule as ::std::default::Default>::default())
+            }
+            Item::History => {
+                quote!(
+                    <::passay_rs::rule::history::HistoryRule as ::std::default::Default>::default()
+                )
+            }
+            Item::Source => quote!(
+                <::passay_rs::rule::source::SourceRule as ::std::default::Default>::default()
+            ),
+            Item::Allowed(characters) => {
+                quote!(::passay_rs::rule::allowed_character::AllowedCharacter::from_chars(#characters))
+            }
+            Item::Illegal(characters) => quote! {
+                ::passay_rs::rule::illegal_character::IllegalCharacterRule::from_chars(#characters.chars().collect())
+            },
+            Item::LengthComplexity(ranges) => {
+                let ranges = ranges.iter().map(|(start, end, rules)| {
+                    let rules = rules.iter().flat_map(Item::rules);
+                    quote! {
+                        rule.add_rules(#start..#end, ::std::vec![#(#rules),*]).expect("validated by policy!");
+                    }
+                });
+                quote! {
+                    {
+                        let mut rule = <::passay_rs::rule::length_complexity::LengthComplexityRule
+                            as ::std::default::Default>::default();
+                        #(#ranges)*
+                        rule
+                    }
+                }
+            }
+        };
+        vec![boxed(rule)]
+    }
+}
+
+impl CharacterItem {
+    fn rule(&self) -> TokenStream2 {
+        let data = character_data(&self.class).expect("validated while parsing");
+        let minimum = self.minimum;
+        quote! {
+            ::passay_rs::rule::character::CharacterRule::new(::std::boxed::Box::new(#data), #minimum)
+                .expect("validated by policy!")
+        }
+    }
+}
+
+fn boxed(rule: TokenStream2) -> TokenStream2 {
+    quote! {
+        ::std::boxed::Box::new(#rule) as ::std::boxed::Box<dyn ::passay_rs::rule::Rule>
+    }
+}
+
+fn character_data(class: &Ident) -> syn::Result<TokenStream2> {
+    let variant = match class.to_string().as_str() {
+        "digit" => quote!(Digit),
+        "upper" => quote!(UpperCase),
+        "lower" => quote!(LowerCase),
+        "alpha" => quote!(Alphabetical),
+        "special" => quote!(Special),
+        _ => {
+            return Err(syn::Error::new(
+                class.span(),
+                "expected `digit`, `upper`, `lower`, `alpha` or `special`",
+            ));
+        }
+    };
+    Ok(quote!(::passay_rs::rule::character_data::EnglishCharacterData::#variant))
+}
+
+fn sequence_data(sequence: &Ident) -> syn::Result<TokenStream2> {
+    let variant = match sequence.to_string().as_str() {
+        "qwerty" => quote!(USQwerty),
+        "alpha" => quote!(Alphabetical),
+        "numeric" => quote!(Numerical),
+        _ => {
+            return Err(syn::Error::new(
+                sequence.span(),
+                "expected `qwerty`, `alpha` or `numeric`",
+            ));
+        }
+    };
+    Ok(quote!(::passay_rs::rule::sequence_data::EnglishSequenceData::#variant))
+}
+
+/// Returns the inclusive bounds of a range of integer literals.
+fn range_bounds(range: &ExprRange) -> syn::Result<(usize, Option<usize>)> {
+    let min = match &range.start {
+        Some(start) => integer(start)?,
+        None => 0,
+    };
+    let max = match (&range.end, &range.limits) {
+        (None, _) => None,
+        (Some(end), RangeLimits::HalfOpen(_)) => {
+            let end = integer(end)?;
+            if end == 0 {
+                return Err(syn::Error::new_spanned(range, "the range is empty"));
+            }
+            Some(end - 1)
+        }
+        (Some(end), RangeLimits::Closed(_)) => Some(integer(end)?),
+    };
+    if max.is_some_and(|max| max < min) {
+        return Err(syn::Error::new_spanned(range, "the range is empty"));
+    }
+    Ok((min, max))
+}
+
+fn integer(expr: &Expr) -> syn::Result<usize> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(literal),
+            ..
+        }) => literal.base10_parse(),
+        _ => Err(syn::Error::new_spanned(expr, "expected an integer literal")),
+    }
+}
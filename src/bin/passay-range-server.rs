@@ -0,0 +1,68 @@
+//! Serves a breach hash file over HTTP in the format of the Have I Been Pwned range API.
+//!
+//! ```text
+//! passay-range-server [--listen ADDRESS] HASH_FILE
+//! ```
+//!
+//! `HASH_FILE` is a text file of `SHA1:COUNT` lines sorted by hash or its binary form, see [HashFile]. The server
+//! listens on `127.0.0.1:8080` unless another address is given.
+use passay_rs::breach::offline::HashFile;
+use passay_rs::breach::server::RangeServer;
+use std::process::ExitCode;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+const USAGE: &str = "usage: passay-range-server [--listen ADDRESS] HASH_FILE";
+
+fn main() -> ExitCode {
+    let mut address = DEFAULT_ADDRESS.to_string();
+    let mut path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-l" | "--listen" => match args.next() {
+                Some(a) => address = a,
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => return usage(),
+        }
+    }
+    let Some(path) = path else {
+        return usage();
+    };
+
+    let file = match HashFile::open(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("failed to open {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = match RangeServer::bind(address.as_str(), file) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("failed to listen on {address}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match server.local_addr() {
+        Ok(local) => println!("serving {path} on http://{local}/range/"),
+        Err(_) => println!("serving {path} on http://{address}/range/"),
+    }
+    match server.serve() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("server stopped: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
@@ -0,0 +1,142 @@
+//! [RangeSource] over HTTP, compatible with the range API of Have I Been Pwned.
+use crate::breach::{RangeEntry, RangeSource, parse_range};
+use std::time::Duration;
+
+/// Base URL of the Have I Been Pwned range API.
+pub const HIBP_URL: &str = "https://api.pwnedpasswords.com/range/";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Queries `GET {base_url}{prefix}` and parses the response in the HIBP format. With padding enabled, the
+/// `Add-Padding` header asks the server to hide the size of the response behind entries with a count of zero.
+///
+/// # Example
+///
+/// ```no_run
+///  use passay_rs::breach::http::HttpRangeSource;
+///  use passay_rs::rule::breached_password::BreachedPasswordRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = BreachedPasswordRule::new(HttpRangeSource::default());
+///  let result = rule.validate(&PasswordData::with_password("password".to_string()));
+///  assert!(!result.valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpRangeSource {
+    base_url: String,
+    padding: bool,
+    agent: ureq::Agent,
+}
+
+impl HttpRangeSource {
+    pub fn new(base_url: &str, padding: bool) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("passay-rs/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Self {
+            base_url: base_url.to_string(),
+            padding,
+            agent,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+impl Default for HttpRangeSource {
+    fn default() -> Self {
+        Self::new(HIBP_URL, true)
+    }
+}
+
+impl RangeSource for HttpRangeSource {
+    fn range(&self, prefix: &str) -> Result<Vec<RangeEntry>, String> {
+        let url = format!("{}{prefix}", self.base_url);
+        let mut request = self.agent.get(&url);
+        if self.padding {
+            request = request.set("Add-Padding", "true");
+        }
+        let body = request
+            .call()
+            .map_err(|e| format!("request to {url} failed: {e}"))?
+            .into_string()
+            .map_err(|e| format!("failed to read response from {url}: {e}"))?;
+        parse_range(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::breach::http::HttpRangeSource;
+    use crate::breach::{RangeEntry, RangeSource};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves a single request with the given status and body, returning the received request head.
+    fn mock_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/range/", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            head
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_range() {
+        let (url, server) = mock_server(
+            "200 OK",
+            "1E4C9B93F3F0682250B6CF8331B7EE68FD8:3\r\nABC:0\r\n",
+        );
+        let source = HttpRangeSource::new(&url, true);
+        assert_eq!(
+            Ok(vec![
+                RangeEntry {
+                    suffix: "1E4C9B93F3F0682250B6CF8331B7EE68FD8".to_string(),
+                    count: 3,
+                },
+                RangeEntry {
+                    suffix: "ABC".to_string(),
+                    count: 0,
+                },
+            ]),
+            source.range("5BAA6")
+        );
+        let head = server.join().unwrap().to_ascii_lowercase();
+        assert!(head.starts_with("get /range/5baa6 http/1.1"), "{head}");
+        assert!(head.contains("add-padding: true"), "{head}");
+    }
+
+    #[test]
+    fn test_error() {
+        let (url, server) = mock_server("503 Service Unavailable", "");
+        let source = HttpRangeSource::new(&url, false);
+        let error = source.range("5BAA6").unwrap_err();
+        assert!(error.contains("503"), "{error}");
+        assert!(!server.join().unwrap().to_ascii_lowercase().contains("add-padding"));
+    }
+}
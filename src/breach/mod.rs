@@ -0,0 +1,104 @@
+//! Sources of breached password hashes, queried with the k-anonymity range protocol of
+//! [Have I Been Pwned](https://haveibeenpwned.com/API/v3#PwnedPasswords). Only the first 5 hex characters of the
+//! SHA-1 hash leave the process, the source answers with the suffixes of all known hashes sharing that prefix.
+#[cfg(feature = "hibp")]
+pub mod http;
+pub mod offline;
+pub mod server;
+
+/// Number of hex characters of the hash that are sent to a [RangeSource].
+pub const PREFIX_LENGTH: usize = 5;
+
+/// Suffix of a breached hash and the number of times it was seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeEntry {
+    /// Upper-case hex digits of the hash after the prefix.
+    pub suffix: String,
+    pub count: u64,
+}
+
+/// Strategy trait for looking up the breached hashes that start with a prefix.
+pub trait RangeSource {
+    /// Returns all entries whose hash starts with the given prefix of [PREFIX_LENGTH] upper-case hex digits. Sources
+    /// may add padding entries with a count of zero.
+    fn range(&self, prefix: &str) -> Result<Vec<RangeEntry>, String>;
+}
+
+impl<S: RangeSource + ?Sized> RangeSource for Box<S> {
+    fn range(&self, prefix: &str) -> Result<Vec<RangeEntry>, String> {
+        (**self).range(prefix)
+    }
+}
+
+/// Parses a range response in the HIBP format, one `SUFFIX:COUNT` entry per line.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::breach::{RangeEntry, parse_range};
+///
+///  let entries = parse_range("0018A45C4D1DEF81644B54AB7F969B88D65:10\r\n00D4F6E8FA6EECAD2A3AA415EEC418D38EC:0\r\n");
+///  assert_eq!(
+///      Ok(RangeEntry { suffix: "0018A45C4D1DEF81644B54AB7F969B88D65".to_string(), count: 10 }),
+///      entries.map(|e| e[0].clone())
+///  );
+/// ```
+pub fn parse_range(body: &str) -> Result<Vec<RangeEntry>, String> {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (suffix, count) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected SUFFIX:COUNT but found {line:?}"))?;
+            if suffix.is_empty() || !suffix.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("invalid hash suffix {suffix:?}"));
+            }
+            let count = count.trim().parse().map_err(|_| format!("invalid count {count:?}"))?;
+            Ok(RangeEntry {
+                suffix: suffix.to_ascii_uppercase(),
+                count,
+            })
+        })
+        .collect()
+}
+
+/// Formats a hash as upper-case hex digits.
+pub fn to_hex(hash: &[u8]) -> String {
+    hash.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::breach::{RangeEntry, parse_range, to_hex};
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            Ok(vec![
+                RangeEntry {
+                    suffix: "1E4C9B93F3F0682250B6CF8331B7EE68FD8".to_string(),
+                    count: 3,
+                },
+                RangeEntry {
+                    suffix: "ABC".to_string(),
+                    count: 0,
+                },
+            ]),
+            parse_range("1E4C9B93F3F0682250B6CF8331B7EE68FD8:3\r\nabc:0\r\n\r\n")
+        );
+        assert_eq!(
+            Err("expected SUFFIX:COUNT but found \"ABC\"".to_string()),
+            parse_range("ABC")
+        );
+        assert_eq!(
+            Err("invalid hash suffix \"XYZ\"".to_string()),
+            parse_range("XYZ:1")
+        );
+        assert_eq!(
+            Err("invalid count \"-1\"".to_string()),
+            parse_range("ABC:-1")
+        );
+        assert_eq!("00FF10", to_hex(&[0, 255, 16]));
+    }
+}
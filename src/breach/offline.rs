@@ -0,0 +1,388 @@
+//! Offline lookup in breach corpora distributed as files, e.g. the `pwnedpasswords` downloads with one
+//! `SHA1:COUNT` line per hash sorted by hash. Files are memory-mapped and binary-searched, so they are never loaded
+//! into memory as a whole.
+use crate::breach::{PREFIX_LENGTH, RangeEntry, RangeSource, to_hex};
+use crate::dictionary::Dictionary;
+use crate::hash::{Hasher, Sha1Hasher};
+use memmap2::Mmap;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// First bytes of the compact binary form written by [write_binary].
+pub const BINARY_MAGIC: &[u8; 8] = b"PASSAYH1";
+const HASH_LENGTH: usize = 20;
+/// A record of the binary form is the raw hash followed by the count as big-endian `u32`.
+const RECORD_LENGTH: usize = HASH_LENGTH + 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Binary,
+}
+
+/// Memory-mapped breach corpus, either a text file of `HASH:COUNT` lines sorted by hash or its binary form written by
+/// [write_binary]. The format is detected when opening the file.
+///
+/// It can be used as a [RangeSource] for the
+/// [BreachedPasswordRule](crate::rule::breached_password::BreachedPasswordRule), which reports the count, or as a
+/// [Dictionary] of the passwords seen at least `minimum_count` times.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::breach::offline::HashFile;
+///  use passay_rs::rule::breached_password::BreachedPasswordRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/test/breached-hashes.txt");
+///  let corpus = HashFile::open(path).unwrap();
+///  assert_eq!(Some(9545824), corpus.count_password("password"));
+///
+///  let rule = BreachedPasswordRule::with_minimum_count(corpus, 1000).unwrap();
+///  let result = rule.validate(&PasswordData::with_password("password".to_string()));
+///  assert!(!result.valid());
+/// ```
+#[derive(Debug)]
+pub struct HashFile {
+    mmap: Mmap,
+    format: Format,
+    minimum_count: u64,
+    len: OnceLock<usize>,
+}
+
+impl HashFile {
+    /// Opens a hash file whose every entry counts as a [Dictionary] match.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::with_minimum_count(path, 1)
+    }
+
+    /// Opens a hash file whose entries only count as a [Dictionary] match if seen at least `minimum_count` times.
+    pub fn with_minimum_count<P: AsRef<Path>>(path: P, minimum_count: u64) -> io::Result<Self> {
+        if minimum_count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "minimum count must be greater than zero",
+            ));
+        }
+        let file = File::open(path)?;
+        // SAFETY: the file is only read, modifying it while it is mapped is not supported
+        let mmap = unsafe { Mmap::map(&file)? };
+        let format = if mmap.starts_with(BINARY_MAGIC) {
+            if !(mmap.len() - BINARY_MAGIC.len()).is_multiple_of(RECORD_LENGTH) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "binary hash file is truncated",
+                ));
+            }
+            Format::Binary
+        } else {
+            Format::Text
+        };
+        Ok(Self {
+            mmap,
+            format,
+            minimum_count,
+            len: OnceLock::new(),
+        })
+    }
+
+    pub fn minimum_count(&self) -> u64 {
+        self.minimum_count
+    }
+
+    /// Returns how often the hash given as hex digits was seen, or `None` if it is not in the corpus.
+    pub fn count(&self, hash: &str) -> Option<u64> {
+        let hash = hash.to_ascii_uppercase();
+        self.entries_from(&hash)
+            .next()
+            .filter(|(h, _)| *h == hash)
+            .map(|(_, count)| count)
+    }
+
+    /// Returns how often the SHA-1 hash of the password was seen.
+    pub fn count_password(&self, password: &str) -> Option<u64> {
+        let hash = Sha1Hasher.hash(password.as_bytes()).ok()?;
+        self.count(&to_hex(&hash))
+    }
+
+    /// Returns the entries starting with the first one whose hash is not less than `hash`.
+    fn entries_from<'a>(&'a self, hash: &str) -> Box<dyn Iterator<Item = (String, u64)> + 'a> {
+        match self.format {
+            Format::Text => {
+                let data = &self.mmap[..];
+                let start = text_lower_bound(data, hash.as_bytes());
+                Box::new(data[start..].split(|&b| b == b'\n').filter_map(parse_line))
+            }
+            Format::Binary => {
+                let records = &self.mmap[BINARY_MAGIC.len()..];
+                let count = records.len() / RECORD_LENGTH;
+                let record = |i: usize| &records[i * RECORD_LENGTH..(i + 1) * RECORD_LENGTH];
+                let start =
+                    partition_point(count, |i| to_hex(&record(i)[..HASH_LENGTH]).as_str() < hash);
+                Box::new((start..count).map(move |i| {
+                    let record = record(i);
+                    let mut count = [0; 4];
+                    count.copy_from_slice(&record[HASH_LENGTH..]);
+                    (
+                        to_hex(&record[..HASH_LENGTH]),
+                        u32::from_be_bytes(count) as u64,
+                    )
+                }))
+            }
+        }
+    }
+}
+
+impl RangeSource for HashFile {
+    fn range(&self, prefix: &str) -> Result<Vec<RangeEntry>, String> {
+        let prefix = prefix.to_ascii_uppercase();
+        if prefix.len() != PREFIX_LENGTH || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid hash prefix {prefix:?}"));
+        }
+        Ok(self
+            .entries_from(&prefix)
+            .take_while(|(hash, _)| hash.starts_with(&prefix))
+            .map(|(hash, count)| RangeEntry {
+                suffix: hash[PREFIX_LENGTH..].to_string(),
+                count,
+            })
+            .collect())
+    }
+}
+
+impl Dictionary for HashFile {
+    fn search(&self, word: &str) -> bool {
+        self.count_password(word).is_some_and(|count| count >= self.minimum_count)
+    }
+
+    /// Returns the number of hashes in the file, regardless of their count.
+    fn len(&self) -> usize {
+        *self.len.get_or_init(|| match self.format {
+            Format::Text => self.mmap.split(|&b| b == b'\n').filter_map(parse_line).count(),
+            Format::Binary => (self.mmap.len() - BINARY_MAGIC.len()) / RECORD_LENGTH,
+        })
+    }
+}
+
+/// Converts a text hash file into the binary form, which is about half the size and faster to search. Entries must be
+/// sorted by hash and counts must fit into `u32`. Returns the number of entries written.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::breach::offline::{BINARY_MAGIC, write_binary};
+///
+///  let text = "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:3\n";
+///  let mut binary = vec![];
+///  assert_eq!(1, write_binary(text.as_bytes(), &mut binary).unwrap());
+///  assert_eq!(BINARY_MAGIC.len() + 24, binary.len());
+/// ```
+pub fn write_binary<R: BufRead, W: Write>(input: R, mut output: W) -> io::Result<u64> {
+    output.write_all(BINARY_MAGIC)?;
+    let mut previous: Option<String> = None;
+    let mut written = 0;
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {message}", i + 1),
+            )
+        };
+        let (hash, count) = parse_line(line.as_bytes())
+            .ok_or_else(|| invalid(format!("expected HASH:COUNT but found {line:?}")))?;
+        if hash.len() != HASH_LENGTH * 2 {
+            return Err(invalid(format!("expected a SHA-1 hash but found {hash:?}")));
+        }
+        if previous.as_ref().is_some_and(|p| p.as_str() >= hash.as_str()) {
+            return Err(invalid("entries are not sorted by hash".to_string()));
+        }
+        let count =
+            u32::try_from(count).map_err(|_| invalid(format!("count {count} is too large")))?;
+        let bytes: Vec<u8> = (0..HASH_LENGTH)
+            .map(|j| u8::from_str_radix(&hash[j * 2..j * 2 + 2], 16).expect("validated hex digits"))
+            .collect();
+        output.write_all(&bytes)?;
+        output.write_all(&count.to_be_bytes())?;
+        previous = Some(hash);
+        written += 1;
+    }
+    output.flush()?;
+    Ok(written)
+}
+
+/// Parses a `HASH:COUNT` line into the upper-case hash and count.
+fn parse_line(line: &[u8]) -> Option<(String, u64)> {
+    let line = std::str::from_utf8(line).ok()?.trim();
+    let (hash, count) = line.split_once(':')?;
+    if hash.is_empty() || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((hash.to_ascii_uppercase(), count.trim().parse().ok()?))
+}
+
+/// Returns the offset of the first line whose hash is not less than `hash`.
+fn text_lower_bound(data: &[u8], hash: &[u8]) -> usize {
+    let mut low = 0;
+    let mut high = data.len();
+    // low is always the start of a line, all lines starting before it are less than hash
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let start = data[..middle].iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1).max(low);
+        let end = data[start..].iter().position(|&b| b == b'\n').map_or(data.len(), |i| start + i);
+        let key = data[start..end].split(|&b| b == b':').next().unwrap_or_default();
+        if compare_hash(key, hash) == Ordering::Less {
+            low = (end + 1).min(data.len());
+        } else {
+            high = start;
+        }
+    }
+    low
+}
+
+fn compare_hash(key: &[u8], hash: &[u8]) -> Ordering {
+    key.iter().map(u8::to_ascii_uppercase).cmp(hash.iter().copied())
+}
+
+fn partition_point<F: Fn(usize) -> bool>(len: usize, less: F) -> usize {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if less(middle) {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::breach::RangeSource;
+    use crate::breach::offline::{HashFile, write_binary};
+    use crate::dictionary::Dictionary;
+    use crate::rule::PasswordData;
+    use crate::rule::breached_password::{BreachedPasswordRule, ERROR_CODE};
+    use crate::rule::dictionary::DictionaryRule;
+    use crate::test::{RulePasswordTestItem, check_passwords};
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::PathBuf;
+
+    const TEXT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/test/breached-hashes.txt"
+    );
+
+    fn binary(name: &str) -> PathBuf {
+        let file = format!("passay-{name}-{}.bin", std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let input = BufReader::new(File::open(TEXT).unwrap());
+        assert_eq!(
+            16,
+            write_binary(input, File::create(&path).unwrap()).unwrap()
+        );
+        path
+    }
+
+    #[test]
+    fn test_lookup() {
+        let binary = binary("lookup");
+        for file in [HashFile::open(TEXT).unwrap(), HashFile::open(&binary).unwrap()] {
+            assert_eq!(16, file.len());
+            assert_eq!(Some(9545824), file.count_password("password"));
+            assert_eq!(
+                Some(42),
+                file.count("0e4cecb0f76c0600f8fc5995fa087260ba91640b")
+            );
+            assert_eq!(Some(40373), file.count_password("hunter2"));
+            assert_eq!(None, file.count_password("correct horse battery staple"));
+            assert_eq!(None, file.count("0"));
+            assert_eq!(None, file.count("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"));
+
+            let range = file.range("5baa6").unwrap();
+            assert_eq!(1, range.len());
+            assert_eq!("1E4C9B93F3F0682250B6CF8331B7EE68FD8", range[0].suffix);
+            assert!(file.range("00000").unwrap().is_empty());
+            assert_eq!(
+                Err("invalid hash prefix \"5BAA61\"".to_string()),
+                file.range("5BAA61")
+            );
+        }
+        std::fs::remove_file(binary).unwrap();
+    }
+
+    #[test]
+    fn test_rules() {
+        let binary = binary("rules");
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(
+                    BreachedPasswordRule::with_minimum_count(HashFile::open(TEXT).unwrap(), 100)
+                        .unwrap(),
+                ),
+                PasswordData::with_password("hunter2".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(
+                    BreachedPasswordRule::with_minimum_count(HashFile::open(&binary).unwrap(), 100)
+                        .unwrap(),
+                ),
+                PasswordData::with_password("correcthorse".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(DictionaryRule::from_dictionary(
+                    HashFile::with_minimum_count(&binary, 100).unwrap(),
+                )),
+                PasswordData::with_password("letmein".to_string()),
+                vec!["ILLEGAL_WORD"],
+            ),
+            RulePasswordTestItem(
+                Box::new(DictionaryRule::from_dictionary(
+                    HashFile::with_minimum_count(TEXT, 100).unwrap(),
+                )),
+                PasswordData::with_password("correcthorse".to_string()),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+        std::fs::remove_file(binary).unwrap();
+    }
+
+    #[test]
+    fn test_write_binary_errors() {
+        let errors = [
+            (
+                "5BAA6:1\n",
+                "line 1: expected a SHA-1 hash but found \"5BAA6\"",
+            ),
+            (
+                "7C4A8D09CA3762AF61E59520943DC26494F8941B:1\n5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:1\n",
+                "line 2: entries are not sorted by hash",
+            ),
+            (
+                "password\n",
+                "line 1: expected HASH:COUNT but found \"password\"",
+            ),
+            (
+                "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:4294967296\n",
+                "line 1: count 4294967296 is too large",
+            ),
+        ];
+        for (text, expected) in errors {
+            let error = write_binary(text.as_bytes(), vec![]).unwrap_err();
+            assert_eq!(expected, error.to_string());
+        }
+    }
+}
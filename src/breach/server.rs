@@ -0,0 +1,440 @@
+//! Minimal HTTP server answering `GET /range/{prefix}` in the format of the Have I Been Pwned range API, so that a
+//! [RangeSource] such as an offline [HashFile](crate::breach::offline::HashFile) can be shared with other services.
+//! The `passay-range-server` binary serves a hash file with it.
+use crate::breach::{PREFIX_LENGTH, RangeEntry, RangeSource};
+use log::{debug, warn};
+use rand::Rng;
+use std::collections::HashSet;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Take, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{Receiver, sync_channel};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const RANGE_PATH: &str = "/range/";
+/// Padded responses contain between this and [MAX_PADDED_ENTRIES] entries, like the responses of HIBP.
+const MIN_PADDED_ENTRIES: usize = 800;
+const MAX_PADDED_ENTRIES: usize = 1000;
+const SUFFIX_LENGTH: usize = 35;
+/// Maximum size of the request line and headers, larger requests are rejected.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+/// Pause after failing to accept a connection, so that a persistent error does not keep a core busy.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+pub const DEFAULT_WORKERS: usize = 16;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HTTP server for a [RangeSource]. Connections are handled by a fixed number of worker threads, further connections
+/// wait until a worker is free. A client has to send its request within the timeout and the request line and headers
+/// may not exceed 8 KiB, so that slow or malicious clients cannot hold a worker or exhaust memory.
+///
+/// Clients sending the `Add-Padding: true` header get responses padded with random entries with a count of zero.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::breach::offline::HashFile;
+///  use passay_rs::breach::server::RangeServer;
+///
+///  let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/test/breached-hashes.txt");
+///  let server = RangeServer::bind("127.0.0.1:0", HashFile::open(path).unwrap()).unwrap();
+///  println!("listening on http://{}/range/", server.local_addr().unwrap());
+///  std::thread::spawn(move || server.serve());
+/// ```
+pub struct RangeServer<S> {
+    listener: TcpListener,
+    source: Arc<S>,
+    workers: usize,
+    timeout: Duration,
+}
+
+impl<S> RangeServer<S>
+where
+    S: RangeSource + Send + Sync + 'static,
+{
+    pub fn bind<A: ToSocketAddrs>(address: A, source: S) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            source: Arc::new(source),
+            workers: DEFAULT_WORKERS,
+            timeout: DEFAULT_TIMEOUT,
+        })
+    }
+
+    /// Sets the number of connections handled at the same time, at least one.
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Sets the time a client has to send its request, and the time allowed for each write of the response, at least
+    /// one millisecond.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout.max(Duration::from_millis(1));
+        self
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections until all workers stopped. Failing to accept a connection, e.g. when the process runs out
+    /// of file descriptors, is logged and accepting continues after a short pause.
+    pub fn serve(self) -> io::Result<()> {
+        // accepted connections queue up to the number of workers, then accepting waits for a free worker
+        let (sender, receiver) = sync_channel(self.workers);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..self.workers {
+            let receiver = Arc::clone(&receiver);
+            let source = Arc::clone(&self.source);
+            let timeout = self.timeout;
+            thread::spawn(move || work(source.as_ref(), &receiver, timeout));
+        }
+        loop {
+            let connection = match self.listener.accept() {
+                Ok(connection) => connection,
+                Err(e) => {
+                    warn!("failed to accept a connection: {e}");
+                    thread::sleep(ACCEPT_RETRY_DELAY);
+                    continue;
+                }
+            };
+            if sender.send(connection).is_err() {
+                return Err(io::Error::other("all workers stopped"));
+            }
+        }
+    }
+}
+
+fn work<S: RangeSource>(
+    source: &S,
+    receiver: &Mutex<Receiver<(TcpStream, SocketAddr)>>,
+    timeout: Duration,
+) {
+    loop {
+        let next = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => return,
+        };
+        let Ok((stream, peer)) = next else {
+            return;
+        };
+        if let Err(e) = handle_connection(source, stream, timeout) {
+            warn!("failed to answer {peer}: {e}");
+        }
+    }
+}
+
+/// Reads from a stream until a deadline, so that a client sending slowly cannot extend the time allowed for the
+/// whole request.
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request timed out"));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str, body: &str) -> Self {
+        Self {
+            status,
+            body: body.to_string(),
+        }
+    }
+}
+
+fn handle_connection<S: RangeSource>(
+    source: &S,
+    mut stream: TcpStream,
+    timeout: Duration,
+) -> io::Result<()> {
+    stream.set_write_timeout(Some(timeout))?;
+    let deadline = DeadlineReader {
+        stream: stream.try_clone()?,
+        deadline: Instant::now() + timeout,
+    };
+    let mut reader = BufReader::new(deadline.take(MAX_REQUEST_BYTES));
+    let response = match read_request(&mut reader)? {
+        Some((request_line, padding)) => {
+            debug!("{}", request_line.trim());
+            respond(source, &request_line, padding)
+        }
+        None => Response::new("431 Request Header Fields Too Large", "Request too large"),
+    };
+    write_response(&mut stream, &response)
+}
+
+/// Reads the request line and whether padding was requested, or `None` if the request is too large.
+fn read_request<R: Read>(reader: &mut BufReader<Take<R>>) -> io::Result<Option<(String, bool)>> {
+    let Some(request_line) = read_line(reader)? else {
+        return Ok(None);
+    };
+    let mut padding = false;
+    loop {
+        let Some(header) = read_line(reader)? else {
+            return Ok(None);
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("add-padding")
+        {
+            padding = value.trim().eq_ignore_ascii_case("true");
+        }
+    }
+    Ok(Some((request_line, padding)))
+}
+
+/// Reads a line, or returns `None` if the size limit of the request is reached before the end of the line.
+fn read_line<R: Read>(reader: &mut BufReader<Take<R>>) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    reader.read_until(b'\n', &mut line)?;
+    if !line.ends_with(b"\n") && reader.get_ref().limit() == 0 {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn respond<S: RangeSource>(source: &S, request_line: &str, padding: bool) -> Response {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Response::new("400 Bad Request", "Malformed request");
+    };
+    let path = target.split('?').next().unwrap_or_default();
+    let Some(prefix) = path.strip_prefix(RANGE_PATH) else {
+        return Response::new("404 Not Found", "Not found");
+    };
+    if method != "GET" {
+        return Response::new("405 Method Not Allowed", "Method not allowed");
+    }
+    if prefix.len() != PREFIX_LENGTH || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Response::new(
+            "400 Bad Request",
+            "The hash prefix was not in a valid format",
+        );
+    }
+    match source.range(&prefix.to_ascii_uppercase()) {
+        Ok(mut entries) => {
+            if padding {
+                pad(&mut entries);
+            }
+            let body = entries
+                .iter()
+                .map(|entry| format!("{}:{}\r\n", entry.suffix, entry.count))
+                .collect();
+            Response {
+                status: "200 OK",
+                body,
+            }
+        }
+        Err(e) => {
+            warn!("range {prefix} failed: {e}");
+            Response::new("500 Internal Server Error", "Range lookup failed")
+        }
+    }
+}
+
+/// Adds random entries with a count of zero until the response has a random size between [MIN_PADDED_ENTRIES] and
+/// [MAX_PADDED_ENTRIES], keeping the entries sorted by suffix.
+fn pad(entries: &mut Vec<RangeEntry>) {
+    let mut rng = rand::rng();
+    let target = rng.random_range(MIN_PADDED_ENTRIES..=MAX_PADDED_ENTRIES);
+    let mut suffixes: HashSet<String> = entries.iter().map(|e| e.suffix.clone()).collect();
+    while entries.len() < target {
+        let suffix: String = (0..SUFFIX_LENGTH)
+            .map(|_| char::from_digit(rng.random_range(0..16), 16).unwrap().to_ascii_uppercase())
+            .collect();
+        if suffixes.insert(suffix.clone()) {
+            entries.push(RangeEntry { suffix, count: 0 });
+        }
+    }
+    entries.sort_by(|a, b| a.suffix.cmp(&b.suffix));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::breach::offline::HashFile;
+    use crate::breach::server::{
+        MAX_PADDED_ENTRIES, MAX_REQUEST_BYTES, MIN_PADDED_ENTRIES, RangeServer, respond,
+    };
+    use crate::breach::{RangeEntry, parse_range};
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+    use std::thread;
+    use std::time::Duration;
+
+    const TEXT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/test/breached-hashes.txt"
+    );
+
+    fn start_server() -> SocketAddr {
+        let server = RangeServer::bind("127.0.0.1:0", HashFile::open(TEXT).unwrap()).unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.serve());
+        address
+    }
+
+    fn get(address: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_respond() {
+        let file = HashFile::open(TEXT).unwrap();
+        let response = respond(&file, "GET /range/5baa6 HTTP/1.1", false);
+        assert_eq!("200 OK", response.status);
+        assert_eq!(
+            "1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\r\n",
+            response.body
+        );
+
+        let response = respond(&file, "GET /range/00000?mode=sha1 HTTP/1.1", false);
+        assert_eq!(("200 OK", ""), (response.status, response.body.as_str()));
+
+        let response = respond(&file, "GET /range/5BAA6 HTTP/1.1", true);
+        let entries = parse_range(&response.body).unwrap();
+        assert!((MIN_PADDED_ENTRIES..=MAX_PADDED_ENTRIES).contains(&entries.len()));
+        assert!(entries.is_sorted_by(|a, b| a.suffix < b.suffix));
+        assert_eq!(
+            vec![&RangeEntry {
+                suffix: "1E4C9B93F3F0682250B6CF8331B7EE68FD8".to_string(),
+                count: 9545824,
+            }],
+            entries.iter().filter(|e| e.count > 0).collect::<Vec<_>>()
+        );
+
+        let cases = [
+            ("GET /range/5BAA HTTP/1.1", "400 Bad Request"),
+            ("GET /range/5BAAG HTTP/1.1", "400 Bad Request"),
+            ("POST /range/5BAA6 HTTP/1.1", "405 Method Not Allowed"),
+            ("GET /pwnedpassword/5BAA6 HTTP/1.1", "404 Not Found"),
+            ("GET", "400 Bad Request"),
+        ];
+        for (request, status) in cases {
+            assert_eq!(status, respond(&file, request, false).status, "{request}");
+        }
+    }
+
+    #[test]
+    fn test_serve() {
+        let address = start_server();
+        let response = get(
+            address,
+            "GET /range/7C4A8 HTTP/1.1\r\nHost: localhost\r\nadd-padding: TRUE\r\n\r\n",
+        );
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{head}");
+        assert!(
+            head.contains(&format!("Content-Length: {}", body.len())),
+            "{head}"
+        );
+        let entries = parse_range(body).unwrap();
+        assert!(entries.len() >= MIN_PADDED_ENTRIES);
+        assert!(entries.contains(&RangeEntry {
+            suffix: "D09CA3762AF61E59520943DC26494F8941B".to_string(),
+            count: 37359195,
+        }));
+    }
+
+    #[test]
+    fn test_limits() {
+        let server = RangeServer::bind("127.0.0.1:0", HashFile::open(TEXT).unwrap())
+            .unwrap()
+            .with_workers(1)
+            .with_timeout(Duration::from_millis(200));
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.serve());
+
+        // an idle client holds the only worker until its request times out
+        let _idle = TcpStream::connect(address).unwrap();
+        let response = get(address, "GET /range/5BAA6 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+
+        let request = format!("GET /range/{}", "A".repeat(MAX_REQUEST_BYTES as usize));
+        let response = get(address, &request[..MAX_REQUEST_BYTES as usize]);
+        assert!(
+            response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"),
+            "{response}"
+        );
+    }
+
+    #[cfg(feature = "hibp")]
+    #[test]
+    fn test_breached_password_rule() {
+        use crate::breach::RangeSource;
+        use crate::breach::http::HttpRangeSource;
+        use crate::rule::PasswordData;
+        use crate::rule::breached_password::{BreachedPasswordRule, ERROR_CODE};
+        use crate::test::{RulePasswordTestItem, check_passwords};
+
+        let url = format!("http://{}/range/", start_server());
+        let source = || HttpRangeSource::new(&url, true);
+        assert_eq!(
+            HashFile::open(TEXT).unwrap().range("B1B37").unwrap(),
+            source()
+                .range("B1B37")
+                .unwrap()
+                .into_iter()
+                .filter(|e| e.count > 0)
+                .collect::<Vec<_>>()
+        );
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::new(source())),
+                PasswordData::with_password("qwerty".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::with_minimum_count(source(), 100).unwrap()),
+                PasswordData::with_password("correcthorse".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::new(source())),
+                PasswordData::with_password("Tr0ub4dor&3".to_string()),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+}
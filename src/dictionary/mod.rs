@@ -6,6 +6,14 @@ use std::io::Read;
 pub mod ternary_tree;
 pub mod word_lists;
 
+const COMMON_PASSWORDS: &str = include_str!("../../resources/common-passwords.txt");
+
+/// Returns a short list of the most commonly used passwords that is bundled with the crate. It is meant as a default
+/// blocklist, larger lists can be loaded with [DictionaryBuilder].
+pub fn common_passwords() -> Vec<String> {
+    COMMON_PASSWORDS.lines().filter(|s| !s.is_empty()).map(String::from).collect()
+}
+
 pub trait Dictionary {
     fn search(&self, word: &str) -> bool;
     fn len(&self) -> usize;
@@ -1535,15 +1535,9 @@ impl<'a, 'b, T> Iterator for TstNeighborIterator<'a, 'b, T> {
 
                     if let Some(ref value) = node.value {
                         let delta = match label {
-                            None => 1,
-
-                            Some(label) => {
-                                if label == node.label {
-                                    0
-                                } else {
-                                    1
-                                }
-                            }
+                            Some(label) if label == node.label => 0,
+
+                            _ => 1,
                         };
 
                         if range >= delta {
@@ -1563,15 +1557,9 @@ impl<'a, 'b, T> Iterator for TstNeighborIterator<'a, 'b, T> {
                     self.todo_i.push((node, GoRight, label, key_tail.clone(), tail_len, range));
 
                     let delta = match label {
-                        None => 1,
+                        Some(label) if label == node.label => 0,
 
-                        Some(label) => {
-                            if label == node.label {
-                                0
-                            } else {
-                                1
-                            }
-                        }
+                        _ => 1,
                     };
 
                     if range >= delta {
@@ -1643,15 +1631,9 @@ impl<'a, 'b, T> DoubleEndedIterator for TstNeighborIterator<'a, 'b, T> {
 
                     if let Some(ref value) = node.value {
                         let delta = match label {
-                            None => 1,
-
-                            Some(label) => {
-                                if label == node.label {
-                                    0
-                                } else {
-                                    1
-                                }
-                            }
+                            Some(label) if label == node.label => 0,
+
+                            _ => 1,
                         };
 
                         if range >= delta {
@@ -1671,15 +1653,9 @@ impl<'a, 'b, T> DoubleEndedIterator for TstNeighborIterator<'a, 'b, T> {
                     self.todo_j.push((node, Visit, label, key_tail.clone(), tail_len, range));
 
                     let delta = match label {
-                        None => 1,
+                        Some(label) if label == node.label => 0,
 
-                        Some(label) => {
-                            if label == node.label {
-                                0
-                            } else {
-                                1
-                            }
-                        }
+                        _ => 1,
                     };
 
                     if range >= delta {
@@ -265,10 +265,10 @@ mod tests {
         assert_eq!(&sorted_array[..], &array[..]);
     }
 
-    fn do_sort(array: &mut Vec<String>, sort: impl ArraySorter, algorithm_name: &str) {
+    fn do_sort(array: &mut [String], sort: impl ArraySorter, algorithm_name: &str) {
         let start = Instant::now();
         debug!("before sort:{:?}", &array[..]);
-        sort.sort(&mut array[..]);
+        sort.sort(array);
         debug!("after sort:{:?}", &array[..]);
 
         let duration = start.elapsed();
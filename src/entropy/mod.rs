@@ -2,10 +2,12 @@ use crate::rule::PasswordData;
 use crate::rule::Rule;
 use crate::rule::character::CharacterRule;
 use crate::rule::character_characteristics::CharacterCharacteristics;
-use crate::rule::character_data::EnglishCharacterData;
+use crate::rule::character_data::{CharacterData, EnglishCharacterData};
 use std::collections::HashSet;
 use std::f64;
 
+pub mod pattern;
+
 pub trait Entropy {
     /// Returns the estimated entropy bits of a password.
     fn estimate(&self) -> f64;
@@ -15,6 +17,10 @@ pub trait Entropy {
 /// see <http://csrc.nist.gov/publications/nistpubs/800-63-1/SP-800-63-1.pdf>
 /// A1. "Randomly Selected Passwords"
 ///
+/// The alphabet is made of the characters of the rules; character data defined by a
+/// [category](crate::rule::character_data::CharacterData::category) adds its
+/// [size](crate::rule::character_data::CharacterData::size) to it.
+///
 /// # Example
 ///
 /// ```
@@ -50,19 +56,39 @@ impl RandomPasswordEntropy {
     ) -> Result<Self, &'static str> {
         // TODO check password data origin
         let mut unique_chars = HashSet::<char>::new();
+        let mut categories = Vec::<&dyn CharacterData>::new();
 
         for rule in rules {
             if let Some(ccc) = rule.as_has_characters() {
-                unique_chars.extend(ccc.characters().chars())
+                unique_chars.extend(ccc.characters().chars());
+                for data in ccc.categories() {
+                    if !categories.iter().any(|c| c.category() == data.category()) {
+                        categories.push(data);
+                    }
+                }
             }
         }
-        if unique_chars.is_empty() {
+        unique_chars.retain(|c| !categories.iter().any(|data| data.contains(*c)));
+        let mut alphabet_size = unique_chars.len();
+        for (i, data) in categories.iter().enumerate() {
+            let earlier = &categories[..i];
+            alphabet_size += if earlier.is_empty() {
+                data.size()
+            } else {
+                // overlapping categories, e.g. letters and uppercase letters, count their characters once
+                data.characters()
+                    .chars()
+                    .filter(|c| !earlier.iter().any(|e| e.contains(*c)))
+                    .count()
+            };
+        }
+        if alphabet_size == 0 {
             return Err(
                 "Password rules must contain at least 1 unique character by CharacterRule definition",
             );
         }
         Ok(RandomPasswordEntropy {
-            alphabet_size: unique_chars.len(),
+            alphabet_size,
             password_size: password_data.password().len(),
         })
     }
@@ -71,8 +97,8 @@ impl Entropy for RandomPasswordEntropy {
     fn estimate(&self) -> f64 {
         let base = self.alphabet_size as f64;
         let exponent = self.password_size as f64;
-        let power_result = base.powf(exponent);
-        log2(power_result)
+        // log2(base^exponent) without computing the power, which overflows for long passwords
+        exponent * log2(base)
     }
 }
 
@@ -224,7 +250,7 @@ mod tests {
     use crate::rule::allowed_character::AllowedCharacter;
     use crate::rule::character::CharacterRule;
     use crate::rule::character_characteristics::CharacterCharacteristics;
-    use crate::rule::character_data::EnglishCharacterData;
+    use crate::rule::character_data::{CharacterData, EnglishCharacterData, UnicodeCharacterData};
     use crate::rule::{PasswordData, Rule};
 
     // TODO need more tests for entropy
@@ -239,6 +265,45 @@ mod tests {
         assert_eq!(28.50219859070546, ent);
     }
 
+    #[test]
+    fn test_random_entropy_unicode() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(AllowedCharacter::from_chars("abcdefghijklmnopqrstuvwxyzL")),
+            Box::new(CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 1).unwrap()),
+        ];
+        let entropy = RandomPasswordEntropy::new(
+            rules.as_slice(),
+            &PasswordData::with_password("heLlo".to_string()),
+        )
+        .unwrap();
+        // L is an uppercase letter and counted once
+        assert_eq!(
+            26 + UnicodeCharacterData::UpperCase.size(),
+            entropy.alphabet_size
+        );
+    }
+
+    #[test]
+    fn test_random_entropy_unicode_only() {
+        let ch_rules = vec![
+            CharacterRule::new(Box::new(UnicodeCharacterData::Alphabetical), 1).unwrap(),
+            CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 1).unwrap(),
+        ];
+        let rules: Vec<Box<dyn Rule>> = vec![Box::new(
+            CharacterCharacteristics::with_rules_and_characteristics(ch_rules, 2).unwrap(),
+        )];
+        let entropy = RandomPasswordEntropy::new(
+            rules.as_slice(),
+            &PasswordData::with_password("heLlo".to_string()),
+        )
+        .unwrap();
+        // uppercase letters are already part of the letters
+        assert_eq!(
+            UnicodeCharacterData::Alphabetical.size(),
+            entropy.alphabet_size
+        );
+    }
+
     #[test]
     fn test_shannon_entropy() {
         let entropy = ShannonEntropy::from_rules(
@@ -0,0 +1,881 @@
+use crate::dictionary::word_lists::ArrayWordList;
+use crate::dictionary::word_lists::sort::SliceSort;
+use crate::dictionary::word_lists::word_list_dictionary::WordListDictionary;
+use crate::dictionary::{Dictionary, common_passwords};
+use crate::entropy::Entropy;
+use crate::rule::PasswordData;
+use crate::rule::date_pattern::{DateMatch, DatePatternRule};
+use crate::rule::keyboard_walk::{KeyboardGraph, KeyboardLayout, KeyboardWalk};
+use crate::rule::repeated_substring::repeats;
+use crate::rule::sequence_data::{EnglishSequenceData, SequenceData};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the dictionary of the bundled common passwords.
+pub const COMMON_PASSWORDS: &str = "commonPasswords";
+/// Name of the dictionary that contains the username of the password data.
+pub const USERNAME: &str = "username";
+
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_SUBMATCH_GUESSES_MULTI_CHAR: f64 = 50.0;
+const MIN_GUESSES_BEFORE_GROWING_SEQUENCE: f64 = 10000.0;
+const MIN_YEAR_SPACE: f64 = 20.0;
+const MIN_WORD_LENGTH: usize = 3;
+const MIN_SEQUENCE_LENGTH: usize = 3;
+const MIN_WALK_LENGTH: usize = 3;
+/// Number of leading characters searched for matches, longer passwords are guessed by brute force after it.
+const MAX_MATCH_LENGTH: usize = 100;
+/// Guesses needed to reach the scores 1 to 4.
+const SCORE_THRESHOLDS: [f64; 4] = [1e3, 1e6, 1e8, 1e10];
+const SCORE_DELTA: f64 = 5.0;
+const SHIFTED_CHARACTERS: &str = "~!@#$%^&*()_+{}|:\"<>?";
+const SECONDS_PER_YEAR: u64 = 31_556_952;
+
+/// The kind of a part of a password found by the [PatternEstimator].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// A word of a dictionary, compared in lower case and optionally reversed.
+    Dictionary {
+        dictionary: String,
+        word: String,
+        /// Position of the word in a ranked word list, or the expected position of a word of an unranked dictionary.
+        rank: usize,
+        reversed: bool,
+    },
+    /// Consecutive characters of a [SequenceData], e.g. `abcd` or `9876`.
+    Sequence { ascending: bool },
+    /// A block of characters repeated in a row, e.g. `abcabc`.
+    Repeat { block: String, count: usize },
+    /// A date, two digit years are expanded to the 20th or 21st century.
+    Date {
+        year: u16,
+        month: Option<u8>,
+        day: Option<u8>,
+    },
+    /// A walk across adjacent keys of the keyboard.
+    KeyboardWalk { turns: usize, shifted: usize },
+    /// Characters not covered by any other pattern.
+    Bruteforce,
+}
+
+impl Pattern {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pattern::Dictionary { .. } => "dictionary",
+            Pattern::Sequence { .. } => "sequence",
+            Pattern::Repeat { .. } => "repeat",
+            Pattern::Date { .. } => "date",
+            Pattern::KeyboardWalk { .. } => "keyboardWalk",
+            Pattern::Bruteforce => "bruteforce",
+        }
+    }
+}
+
+/// A part of a password matched by a pattern, with the guesses needed to find it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternMatch {
+    pub pattern: Pattern,
+    /// Index of the first character of the match in the password.
+    pub start: usize,
+    /// Index after the last character of the match in the password.
+    pub end: usize,
+    pub token: String,
+    pub guesses: f64,
+}
+
+/// Result of the [PatternEstimator]: the decomposition of a password into the matches that need the fewest guesses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternEstimate {
+    guesses: f64,
+    matches: Vec<PatternMatch>,
+}
+
+impl PatternEstimate {
+    /// Returns the estimated number of guesses needed to find the password.
+    pub fn guesses(&self) -> f64 {
+        self.guesses
+    }
+
+    /// Returns the base 2 logarithm of the guesses.
+    pub fn bits(&self) -> f64 {
+        self.guesses.log2()
+    }
+
+    /// Returns a score from 0 (too guessable) to 4 (very unguessable), with thresholds at 10^3, 10^6, 10^8 and 10^10
+    /// guesses.
+    pub fn score(&self) -> u8 {
+        SCORE_THRESHOLDS
+            .iter()
+            .take_while(|t| self.guesses >= **t + SCORE_DELTA)
+            .count() as u8
+    }
+
+    /// Returns the matches covering the password in order, including [Pattern::Bruteforce] matches.
+    pub fn matches(&self) -> &[PatternMatch] {
+        &self.matches
+    }
+}
+
+impl Entropy for PatternEstimate {
+    fn estimate(&self) -> f64 {
+        self.bits()
+    }
+}
+
+struct RankedDictionary {
+    name: String,
+    dictionary: Box<dyn Dictionary>,
+    ranks: HashMap<String, usize>,
+}
+
+impl RankedDictionary {
+    fn rank(&self, word: &str) -> Option<usize> {
+        if !self.dictionary.search(word) {
+            return None;
+        }
+        let rank = self.ranks.get(word).copied();
+        Some(rank.unwrap_or(self.dictionary.len().div_ceil(2)).max(1))
+    }
+}
+
+/// Positions of the characters of all forms of a character sequence.
+struct SequenceIndex {
+    positions: HashMap<char, usize>,
+    length: usize,
+}
+
+/// Password strength estimator in the spirit of zxcvbn. The password is broken into matches found by this crate's
+/// detectors: dictionary words, character sequences, repeated blocks, dates and keyboard walks. The decomposition
+/// needing the fewest guesses, with characters not covered by any match guessed by brute force, determines the
+/// estimate. The estimator works offline, by default with the bundled [common_passwords] ranked by their position,
+/// the English alphabetical and numerical sequences, the US QWERTY keyboard and the default date formats. The
+/// username of the password data is matched as a word of rank 1. Only the first 100 characters are searched for
+/// matches, the rest of a longer password is guessed by brute force.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::entropy::pattern::{Pattern, PatternEstimator};
+///  use passay_rs::rule::PasswordData;
+///
+///  let estimator = PatternEstimator::default();
+///  let estimate = estimator.estimate(&PasswordData::with_password("Password1985".to_string()));
+///  assert_eq!(1, estimate.score());
+///  let patterns: Vec<&str> = estimate.matches().iter().map(|m| m.pattern.name()).collect();
+///  assert_eq!(vec!["dictionary", "date"], patterns);
+///  assert!(matches!(estimate.matches()[0].pattern, Pattern::Dictionary { rank: 2, .. }));
+/// ```
+pub struct PatternEstimator {
+    dictionaries: Vec<RankedDictionary>,
+    sequences: Vec<SequenceIndex>,
+    keyboard: KeyboardGraph,
+    key_count: f64,
+    average_degree: f64,
+    dates: DatePatternRule,
+    reference_year: u16,
+}
+
+impl Default for PatternEstimator {
+    fn default() -> Self {
+        Self::new()
+            .add_ranked_words(COMMON_PASSWORDS, common_passwords())
+            .add_sequence_data(&EnglishSequenceData::Alphabetical)
+            .add_sequence_data(&EnglishSequenceData::Numerical)
+    }
+}
+
+impl PatternEstimator {
+    /// Creates an estimator without dictionaries and sequences, using the US QWERTY keyboard and the default date
+    /// formats.
+    pub fn new() -> Self {
+        let year = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| 1970 + d.as_secs() / SECONDS_PER_YEAR)
+            .unwrap_or(2000);
+        let keyboard = KeyboardLayout::USQwerty.graph();
+        Self {
+            dictionaries: vec![],
+            sequences: vec![],
+            key_count: keyboard.key_count() as f64,
+            average_degree: keyboard.average_degree(),
+            keyboard,
+            dates: DatePatternRule::default(),
+            reference_year: year as u16,
+        }
+    }
+
+    /// Adds a dictionary whose words are all equally likely. Words are searched in lower case.
+    pub fn add_dictionary(mut self, name: &str, dictionary: Box<dyn Dictionary>) -> Self {
+        self.dictionaries.push(RankedDictionary {
+            name: name.to_string(),
+            dictionary,
+            ranks: HashMap::new(),
+        });
+        self
+    }
+
+    /// Adds a word list ordered from the most to the least likely word.
+    pub fn add_ranked_words(mut self, name: &str, words: Vec<String>) -> Self {
+        let mut ranks = HashMap::new();
+        for (index, word) in words.iter().enumerate() {
+            ranks.entry(word.to_lowercase()).or_insert(index + 1);
+        }
+        let word_list = ArrayWordList::with_sorter(words, false, Some(SliceSort));
+        self.dictionaries.push(RankedDictionary {
+            name: name.to_string(),
+            dictionary: Box::new(WordListDictionary::new(word_list)),
+            ranks,
+        });
+        self
+    }
+
+    /// Adds the sequences of the given sequence data. Keyboard rows are better described by [Self::keyboard].
+    pub fn add_sequence_data(mut self, data: &dyn SequenceData) -> Self {
+        for sequence in data.get_sequences() {
+            let mut positions = HashMap::new();
+            for form in sequence.get_forms() {
+                for (index, c) in form.chars().enumerate() {
+                    if c != '\u{0}' {
+                        positions.entry(c).or_insert(index);
+                    }
+                }
+            }
+            self.sequences.push(SequenceIndex {
+                positions,
+                length: sequence.length(),
+            });
+        }
+        self
+    }
+
+    /// Sets the keyboard used to find keyboard walks.
+    pub fn keyboard(mut self, keyboard: KeyboardGraph) -> Self {
+        self.key_count = keyboard.key_count() as f64;
+        self.average_degree = keyboard.average_degree();
+        self.keyboard = keyboard;
+        self
+    }
+
+    /// Sets the rule used to find dates.
+    pub fn dates(mut self, dates: DatePatternRule) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    /// Sets the year dates are compared with, by default the current year. Years close to it are guessed first.
+    pub fn reference_year(mut self, year: u16) -> Self {
+        self.reference_year = year;
+        self
+    }
+
+    /// Estimates the guesses needed to find the password of the given password data.
+    pub fn estimate(&self, password_data: &PasswordData) -> PatternEstimate {
+        let username = password_data
+            .username()
+            .map(|u| u.chars().map(lower).collect::<String>())
+            .filter(|u| u.chars().count() >= MIN_WORD_LENGTH);
+        self.most_guessable(password_data.password(), username.as_deref())
+    }
+
+    fn most_guessable(&self, password: &str, username: Option<&str>) -> PatternEstimate {
+        let chars: Vec<char> = password.chars().collect();
+        let n = chars.len();
+        if n == 0 {
+            return PatternEstimate {
+                guesses: 1.0,
+                matches: vec![],
+            };
+        }
+        if n > MAX_MATCH_LENGTH {
+            return self.truncated(&chars, username);
+        }
+        let mut candidates = self.find_matches(password, &chars, username);
+        // optimal[k] maps the number of matches of the best decomposition of the first k + 1 characters to it
+        let mut optimal: Vec<BTreeMap<usize, Step>> = vec![BTreeMap::new(); n];
+        let mut by_end: Vec<Vec<usize>> = vec![vec![]; n];
+        for (index, m) in candidates.iter().enumerate() {
+            by_end[m.end - 1].push(index);
+        }
+        for (k, ending) in by_end.iter().enumerate() {
+            for &index in ending {
+                extend(&mut optimal, &candidates, index);
+            }
+            for start in 0..=k {
+                let follows_match = start == 0
+                    || optimal[start - 1]
+                        .values()
+                        .any(|step| candidates[step.index].pattern != Pattern::Bruteforce);
+                if follows_match {
+                    candidates.push(bruteforce(&chars, start, k + 1));
+                    extend(&mut optimal, &candidates, candidates.len() - 1);
+                }
+            }
+        }
+        let (mut length, best) = optimal[n - 1]
+            .iter()
+            .min_by(|a, b| a.1.guesses.total_cmp(&b.1.guesses))
+            .map(|(l, step)| (*l, step.guesses))
+            .unwrap();
+        let mut matches = Vec::with_capacity(length);
+        let mut k = n;
+        while k > 0 {
+            let m = &candidates[optimal[k - 1][&length].index];
+            k = m.start;
+            length -= 1;
+            matches.push(m.clone());
+        }
+        matches.reverse();
+        PatternEstimate {
+            guesses: best,
+            matches,
+        }
+    }
+
+    /// Estimates the first [MAX_MATCH_LENGTH] characters and adds the rest as a brute force match.
+    fn truncated(&self, chars: &[char], username: Option<&str>) -> PatternEstimate {
+        let prefix: String = chars[..MAX_MATCH_LENGTH].iter().collect();
+        let mut matches = self.most_guessable(&prefix, username).matches;
+        // brute force matches never follow each other
+        let start = match matches.last() {
+            Some(m) if m.pattern == Pattern::Bruteforce => matches.pop().unwrap().start,
+            _ => MAX_MATCH_LENGTH,
+        };
+        matches.push(bruteforce(chars, start, chars.len()));
+        let product: f64 = matches.iter().map(|m| m.guesses).product();
+        let length = matches.len();
+        let guesses = factorial(length) * product
+            + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(length as i32 - 1);
+        PatternEstimate {
+            guesses: guesses.min(f64::MAX),
+            matches,
+        }
+    }
+
+    fn find_matches(
+        &self,
+        password: &str,
+        chars: &[char],
+        username: Option<&str>,
+    ) -> Vec<PatternMatch> {
+        let mut matches = self.dictionary_matches(chars, username);
+        matches.extend(self.sequence_matches(chars));
+        matches.extend(self.repeat_matches(chars, username));
+        matches.extend(self.dates.find_dates(password).iter().map(|d| self.date_match(d)));
+        for walk in self.keyboard.walks(password, MIN_WALK_LENGTH, usize::MAX) {
+            matches.push(self.walk_match(&walk));
+        }
+        for m in &mut matches {
+            let length = m.end - m.start;
+            if length < chars.len() {
+                let min = if length == 1 {
+                    MIN_SUBMATCH_GUESSES_SINGLE_CHAR
+                } else {
+                    MIN_SUBMATCH_GUESSES_MULTI_CHAR
+                };
+                m.guesses = m.guesses.max(min);
+            }
+        }
+        matches
+    }
+
+    fn dictionary_matches(&self, chars: &[char], username: Option<&str>) -> Vec<PatternMatch> {
+        let lowered: Vec<char> = chars.iter().map(|c| lower(*c)).collect();
+        let mut matches = Vec::new();
+        for start in 0..chars.len() {
+            for end in start + MIN_WORD_LENGTH..=chars.len() {
+                let word: String = lowered[start..end].iter().collect();
+                let reversed: String = lowered[start..end].iter().rev().collect();
+                let token = &chars[start..end];
+                let mut add = |dictionary: &str, word: &str, rank: usize, is_reversed: bool| {
+                    let mut guesses = rank as f64 * uppercase_variations(token);
+                    if is_reversed {
+                        guesses *= 2.0;
+                    }
+                    matches.push(PatternMatch {
+                        pattern: Pattern::Dictionary {
+                            dictionary: dictionary.to_string(),
+                            word: word.to_string(),
+                            rank,
+                            reversed: is_reversed,
+                        },
+                        start,
+                        end,
+                        token: token.iter().collect(),
+                        guesses,
+                    });
+                };
+                if username == Some(word.as_str()) {
+                    add(USERNAME, &word, 1, false);
+                } else if word != reversed && username == Some(reversed.as_str()) {
+                    add(USERNAME, &reversed, 1, true);
+                }
+                for dictionary in &self.dictionaries {
+                    if let Some(rank) = dictionary.rank(&word) {
+                        add(&dictionary.name, &word, rank, false);
+                    }
+                    if word != reversed
+                        && let Some(rank) = dictionary.rank(&reversed)
+                    {
+                        add(&dictionary.name, &reversed, rank, true);
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    fn sequence_matches(&self, chars: &[char]) -> Vec<PatternMatch> {
+        let mut matches = Vec::new();
+        for sequence in &self.sequences {
+            let indices: Vec<Option<usize>> =
+                chars.iter().map(|c| sequence.positions.get(c).copied()).collect();
+            let next = |from: Option<usize>, to: Option<usize>, ascending: bool| match (from, to) {
+                (Some(a), Some(b)) if ascending => b == a + 1,
+                (Some(a), Some(b)) => a == b + 1,
+                _ => false,
+            };
+            let mut start = 0;
+            while start + 1 < chars.len() {
+                let ascending = if next(indices[start], indices[start + 1], true) {
+                    true
+                } else if next(indices[start], indices[start + 1], false) {
+                    false
+                } else {
+                    start += 1;
+                    continue;
+                };
+                let mut end = start + 2;
+                while end < chars.len() && next(indices[end - 1], indices[end], ascending) {
+                    end += 1;
+                }
+                let length = end - start;
+                if length >= MIN_SEQUENCE_LENGTH {
+                    let first = indices[start].unwrap();
+                    // sequences starting at either end are guessed first
+                    let mut guesses = if first == 0 || first + 1 == sequence.length {
+                        4.0
+                    } else {
+                        sequence.length as f64
+                    };
+                    if !ascending {
+                        guesses *= 2.0;
+                    }
+                    matches.push(PatternMatch {
+                        pattern: Pattern::Sequence { ascending },
+                        start,
+                        end,
+                        token: chars[start..end].iter().collect(),
+                        guesses: guesses * length as f64,
+                    });
+                }
+                start = end - 1;
+            }
+        }
+        matches
+    }
+
+    fn repeat_matches(&self, chars: &[char], username: Option<&str>) -> Vec<PatternMatch> {
+        let mut matches = Vec::new();
+        for start in 0..chars.len() {
+            let found: Vec<(usize, usize)> = (1..=(chars.len() - start) / 2)
+                .map(|length| (length, repeats(chars, start, length)))
+                .filter(|(_, count)| *count >= 2)
+                .collect();
+            let Some(&shortest) = found.first() else {
+                continue;
+            };
+            // the shortest block and the block covering the most characters
+            let longest = *found
+                .iter()
+                .max_by_key(|(length, count)| (length * count, Reverse(*length)))
+                .unwrap();
+            let mut blocks = vec![shortest];
+            if longest != shortest {
+                blocks.push(longest);
+            }
+            for (length, count) in blocks {
+                let block: String = chars[start..start + length].iter().collect();
+                let base = self.most_guessable(&block, username).guesses;
+                matches.push(PatternMatch {
+                    pattern: Pattern::Repeat { block, count },
+                    start,
+                    end: start + length * count,
+                    token: chars[start..start + length * count].iter().collect(),
+                    guesses: base * count as f64,
+                });
+            }
+        }
+        matches
+    }
+
+    fn date_match(&self, date: &DateMatch) -> PatternMatch {
+        let year = match date.year {
+            year if !date.two_digit_year => year,
+            year if year < 50 => 2000 + year,
+            year => 1900 + year,
+        };
+        let year_space = (year.abs_diff(self.reference_year) as f64).max(MIN_YEAR_SPACE);
+        let mut guesses = match (date.month, date.day) {
+            (Some(_), Some(_)) => year_space * 365.0,
+            (Some(_), None) => year_space * 12.0,
+            _ => year_space,
+        };
+        if date.format.chars().any(|c| !matches!(c, 'd' | 'm' | 'y')) {
+            guesses *= 4.0;
+        }
+        PatternMatch {
+            pattern: Pattern::Date {
+                year,
+                month: date.month,
+                day: date.day,
+            },
+            start: date.start,
+            end: date.end,
+            token: date.text.clone(),
+            guesses,
+        }
+    }
+
+    fn walk_match(&self, walk: &KeyboardWalk) -> PatternMatch {
+        // a walk without direction changes consists of a single segment
+        let segments = walk.turns + 1;
+        let mut guesses = 0.0;
+        for i in 2..=walk.length {
+            for j in 1..=segments.min(i - 1) {
+                guesses +=
+                    binomial(i - 1, j - 1) * self.key_count * self.average_degree.powi(j as i32);
+            }
+        }
+        let shifted = walk
+            .sequence
+            .chars()
+            .filter(|c| c.is_uppercase() || SHIFTED_CHARACTERS.contains(*c))
+            .count();
+        guesses *= variations(shifted, walk.length - shifted);
+        PatternMatch {
+            pattern: Pattern::KeyboardWalk {
+                turns: walk.turns,
+                shifted,
+            },
+            start: walk.start,
+            end: walk.start + walk.length,
+            token: walk.sequence.clone(),
+            guesses,
+        }
+    }
+}
+
+/// The best decomposition ending at a position with a given number of matches.
+#[derive(Clone)]
+struct Step {
+    /// Index of the last match of the decomposition.
+    index: usize,
+    /// Product of the guesses of all matches of the decomposition.
+    product: f64,
+    guesses: f64,
+}
+
+/// Extends the decompositions ending before the given match with it. Brute force matches never follow each other.
+fn extend(optimal: &mut [BTreeMap<usize, Step>], candidates: &[PatternMatch], index: usize) {
+    let m = &candidates[index];
+    let previous: Vec<(usize, f64)> = if m.start == 0 {
+        vec![(0, 1.0)]
+    } else {
+        optimal[m.start - 1]
+            .iter()
+            .filter(|(_, step)| {
+                m.pattern != Pattern::Bruteforce
+                    || candidates[step.index].pattern != Pattern::Bruteforce
+            })
+            .map(|(l, step)| (*l, step.product))
+            .collect()
+    };
+    let k = m.end - 1;
+    for (length, product) in previous {
+        let length = length + 1;
+        let product = product * m.guesses;
+        // a decomposition with more matches is penalized for the order of its matches and their number
+        let guesses = factorial(length) * product
+            + MIN_GUESSES_BEFORE_GROWING_SEQUENCE.powi(length as i32 - 1);
+        if optimal[k].iter().any(|(l, step)| *l <= length && step.guesses <= guesses) {
+            continue;
+        }
+        optimal[k].insert(
+            length,
+            Step {
+                index,
+                product,
+                guesses,
+            },
+        );
+    }
+}
+
+fn bruteforce(chars: &[char], start: usize, end: usize) -> PatternMatch {
+    let length = end - start;
+    let min = if length == 1 {
+        MIN_SUBMATCH_GUESSES_SINGLE_CHAR + 1.0
+    } else {
+        MIN_SUBMATCH_GUESSES_MULTI_CHAR + 1.0
+    };
+    PatternMatch {
+        pattern: Pattern::Bruteforce,
+        start,
+        end,
+        token: chars[start..end].iter().collect(),
+        guesses: BRUTEFORCE_CARDINALITY.powi(length as i32).clamp(min, f64::MAX),
+    }
+}
+
+fn lower(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
+    }
+}
+
+/// Returns the number of ways to capitalize a word: lower case words are guessed first, then words with only the
+/// first or last letter or all letters in upper case, then any other mix.
+fn uppercase_variations(token: &[char]) -> f64 {
+    let upper = token.iter().filter(|c| c.is_uppercase()).count();
+    let lower = token.iter().filter(|c| c.is_lowercase()).count();
+    if upper == 1 && (token[0].is_uppercase() || token[token.len() - 1].is_uppercase()) {
+        return 2.0;
+    }
+    variations(upper, lower)
+}
+
+/// Returns the number of ways to change `changed` of `changed + unchanged` characters, counting at most half of them.
+fn variations(changed: usize, unchanged: usize) -> f64 {
+    if changed == 0 {
+        1.0
+    } else if unchanged == 0 {
+        2.0
+    } else {
+        (1..=changed.min(unchanged)).map(|i| binomial(changed + unchanged, i)).sum()
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (1..=k).fold(1.0, |result, d| result * (n + 1 - d) as f64 / d as f64)
+}
+
+fn factorial(n: usize) -> f64 {
+    (2..=n).fold(1.0, |result, i| result * i as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dictionary::DictionaryBuilder;
+    use crate::entropy::Entropy;
+    use crate::entropy::pattern::{
+        COMMON_PASSWORDS, MAX_MATCH_LENGTH, Pattern, PatternEstimate, PatternEstimator, USERNAME,
+    };
+    use crate::rule::PasswordData;
+    use crate::rule::Rule;
+    use crate::rule::minimum_entropy::MinimumEntropyRule;
+
+    fn estimator() -> PatternEstimator {
+        PatternEstimator::default().reference_year(2025)
+    }
+
+    fn estimate(estimator: &PatternEstimator, password: &str) -> PatternEstimate {
+        estimator.estimate(&PasswordData::with_password(password.to_string()))
+    }
+
+    fn patterns(estimator: &PatternEstimator, password: &str) -> Vec<Pattern> {
+        let estimate = estimate(estimator, password);
+        estimate.matches().iter().map(|m| m.pattern.clone()).collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        let estimate = estimate(&estimator(), "");
+        assert_eq!(1.0, estimate.guesses());
+        assert_eq!(0.0, estimate.bits());
+        assert_eq!(0, estimate.score());
+        assert!(estimate.matches().is_empty());
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let estimator = estimator();
+        let password = estimate(&estimator, "password");
+        assert_eq!(3.0, password.guesses());
+        assert_eq!(3f64.log2(), password.estimate());
+        assert_eq!(0, password.score());
+        assert_eq!(
+            vec![Pattern::Dictionary {
+                dictionary: COMMON_PASSWORDS.to_string(),
+                word: "password".to_string(),
+                rank: 2,
+                reversed: false,
+            }],
+            patterns(&estimator, "password")
+        );
+
+        // capitalized and reversed words need more guesses
+        assert_eq!(5.0, estimate(&estimator, "PASSWORD").guesses());
+        assert_eq!(5.0, estimate(&estimator, "drowssap").guesses());
+        assert!(matches!(
+            patterns(&estimator, "drowssap")[0],
+            Pattern::Dictionary { reversed: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_custom_dictionaries() {
+        let estimator = PatternEstimator::new()
+            .add_ranked_words("names", vec!["alice".to_string(), "bob".to_string()])
+            .add_dictionary(
+                "words",
+                Box::new(
+                    DictionaryBuilder::new()
+                        .add_read(Box::new("correct\nhorse\nbattery\nstaple\n".as_bytes()))
+                        .build(),
+                ),
+            );
+        let estimate = estimate(&estimator, "bobhorse");
+        let names: Vec<(&str, usize)> = estimate
+            .matches()
+            .iter()
+            .map(|m| match &m.pattern {
+                Pattern::Dictionary {
+                    dictionary, rank, ..
+                } => (dictionary.as_str(), *rank),
+                p => panic!("unexpected pattern {p:?}"),
+            })
+            .collect();
+        assert_eq!(vec![("names", 2), ("words", 2)], names);
+    }
+
+    #[test]
+    fn test_username() {
+        let estimator = estimator();
+        let password = "Alicia#x".to_string();
+        let without = estimate(&estimator, &password);
+        let with = estimator.estimate(&PasswordData::with_password_and_user(
+            password,
+            Some("alicia".to_string()),
+        ));
+        assert!(with.guesses() < without.guesses());
+        assert!(matches!(
+            &with.matches()[0].pattern,
+            Pattern::Dictionary { dictionary, rank: 1, .. } if dictionary == USERNAME
+        ));
+    }
+
+    #[test]
+    fn test_patterns() {
+        let estimator = estimator();
+        assert_eq!(
+            vec![Pattern::Sequence { ascending: true }],
+            patterns(&estimator, "abcdefg")
+        );
+        assert_eq!(
+            vec![Pattern::Sequence { ascending: false }],
+            patterns(&estimator, "76543")
+        );
+        assert_eq!(
+            vec![Pattern::Repeat {
+                block: "x".to_string(),
+                count: 9,
+            }],
+            patterns(&estimator, "xxxxxxxxx")
+        );
+        assert_eq!(
+            vec![Pattern::Date {
+                year: 1985,
+                month: Some(7),
+                day: Some(3),
+            }],
+            patterns(&estimator, "03.07.1985")
+        );
+        assert_eq!(
+            vec![Pattern::KeyboardWalk {
+                turns: 2,
+                shifted: 0,
+            }],
+            patterns(&estimator, "1qazxsw2")
+        );
+        assert_eq!(
+            vec![Pattern::Bruteforce],
+            patterns(&estimator, "x7Kq!9mZ#pL2")
+        );
+    }
+
+    #[test]
+    fn test_decomposition() {
+        let estimate = estimate(&estimator(), "dragon6789$#k");
+        let tokens: Vec<(&str, &str)> = estimate
+            .matches()
+            .iter()
+            .map(|m| (m.pattern.name(), m.token.as_str()))
+            .collect();
+        assert_eq!(
+            vec![("dictionary", "dragon"), ("sequence", "6789"), ("bruteforce", "$#k")],
+            tokens
+        );
+        let product: f64 = estimate.matches().iter().map(|m| m.guesses).product();
+        assert!(estimate.guesses() >= product);
+    }
+
+    #[test]
+    fn test_long_password() {
+        let estimator = estimator();
+        let password = format!("password{}", "x7Kq!9mZ#pL2".repeat(10));
+        let long = estimate(&estimator, &password);
+        let first = &long.matches()[0];
+        assert_eq!(
+            ("dictionary", "password"),
+            (first.pattern.name(), first.token.as_str())
+        );
+        let last = long.matches().last().unwrap();
+        assert_eq!(Pattern::Bruteforce, last.pattern);
+        assert_eq!(password.chars().count(), last.end);
+        assert_eq!(4, long.score());
+
+        // only the first characters are searched for matches, the rest is brute forced
+        let password = "é日a1".repeat(2500);
+        let long = estimate(&estimator, &password);
+        let (last, searched) = long.matches().split_last().unwrap();
+        assert_eq!(Pattern::Bruteforce, last.pattern);
+        assert!(last.start <= MAX_MATCH_LENGTH);
+        assert_eq!(password.chars().count(), last.end);
+        assert!(searched.iter().all(|m| m.end <= MAX_MATCH_LENGTH));
+        assert!(long.bits().is_finite());
+    }
+
+    #[test]
+    fn test_scores() {
+        let estimator = estimator();
+        let scores: Vec<u8> = ["123456", "monkey2024", "Tr0ub4dor&3", "x7Kq!9mZ#pL2"]
+            .iter()
+            .map(|p| estimate(&estimator, p).score())
+            .collect();
+        assert!(scores.windows(2).all(|w| w[0] <= w[1]), "{scores:?}");
+        assert_eq!(0, scores[0]);
+        assert_eq!(4, scores[3]);
+    }
+
+    #[test]
+    fn test_minimum_entropy_rule() {
+        let estimator = estimator();
+        let rule =
+            MinimumEntropyRule::new(
+                move |password_data| {
+                    Ok(Box::new(estimator.estimate(password_data)) as Box<dyn Entropy>)
+                },
+                30.0,
+            )
+            .unwrap();
+        assert!(!rule.validate(&PasswordData::with_password("qwerty123".to_string())).valid());
+        assert!(rule.validate(&PasswordData::with_password("x7Kq!9mZ#pL2".to_string())).valid());
+    }
+}
@@ -6,3 +6,17 @@ pub trait Hasher<E> {
     ///Compares a known hash value with the hash of the given data.
     fn compare(&self, hash: &[u8], data: &[u8]) -> Result<bool, E>;
 }
+
+/// SHA-1 [Hasher] producing the raw 20 byte digest, as used by the k-anonymity range protocol of breach corpora.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Sha1Hasher;
+
+impl Hasher<String> for Sha1Hasher {
+    fn hash(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(sha1_smol::Sha1::from(data).digest().bytes().to_vec())
+    }
+
+    fn compare(&self, hash: &[u8], data: &[u8]) -> Result<bool, String> {
+        Ok(self.hash(data)? == hash)
+    }
+}
@@ -1,11 +1,16 @@
 #![warn(rustdoc::broken_intra_doc_links)]
 #![warn(rustdoc::redundant_explicit_links)]
 
+pub mod breach;
 pub mod dictionary;
 pub mod entropy;
 pub mod hash;
 pub mod password_generator;
+pub mod policy;
 pub mod rule;
 
+#[cfg(feature = "macros")]
+pub use passay_rs_macros::policy;
+
 #[cfg(test)]
 mod test;
@@ -70,8 +70,8 @@ impl PasswordGenerator {
             }
         };
 
+        let src_chars = source.chars().collect::<Vec<char>>();
         for _ in 0..size {
-            let src_chars = source.chars().collect::<Vec<char>>();
             let index = uni.sample(&mut self.random);
             let char = src_chars[index];
             target.push(char);
@@ -112,7 +112,7 @@ mod tests {
     use crate::password_generator::PasswordGenerator;
     use crate::rule::character::CharacterRule;
     use crate::rule::character_characteristics::CharacterCharacteristics;
-    use crate::rule::character_data::EnglishCharacterData;
+    use crate::rule::character_data::{EnglishCharacterData, UnicodeCharacterData};
     use crate::rule::{PasswordData, Rule};
     use std::ops::Not;
 
@@ -147,6 +147,28 @@ mod tests {
             )
             .unwrap();
     }
+    #[test]
+    fn test_unicode() {
+        let rules = vec![
+            CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 3).unwrap(),
+            CharacterRule::new(Box::new(UnicodeCharacterData::Digit), 3).unwrap(),
+        ];
+        let verify = CharacterCharacteristics::with_rules_and_characteristics(
+            vec![
+                CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 3).unwrap(),
+                CharacterRule::new(Box::new(UnicodeCharacterData::Digit), 3).unwrap(),
+            ],
+            2,
+        )
+        .unwrap();
+        let mut password_generator = PasswordGenerator::new();
+        for _ in 0..10 {
+            let password = password_generator.generate_password(8, &rules).unwrap();
+            assert_eq!(8, password.chars().count());
+            assert!(verify.validate(&PasswordData::with_password(password)).valid());
+        }
+    }
+
     fn random_passwords() -> Vec<String> {
         let mut passwords = Vec::with_capacity(100);
         const LEN: usize = 10;
@@ -0,0 +1,320 @@
+//! Import of Active Directory domain password policies, either exported by `secedit /export` (the `[System Access]`
+//! section with `MinimumPasswordLength = 8`) or printed by `Get-ADDefaultDomainPasswordPolicy`
+//! (`MinPasswordLength : 8`).
+use crate::policy::PolicyError;
+use crate::policy::Unsupported;
+use crate::policy::import::ImportedPolicy;
+use crate::policy::presets::ActiveDirectoryComplexity;
+use crate::rule::Rule;
+use crate::rule::history::HistoryRule;
+use crate::rule::length::LengthRule;
+use crate::rule::password_age::MinimumPasswordAgeRule;
+use crate::rule::password_validator::PasswordValidator;
+use std::time::Duration;
+
+const SYSTEM_ACCESS: &str = "System Access";
+const DAY: u64 = 24 * 60 * 60;
+
+/// Parses an Active Directory password policy into a validator.
+///
+/// Supported settings are the minimum password length, the complexity requirements (see [ActiveDirectoryComplexity])
+/// and a non-zero password history size, which adds a [HistoryRule]. A non-zero minimum password age adds a
+/// [MinimumPasswordAgeRule]; `secedit` gives it in days and PowerShell as a `d.hh:mm:ss` time span. The maximum
+/// password age, lockout and reversible encryption settings are reported as unsupported. Sections of a `secedit`
+/// export other than `[System Access]` are ignored.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::policy::import::active_directory;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let policy = "[System Access]\nMinimumPasswordLength = 8\nPasswordComplexity = 1\n";
+///  let policy = active_directory::parse(policy).unwrap();
+///  let password = PasswordData::with_password("password".to_string());
+///  assert!(!policy.validator().validate(&password).valid());
+/// ```
+pub fn parse(text: &str) -> Result<ImportedPolicy, PolicyError> {
+    let mut min_length = 0;
+    let mut complexity = false;
+    let mut history = 0;
+    let mut min_age = Duration::ZERO;
+    let mut unsupported = vec![];
+    let mut section: Option<&str> = None;
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(name.trim());
+            continue;
+        }
+        if section.is_some_and(|s| s != SYSTEM_ACCESS) {
+            continue;
+        }
+        let Some(separator) = line.find(['=', ':']) else {
+            return Err(PolicyError::Syntax(format!(
+                "line {}: expected `name = value` or `name : value`",
+                i + 1
+            )));
+        };
+        let name = line[..separator].trim();
+        let value = line[separator + 1..].trim();
+        match name {
+            "MinimumPasswordLength" | "MinPasswordLength" => min_length = integer(name, value)?,
+            "PasswordComplexity" | "ComplexityEnabled" => complexity = boolean(name, value)?,
+            "PasswordHistorySize" | "PasswordHistoryCount" => history = integer(name, value)?,
+            "MinimumPasswordAge" | "MinPasswordAge" => min_age = age(name, value)?,
+            "MaximumPasswordAge" | "MaxPasswordAge" => {
+                unsupported.push(Unsupported::new(name, "password expiry is not checked"))
+            }
+            "ClearTextPassword" | "ReversibleEncryptionEnabled" => {
+                unsupported.push(Unsupported::new(
+                    name,
+                    "password storage setting without effect on validation",
+                ))
+            }
+            "LockoutBadCount"
+            | "LockoutThreshold"
+            | "LockoutDuration"
+            | "LockoutObservationWindow"
+            | "ResetLockoutCount"
+            | "ForceLogoffWhenHourExpire" => unsupported.push(Unsupported::new(
+                name,
+                "account setting without effect on validation",
+            )),
+            "DistinguishedName" | "objectClass" | "objectGuid" => {}
+            _ => unsupported.push(Unsupported::new(name, "unknown setting")),
+        }
+    }
+
+    let mut rules: Vec<Box<dyn Rule>> = vec![];
+    if complexity {
+        let preset = ActiveDirectoryComplexity {
+            min_length,
+            ..Default::default()
+        };
+        let validator = preset
+            .validator()
+            .map_err(|e| PolicyError::invalid("MinimumPasswordLength", e))?;
+        rules.push(Box::new(validator));
+    } else {
+        let max_length = ActiveDirectoryComplexity::default().max_length;
+        rules.push(Box::new(LengthRule::new(min_length, max_length)));
+    }
+    if history > 0 {
+        let rule = HistoryRule::with_depth(history, true)
+            .map_err(|e| PolicyError::invalid("PasswordHistorySize", e))?;
+        rules.push(Box::new(rule));
+    }
+    if !min_age.is_zero() {
+        rules.push(Box::new(MinimumPasswordAgeRule::new(min_age)));
+    }
+    Ok(ImportedPolicy::new(
+        PasswordValidator::new(rules),
+        unsupported,
+    ))
+}
+
+fn integer(name: &str, value: &str) -> Result<usize, PolicyError> {
+    value.parse().map_err(|_| {
+        PolicyError::invalid(
+            name,
+            format!("expected a non-negative integer but found {value:?}"),
+        )
+    })
+}
+
+/// Parses a password age given either in days or as a `d.hh:mm:ss` time span.
+fn age(name: &str, value: &str) -> Result<Duration, PolicyError> {
+    if !value.contains(':') {
+        return Ok(Duration::from_secs(integer(name, value)? as u64 * DAY));
+    }
+    let invalid = || {
+        PolicyError::invalid(
+            name,
+            format!("expected days or a `d.hh:mm:ss` time span but found {value:?}"),
+        )
+    };
+    let parts: Vec<&str> = value.split(':').collect();
+    let [hours, minutes, seconds] = parts[..] else {
+        return Err(invalid());
+    };
+    let (days, hours) = hours.split_once('.').unwrap_or(("0", hours));
+    // fractions of a second are dropped
+    let seconds = seconds.split_once('.').map_or(seconds, |(s, _)| s);
+    let mut secs = 0u64;
+    for (part, unit) in [(days, DAY), (hours, 60 * 60), (minutes, 60), (seconds, 1)] {
+        let part: u64 = part.parse().map_err(|_| invalid())?;
+        secs += part * unit;
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+fn boolean(name: &str, value: &str) -> Result<bool, PolicyError> {
+    match value {
+        "1" | "True" | "true" => Ok(true),
+        "0" | "False" | "false" => Ok(false),
+        _ => Err(PolicyError::invalid(
+            name,
+            format!("expected boolean but found {value:?}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::PolicyError;
+    use crate::policy::Unsupported;
+    use crate::policy::import::active_directory::parse;
+    use crate::rule::history::HistoricalReference;
+    use crate::rule::reference::Reference;
+    use crate::rule::{
+        PasswordData, character_characteristics, history, length, password_age, username,
+    };
+    use crate::test::{RulePasswordTestItem, check_passwords};
+    use std::time::{Duration, SystemTime};
+
+    const SECEDIT: &str = "
+[Unicode]
+Unicode=yes
+[System Access]
+MinimumPasswordAge = 1
+MaximumPasswordAge = 42
+MinimumPasswordLength = 10
+PasswordComplexity = 1
+PasswordHistorySize = 24
+LockoutBadCount = 0
+[Version]
+signature=\"$CHICAGO$\"
+";
+
+    const POWERSHELL: &str = "
+ComplexityEnabled           : False
+DistinguishedName           : DC=example,DC=com
+LockoutDuration             : 00:30:00
+MinPasswordAge              : 0.12:00:00
+MinPasswordLength           : 12
+PasswordHistoryCount        : 0
+";
+
+    /// Creates a history with a single password changed the given number of seconds ago.
+    fn changed(age: u64) -> Vec<Box<dyn Reference>> {
+        let mut reference = HistoricalReference::with_password("Summer2024!".to_string());
+        reference.set_created(Some(SystemTime::now() - Duration::from_secs(age)));
+        vec![Box::new(reference)]
+    }
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password_and_user(password.to_string(), Some("alice".to_string()))
+    }
+
+    #[test]
+    fn test_secedit() {
+        let policy = parse(SECEDIT).unwrap();
+        assert_eq!(
+            vec!["MaximumPasswordAge", "LockoutBadCount"],
+            policy.unsupported().iter().map(|u| u.directive.as_str()).collect::<Vec<_>>()
+        );
+        let validator = || Box::new(policy.validator().clone());
+        let history: Vec<Box<dyn Reference>> = vec![Box::new(HistoricalReference::with_password(
+            "Summer2024!".to_string(),
+        ))];
+        let test_cases = vec![
+            RulePasswordTestItem(validator(), password("Winter2024!"), vec![]),
+            RulePasswordTestItem(
+                validator(),
+                password("Winter24!"),
+                vec![length::ERROR_CODE_MIN],
+            ),
+            RulePasswordTestItem(validator(), password("winter2024!"), vec![]),
+            RulePasswordTestItem(
+                validator(),
+                password("winter2024x"),
+                vec![
+                    "INSUFFICIENT_UPPERCASE",
+                    "INSUFFICIENT_SPECIAL",
+                    character_characteristics::ERROR_CODE,
+                ],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("Alice-2024!"),
+                vec![username::ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::new("Summer2024!".to_string(), None, history),
+                vec![history::ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::new("Winter2024!".to_string(), None, changed(60 * 60)),
+                vec![password_age::ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_powershell() {
+        let policy = parse(POWERSHELL).unwrap();
+        assert_eq!(
+            &[Unsupported::new(
+                "LockoutDuration",
+                "account setting without effect on validation"
+            )],
+            policy.unsupported()
+        );
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(policy.validator().clone()),
+                password("alicealice12"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(policy.validator().clone()),
+                password("alicealice1"),
+                vec![length::ERROR_CODE_MIN],
+            ),
+            RulePasswordTestItem(
+                Box::new(policy.validator().clone()),
+                PasswordData::new("alicealice12".to_string(), None, changed(11 * 60 * 60)),
+                vec![password_age::ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(policy.validator().clone()),
+                PasswordData::new("alicealice12".to_string(), None, changed(13 * 60 * 60)),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Some(PolicyError::invalid(
+                "PasswordComplexity",
+                "expected boolean but found \"yes\"".to_string()
+            )),
+            parse("PasswordComplexity = yes").err()
+        );
+        assert_eq!(
+            Some(PolicyError::Syntax(
+                "line 2: expected `name = value` or `name : value`".to_string()
+            )),
+            parse("[System Access]\nMinimumPasswordLength").err()
+        );
+        assert_eq!(
+            Some(PolicyError::invalid(
+                "MinPasswordAge",
+                "expected days or a `d.hh:mm:ss` time span but found \"1.00:00\"".to_string()
+            )),
+            parse("MinPasswordAge : 1.00:00").err()
+        );
+    }
+}
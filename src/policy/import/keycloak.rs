@@ -0,0 +1,273 @@
+//! Import of Keycloak realm password policy strings, e.g. `length(8) and digits(1) and notUsername`.
+use crate::policy::PolicyError;
+use crate::policy::Unsupported;
+use crate::policy::import::ImportedPolicy;
+use crate::rule::Rule;
+use crate::rule::allowed_character::MatchBehavior;
+use crate::rule::allowed_regex::AllowedRegex;
+use crate::rule::character::CharacterRule;
+use crate::rule::character_data::EnglishCharacterData;
+use crate::rule::history::HistoryRule;
+use crate::rule::length::LengthRule;
+use crate::rule::password_validator::PasswordValidator;
+use crate::rule::username::UsernameRule;
+use fancy_regex::Regex;
+
+/// Parses a Keycloak password policy string into a validator.
+///
+/// Supported policies are `length`, `maxLength`, `digits`, `lowerCase`, `upperCase`, `specialChars`, `notUsername`,
+/// `notContainsUsername`, `regexPattern` and `passwordHistory`, with the Keycloak defaults when the argument is
+/// omitted. `notUsername` is enforced by [UsernameRule], which also rejects passwords that merely contain the username;
+/// the stricter check is reported in [unsupported](ImportedPolicy::unsupported). All other policies, such as
+/// `hashIterations` or `notEmail`, are reported as unsupported.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::policy::import::keycloak;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let policy = keycloak::parse("length(8) and digits(1) and notContainsUsername").unwrap();
+///  assert!(policy.unsupported().is_empty());
+///  let password =
+///      PasswordData::with_password_and_user("password".to_string(), Some("alice".to_string()));
+///  assert!(!policy.validator().validate(&password).valid());
+/// ```
+pub fn parse(text: &str) -> Result<ImportedPolicy, PolicyError> {
+    let mut min_length = None;
+    let mut max_length = None;
+    let mut rules: Vec<Box<dyn Rule>> = vec![];
+    let mut unsupported = vec![];
+
+    for policy in split_policies(text)? {
+        let (name, arg) = match policy.split_once('(') {
+            Some((name, arg)) => match arg.strip_suffix(')') {
+                Some(arg) => (name.trim(), Some(arg.trim())),
+                None => {
+                    return Err(PolicyError::Syntax(format!(
+                        "missing closing parenthesis in {policy:?}"
+                    )));
+                }
+            },
+            None => (policy, None),
+        };
+        match name {
+            "length" => min_length = Some(integer(name, arg, 8)?),
+            "maxLength" => max_length = Some(integer(name, arg, 64)?),
+            "digits" => rules.push(character(name, arg, EnglishCharacterData::Digit)?),
+            "lowerCase" => rules.push(character(name, arg, EnglishCharacterData::LowerCase)?),
+            "upperCase" => rules.push(character(name, arg, EnglishCharacterData::UpperCase)?),
+            "specialChars" => rules.push(character(name, arg, EnglishCharacterData::Special)?),
+            "notUsername" | "notContainsUsername" => {
+                if name == "notUsername" {
+                    unsupported.push(Unsupported::new(
+                        name,
+                        "approximated by rejecting passwords that contain the username",
+                    ));
+                }
+                rules.push(Box::new(UsernameRule::new(
+                    false,
+                    true,
+                    MatchBehavior::Contains,
+                )))
+            }
+            "regexPattern" => {
+                let pattern = arg.unwrap_or_default();
+                let regex = Regex::new(&format!("^(?:{pattern})$"))
+                    .map_err(|e| PolicyError::invalid(name, e.to_string()))?;
+                rules.push(Box::new(AllowedRegex::from_regex(regex)));
+            }
+            "passwordHistory" => {
+                let depth = integer(name, arg, 3)?;
+                // a depth of 0 disables the history check
+                if depth > 0 {
+                    let rule = HistoryRule::with_depth(depth, true).map_err(|e| PolicyError::invalid(name, e))?;
+                    rules.push(Box::new(rule));
+                }
+            }
+            "notEmail" => unsupported.push(Unsupported::new(
+                name,
+                "the email address is not part of the password data",
+            )),
+            "passwordBlacklist" => unsupported.push(Unsupported::new(
+                name,
+                "the blacklist file is stored on the Keycloak server, use a dictionary rule instead",
+            )),
+            "hashAlgorithm" | "hashIterations" => unsupported.push(Unsupported::new(
+                name,
+                "password storage setting without effect on validation",
+            )),
+            "forceExpiredPasswordChange" | "maxAuthAge" | "passwordAge" => unsupported.push(
+                Unsupported::new(name, "password age is not checked"),
+            ),
+            _ => unsupported.push(Unsupported::new(name, "unknown policy")),
+        }
+    }
+
+    if min_length.is_some() || max_length.is_some() {
+        let length = LengthRule::new(min_length.unwrap_or(0), max_length.unwrap_or(usize::MAX));
+        rules.insert(0, Box::new(length));
+    }
+    Ok(ImportedPolicy::new(
+        PasswordValidator::new(rules),
+        unsupported,
+    ))
+}
+
+/// Splits the policy string at every ` and ` that is not part of a policy argument.
+fn split_policies(text: &str) -> Result<Vec<&str>, PolicyError> {
+    let mut policies = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                return Err(PolicyError::Syntax(format!(
+                    "unexpected closing parenthesis at {i}"
+                )));
+            }
+            ')' => depth -= 1,
+            _ if depth == 0 && text[i..].starts_with(" and ") => {
+                policies.push(text[start..i].trim());
+                start = i + " and ".len();
+            }
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        return Err(PolicyError::Syntax(
+            "missing closing parenthesis".to_string(),
+        ));
+    }
+    policies.push(text[start..].trim());
+    Ok(policies.into_iter().filter(|p| !p.is_empty()).collect())
+}
+
+fn integer(name: &str, arg: Option<&str>, default: usize) -> Result<usize, PolicyError> {
+    match arg {
+        None | Some("") => Ok(default),
+        Some(arg) => arg.parse().map_err(|_| {
+            PolicyError::invalid(
+                name,
+                format!("expected a non-negative integer but found {arg:?}"),
+            )
+        }),
+    }
+}
+
+fn character(
+    name: &str,
+    arg: Option<&str>,
+    data: EnglishCharacterData,
+) -> Result<Box<dyn Rule>, PolicyError> {
+    let rule = CharacterRule::new(Box::new(data), integer(name, arg, 1)?)
+        .map_err(|e| PolicyError::invalid(name, e))?;
+    Ok(Box::new(rule))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::PolicyError;
+    use crate::policy::Unsupported;
+    use crate::policy::import::keycloak::{parse, split_policies};
+    use crate::rule::PasswordData;
+    use crate::rule::{length, username};
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password_and_user(password.to_string(), Some("alice".to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let policy = parse(
+            "length(10) and maxLength(16) and digits(2) and upperCase and specialChars(1) and \
+             notUsername and regexPattern([^ ]+) and hashIterations(27500) and notEmail",
+        )
+        .unwrap();
+        assert_eq!(
+            &[
+                Unsupported::new(
+                    "notUsername",
+                    "approximated by rejecting passwords that contain the username"
+                ),
+                Unsupported::new(
+                    "hashIterations",
+                    "password storage setting without effect on validation"
+                ),
+                Unsupported::new(
+                    "notEmail",
+                    "the email address is not part of the password data"
+                ),
+            ],
+            policy.unsupported()
+        );
+        let validator = || Box::new(policy.validator().clone());
+        let test_cases = vec![
+            RulePasswordTestItem(validator(), password("Tr0ub4dor&3"), vec![]),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4dor"),
+                vec![length::ERROR_CODE_MIN, "INSUFFICIENT_SPECIAL"],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4dor&3Tr0ub4dor&3"),
+                vec![length::ERROR_CODE_MAX],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("tr0ub4dor&3"),
+                vec!["INSUFFICIENT_UPPERCASE"],
+            ),
+            RulePasswordTestItem(validator(), password("Tr0ub4dor 3&"), vec!["ALLOWED_MATCH"]),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4&ALICE"),
+                vec![username::ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_split_policies() {
+        assert_eq!(
+            vec!["regexPattern(a and b)", "digits"],
+            split_policies(" regexPattern(a and b) and digits ").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = [
+            (
+                "length(eight)",
+                PolicyError::invalid(
+                    "length",
+                    "expected a non-negative integer but found \"eight\"".to_string(),
+                ),
+            ),
+            (
+                "digits(0)",
+                PolicyError::invalid(
+                    "digits",
+                    "Number of characters must be greater than zero".to_string(),
+                ),
+            ),
+            (
+                "length(8 and digits(1)",
+                PolicyError::Syntax("missing closing parenthesis".to_string()),
+            ),
+            (
+                "length(8)) and digits",
+                PolicyError::Syntax("unexpected closing parenthesis at 9".to_string()),
+            ),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(Some(expected), parse(text).err(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Conversion of password policies defined by other systems into [PasswordValidator]s built from the existing rules.
+//! Settings that have no equivalent rule are not dropped silently but reported by [ImportedPolicy::unsupported].
+use crate::policy::Unsupported;
+use crate::rule::password_validator::PasswordValidator;
+
+pub mod active_directory;
+pub mod keycloak;
+pub mod pwquality;
+
+/// Result of importing a foreign policy.
+#[derive(Clone)]
+pub struct ImportedPolicy {
+    validator: PasswordValidator,
+    unsupported: Vec<Unsupported>,
+}
+
+impl ImportedPolicy {
+    pub(crate) fn new(validator: PasswordValidator, unsupported: Vec<Unsupported>) -> Self {
+        Self {
+            validator,
+            unsupported,
+        }
+    }
+
+    pub fn validator(&self) -> &PasswordValidator {
+        &self.validator
+    }
+
+    pub fn into_validator(self) -> PasswordValidator {
+        self.validator
+    }
+
+    /// Returns the directives of the source policy that are not enforced by the validator.
+    pub fn unsupported(&self) -> &[Unsupported] {
+        &self.unsupported
+    }
+}
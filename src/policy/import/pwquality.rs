@@ -0,0 +1,307 @@
+//! Import of libpwquality configuration files, usually found at `/etc/security/pwquality.conf`.
+use crate::dictionary::word_lists::ArrayWordList;
+use crate::dictionary::word_lists::sort::SliceSort;
+use crate::dictionary::word_lists::word_list_dictionary::WordListDictionary;
+use crate::policy::PolicyError;
+use crate::policy::Unsupported;
+use crate::policy::import::ImportedPolicy;
+use crate::rule::Rule;
+use crate::rule::allowed_character::MatchBehavior;
+use crate::rule::character::CharacterRule;
+use crate::rule::character_characteristics::CharacterCharacteristics;
+use crate::rule::character_data::EnglishCharacterData;
+use crate::rule::dictionary_substring::DictionarySubstringRule;
+use crate::rule::illegal_sequence::IllegalSequenceRule;
+use crate::rule::length::LengthRule;
+use crate::rule::password_validator::PasswordValidator;
+use crate::rule::repeat_character::RepeatCharacterRule;
+use crate::rule::sequence_data::EnglishSequenceData;
+use crate::rule::username::UsernameRule;
+
+const DEFAULT_MIN_LENGTH: i64 = 8;
+
+/// Parses the contents of a `pwquality.conf` file. Directives that are not set use the libpwquality defaults, so a
+/// minimum length of 8 and the username check are always applied unless disabled.
+///
+/// Supported directives are `minlen`, negative `dcredit`, `ucredit`, `lcredit` and `ocredit`, `minclass`,
+/// `maxrepeat`, `maxsequence`, `usercheck` and `badwords`. All others are reported as unsupported.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::policy::import::pwquality;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let conf = "minlen = 12\ndcredit = -1\nmaxrepeat = 3\ndifok = 5\n";
+///  let policy = pwquality::parse(conf).unwrap();
+///  assert_eq!("difok", policy.unsupported()[0].directive);
+///  let password = PasswordData::with_password("correcthorse".to_string());
+///  assert!(!policy.validator().validate(&password).valid());
+/// ```
+pub fn parse(text: &str) -> Result<ImportedPolicy, PolicyError> {
+    let mut min_length = DEFAULT_MIN_LENGTH;
+    let mut credits: Vec<(EnglishCharacterData, i64)> = vec![];
+    let mut min_class = 0;
+    let mut max_repeat = 0;
+    let mut max_sequence = 0;
+    let mut user_check = true;
+    let mut bad_words: Vec<String> = vec![];
+    let mut unsupported = vec![];
+
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => (line, ""),
+        };
+        match name {
+            "minlen" => min_length = integer(name, value)?,
+            "dcredit" => credits.push((EnglishCharacterData::Digit, integer(name, value)?)),
+            "ucredit" => credits.push((EnglishCharacterData::UpperCase, integer(name, value)?)),
+            "lcredit" => credits.push((EnglishCharacterData::LowerCase, integer(name, value)?)),
+            "ocredit" => credits.push((EnglishCharacterData::Special, integer(name, value)?)),
+            "minclass" => min_class = integer(name, value)?,
+            "maxrepeat" => max_repeat = integer(name, value)?,
+            "maxsequence" => max_sequence = integer(name, value)?,
+            "usercheck" => user_check = integer(name, value)? != 0,
+            "badwords" => bad_words = value.split_whitespace().map(String::from).collect(),
+            "difok" => unsupported.push(Unsupported::new(
+                name,
+                "comparison with the old password is not available",
+            )),
+            "maxclassrepeat" => unsupported.push(Unsupported::new(
+                name,
+                "repeats of the same character class are not checked",
+            )),
+            "gecoscheck" | "usersubstr" => unsupported.push(Unsupported::new(
+                name,
+                "only the complete username is checked",
+            )),
+            "dictcheck" | "dictpath" => unsupported.push(Unsupported::new(
+                name,
+                "cracklib dictionaries are not supported, use a dictionary rule instead",
+            )),
+            "enforcing" | "retry" | "enforce_for_root" | "local_users_only" => unsupported.push(
+                Unsupported::new(name, "PAM module setting without effect on validation"),
+            ),
+            _ => unsupported.push(Unsupported::new(name, "unknown directive")),
+        }
+    }
+
+    let mut rules: Vec<Box<dyn Rule>> = vec![];
+    if min_length > 0 {
+        rules.push(Box::new(LengthRule::new(min_length as usize, usize::MAX)));
+    }
+    for (data, credit) in credits {
+        let name = credit_name(&data);
+        if credit < 0 {
+            let rule = CharacterRule::new(Box::new(data), credit.unsigned_abs() as usize)
+                .map_err(|e| PolicyError::invalid(name, e))?;
+            rules.push(Box::new(rule));
+        } else if credit > 0 {
+            unsupported.push(Unsupported::new(
+                name,
+                "positive credits for the length are not supported",
+            ));
+        }
+    }
+    if min_class > 0 {
+        let char_rules = [
+            EnglishCharacterData::Digit,
+            EnglishCharacterData::UpperCase,
+            EnglishCharacterData::LowerCase,
+            EnglishCharacterData::Special,
+        ]
+        .into_iter()
+        .map(|data| CharacterRule::from_character_data(Box::new(data)))
+        .collect();
+        let rule = CharacterCharacteristics::with_rules_and_characteristics(
+            char_rules,
+            min_class as usize,
+        )
+        .map_err(|e| PolicyError::invalid("minclass", e))?;
+        rules.push(Box::new(rule));
+    }
+    if max_repeat > 0 {
+        match RepeatCharacterRule::with_sequence_len(max_repeat as usize + 1) {
+            Ok(rule) => rules.push(Box::new(rule)),
+            Err(e) => unsupported.push(Unsupported::new("maxrepeat", &e)),
+        }
+    }
+    if max_sequence > 0 {
+        let length = max_sequence as usize + 1;
+        for data in [EnglishSequenceData::Alphabetical, EnglishSequenceData::Numerical] {
+            rules.push(Box::new(IllegalSequenceRule::with_warp(
+                data, length, false,
+            )));
+        }
+    }
+    if user_check {
+        rules.push(Box::new(UsernameRule::new(
+            true,
+            true,
+            MatchBehavior::Contains,
+        )));
+    }
+    if !bad_words.is_empty() {
+        let word_list = ArrayWordList::with_sorter(bad_words, false, Some(SliceSort));
+        rules.push(Box::new(DictionarySubstringRule::from_dictionary(
+            WordListDictionary::new(word_list),
+        )));
+    }
+    Ok(ImportedPolicy::new(
+        PasswordValidator::new(rules),
+        unsupported,
+    ))
+}
+
+fn integer(name: &str, value: &str) -> Result<i64, PolicyError> {
+    let i: i64 = value
+        .parse()
+        .map_err(|_| PolicyError::invalid(name, format!("expected integer but found {value:?}")))?;
+    if i < 0 && !name.ends_with("credit") {
+        return Err(PolicyError::invalid(
+            name,
+            format!("expected a non-negative integer but found {i}"),
+        ));
+    }
+    Ok(i)
+}
+
+fn credit_name(data: &EnglishCharacterData) -> &'static str {
+    match data {
+        EnglishCharacterData::Digit => "dcredit",
+        EnglishCharacterData::UpperCase => "ucredit",
+        EnglishCharacterData::LowerCase => "lcredit",
+        _ => "ocredit",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::PolicyError;
+    use crate::policy::Unsupported;
+    use crate::policy::import::pwquality::parse;
+    use crate::rule::PasswordData;
+    use crate::rule::{character_characteristics, length, repeat_character, username};
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    const CONF: &str = "
+# Configuration for systemwide password quality limits
+minlen = 10
+dcredit = -1
+ucredit = -1
+lcredit = 1
+minclass = 3
+maxrepeat = 2
+maxsequence = 3
+usercheck = 1
+badwords = acme
+difok = 5
+enforce_for_root
+";
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password_and_user(password.to_string(), Some("alice".to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let policy = parse(CONF).unwrap();
+        assert_eq!(
+            vec!["difok", "enforce_for_root", "lcredit"],
+            policy.unsupported().iter().map(|u| u.directive.as_str()).collect::<Vec<_>>()
+        );
+        let validator = || Box::new(policy.validator().clone());
+        let test_cases = vec![
+            RulePasswordTestItem(validator(), password("Tr0ub4dor&x"), vec![]),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4d"),
+                vec![length::ERROR_CODE_MIN],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("tr0ub4dor&xxx"),
+                vec!["INSUFFICIENT_UPPERCASE", repeat_character::ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4dor&1234"),
+                vec!["ILLEGAL_NUMERICAL_SEQUENCE"],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4dor&ecila"),
+                vec![username::ERROR_CODE_REVERSED],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                password("Tr0ub4dor&ACME"),
+                vec!["ILLEGAL_WORD"],
+            ),
+            RulePasswordTestItem(validator(), password("TROUB4DOR&X"), vec![]),
+            RulePasswordTestItem(
+                validator(),
+                password("troub4dorzx"),
+                vec![
+                    "INSUFFICIENT_UPPERCASE",
+                    "INSUFFICIENT_UPPERCASE",
+                    "INSUFFICIENT_SPECIAL",
+                    character_characteristics::ERROR_CODE,
+                ],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_defaults() {
+        let policy = parse("").unwrap();
+        assert!(policy.unsupported().is_empty());
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(policy.validator().clone()),
+                password("abcdefg"),
+                vec![length::ERROR_CODE_MIN],
+            ),
+            RulePasswordTestItem(
+                Box::new(policy.validator().clone()),
+                password("xalicexx"),
+                vec![username::ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+
+        let policy = parse("usercheck = 0\nmaxrepeat = 1\nfoo = bar").unwrap();
+        assert_eq!(
+            &[
+                Unsupported::new("foo", "unknown directive"),
+                Unsupported::new("maxrepeat", "sequence length must be >= 3"),
+            ],
+            policy.unsupported()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Some(PolicyError::invalid(
+                "minlen",
+                "expected integer but found \"ten\"".to_string()
+            )),
+            parse("minlen = ten").err()
+        );
+        assert_eq!(
+            Some(PolicyError::invalid(
+                "maxrepeat",
+                "expected a non-negative integer but found -2".to_string()
+            )),
+            parse("maxrepeat = -2").err()
+        );
+    }
+}
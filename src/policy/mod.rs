@@ -0,0 +1,469 @@
+use crate::policy::registry::Registry;
+use crate::policy::value::{Params, Value};
+use crate::rule::Rule;
+use crate::rule::password_validator::PasswordValidator;
+use std::fmt::{Debug, Display, Formatter};
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+use std::fs;
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub mod import;
+pub mod password_rules;
+pub mod presets;
+pub mod registry;
+mod rules;
+pub mod value;
+
+/// Error produced while loading a policy document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// The document could not be read.
+    Io(String),
+    /// The document is not well-formed in its format.
+    Syntax(String),
+    /// The document is well-formed but does not describe a valid policy. `path` points at the offending entry, e.g.
+    /// `rules[2].minimum`.
+    Invalid { path: String, message: String },
+}
+
+impl PolicyError {
+    pub fn invalid(path: &str, message: String) -> Self {
+        PolicyError::Invalid {
+            path: path.to_string(),
+            message,
+        }
+    }
+}
+
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyError::Io(message) => write!(f, "cannot read policy: {message}"),
+            PolicyError::Syntax(message) => write!(f, "malformed policy: {message}"),
+            PolicyError::Invalid { path, message } if path.is_empty() => {
+                write!(f, "invalid policy: {message}")
+            }
+            PolicyError::Invalid { path, message } => {
+                write!(f, "invalid policy at `{path}`: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/// A setting that could not be translated, either a directive of an imported policy or a rule that cannot be
+/// exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    /// The directive name or the path of the rule, e.g. `rules[2]`.
+    pub directive: String,
+    pub reason: String,
+}
+
+impl Unsupported {
+    pub(crate) fn new(directive: &str, reason: &str) -> Self {
+        Self {
+            directive: directive.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.directive, self.reason)
+    }
+}
+
+impl Debug for PolicyLoader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PolicyLoader").field("base_dir", &self.base_dir).finish()
+    }
+}
+
+/// Supported policy document formats, each one enabled by the feature of the same name.
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyFormat {
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+impl PolicyFormat {
+    /// Guesses the format of a document from its file extension.
+    pub fn from_path(path: &Path) -> Option<PolicyFormat> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            #[cfg(feature = "toml")]
+            "toml" => Some(PolicyFormat::Toml),
+            #[cfg(feature = "json")]
+            "json" => Some(PolicyFormat::Json),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(PolicyFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Parses a document of this format into a [Value].
+    pub fn parse(&self, text: &str) -> Result<Value, PolicyError> {
+        match self {
+            #[cfg(feature = "toml")]
+            PolicyFormat::Toml => {
+                toml::from_str(text).map_err(|e| PolicyError::Syntax(e.to_string()))
+            }
+            #[cfg(feature = "json")]
+            PolicyFormat::Json => {
+                serde_json::from_str(text).map_err(|e| PolicyError::Syntax(e.to_string()))
+            }
+            #[cfg(feature = "yaml")]
+            PolicyFormat::Yaml => {
+                serde_yaml::from_str(text).map_err(|e| PolicyError::Syntax(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Builds a [PasswordValidator] from a declarative policy document. A document is a table with a `rules` array, each
+/// entry names a rule with its `type` and lists the rule parameters next to it.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::policy::{PolicyFormat, PolicyLoader};
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let policy = r#"
+///      [[rules]]
+///      type = "length"
+///      min = 8
+///      max = 64
+///
+///      [[rules]]
+///      type = "character"
+///      data = "english.digit"
+///      minimum = 1
+///
+///      [[rules]]
+///      type = "illegal_sequence"
+///      sequence = "english.usqwerty"
+///      length = 5
+///  "#;
+///  let validator = PolicyLoader::new().load_str(policy, PolicyFormat::Toml).unwrap();
+///  let result = validator.validate(&PasswordData::with_password("qwerty123".to_string()));
+///  assert!(!result.valid());
+/// ```
+#[derive(Clone, Default)]
+pub struct PolicyLoader {
+    base_dir: Option<PathBuf>,
+    registry: Rc<Registry>,
+}
+
+impl PolicyLoader {
+    pub fn new() -> Self {
+        Self {
+            base_dir: None,
+            registry: Rc::new(Registry::default()),
+        }
+    }
+
+    /// Uses the given registry to resolve rule types and data names instead of the built-in one.
+    pub fn with_registry(mut self, registry: Registry) -> Self {
+        self.registry = Rc::new(registry);
+        self
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Sets the directory that relative dictionary paths are resolved against. When a policy is loaded with
+    /// [PolicyLoader::load_path] the directory of the policy file is used by default.
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    /// Loads a policy from a file, choosing the format by its extension.
+    #[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+    pub fn load_path(&self, path: impl AsRef<Path>) -> Result<PasswordValidator, PolicyError> {
+        let path = path.as_ref();
+        let format = PolicyFormat::from_path(path).ok_or_else(|| {
+            PolicyError::Io(format!("unsupported policy format: {}", path.display()))
+        })?;
+        let text = fs::read_to_string(path)
+            .map_err(|e| PolicyError::Io(format!("{}: {e}", path.display())))?;
+        let document = format.parse(&text)?;
+        if self.base_dir.is_none() {
+            let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            return self.clone().with_base_dir(base_dir).load_value(&document);
+        }
+        self.load_value(&document)
+    }
+
+    /// Loads a policy from a string in the given format.
+    #[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+    pub fn load_str(
+        &self,
+        text: &str,
+        format: PolicyFormat,
+    ) -> Result<PasswordValidator, PolicyError> {
+        self.load_value(&format.parse(text)?)
+    }
+
+    /// Loads a policy from an already parsed document.
+    pub fn load_value(&self, document: &Value) -> Result<PasswordValidator, PolicyError> {
+        let mut params = Params::new("", document)?;
+        let specs = params
+            .array("rules")?
+            .ok_or_else(|| PolicyError::invalid("", "missing parameter `rules`".to_string()))?;
+        params.finish()?;
+        Ok(PasswordValidator::new(self.build_rules(specs)?))
+    }
+
+    /// Builds the rules described by the given document entries, e.g. the nested rules of a custom rule.
+    pub fn build_rules(
+        &self,
+        specs: Vec<(String, Value)>,
+    ) -> Result<Vec<Box<dyn Rule>>, PolicyError> {
+        specs
+            .into_iter()
+            .map(|(path, spec)| self.registry.build_rule(self, &path, &spec))
+            .collect()
+    }
+
+    pub(crate) fn resolve_path(&self, path: &str) -> PathBuf {
+        match &self.base_dir {
+            Some(base_dir) => base_dir.join(path),
+            None => PathBuf::from(path),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use crate::policy::{PolicyError, PolicyFormat, PolicyLoader};
+    use crate::rule::sequence_data::{EnglishSequenceData, SequenceData};
+    use crate::rule::{PasswordData, Rule, dictionary, length, whitespace};
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    const TOML_POLICY: &str = r#"
+        [[rules]]
+        type = "length"
+        min = 8
+        max = 16
+
+        [[rules]]
+        type = "character_characteristics"
+        num_characteristics = 3
+        rules = [
+            { data = "english.digit" },
+            { data = "english.special" },
+            { data = "english.uppercase" },
+            { data = "english.lowercase" },
+        ]
+
+        [[rules]]
+        type = "whitespace"
+
+        [[rules]]
+        type = "illegal_sequence"
+        sequence = "english.usqwerty"
+
+        [[rules]]
+        type = "dictionary_substring"
+        paths = ["web2-gt3"]
+        match_backwards = true
+    "#;
+
+    #[cfg(feature = "json")]
+    const JSON_POLICY: &str = r#"{
+        "rules": [
+            { "type": "length", "min": 8, "max": 16 },
+            {
+                "type": "character_characteristics",
+                "num_characteristics": 3,
+                "rules": [
+                    { "data": "english.digit" },
+                    { "data": "english.special" },
+                    { "data": "english.uppercase" },
+                    { "data": "english.lowercase" }
+                ]
+            },
+            { "type": "whitespace" },
+            { "type": "illegal_sequence", "sequence": "english.usqwerty" },
+            { "type": "dictionary_substring", "paths": ["web2-gt3"], "match_backwards": true }
+        ]
+    }"#;
+
+    #[cfg(feature = "yaml")]
+    const YAML_POLICY: &str = r#"
+rules:
+  - type: length
+    min: 8
+    max: 16
+  - type: character_characteristics
+    num_characteristics: 3
+    rules:
+      - data: english.digit
+      - data: english.special
+      - data: english.uppercase
+      - data: english.lowercase
+  - type: whitespace
+  - type: illegal_sequence
+    sequence: english.usqwerty
+  - type: dictionary_substring
+    paths: [web2-gt3]
+    match_backwards: true
+"#;
+
+    fn loader() -> PolicyLoader {
+        PolicyLoader::new().with_base_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/resources/test"))
+    }
+
+    #[test]
+    fn test_formats() {
+        #[cfg_attr(not(any(feature = "json", feature = "yaml")), allow(unused_mut))]
+        let mut formats = vec![(TOML_POLICY, PolicyFormat::Toml)];
+        #[cfg(feature = "json")]
+        formats.push((JSON_POLICY, PolicyFormat::Json));
+        #[cfg(feature = "yaml")]
+        formats.push((YAML_POLICY, PolicyFormat::Yaml));
+        for (text, format) in formats {
+            let validator = loader().load_str(text, format).unwrap();
+            let test_cases: Vec<RulePasswordTestItem> = vec![
+                RulePasswordTestItem(
+                    Box::new(validator.clone()),
+                    PasswordData::with_password("p4T3t#N65".to_string()),
+                    vec![],
+                ),
+                RulePasswordTestItem(
+                    Box::new(validator.clone()),
+                    PasswordData::with_password("p4T3t#".to_string()),
+                    vec![length::ERROR_CODE_MIN],
+                ),
+                RulePasswordTestItem(
+                    Box::new(validator.clone()),
+                    PasswordData::with_password("AycD Pd5yz".to_string()),
+                    vec![whitespace::ERROR_CODE],
+                ),
+                RulePasswordTestItem(
+                    Box::new(validator.clone()),
+                    PasswordData::with_password("p4zxcvb#n65".to_string()),
+                    vec![EnglishSequenceData::USQwerty.error_code()],
+                ),
+                RulePasswordTestItem(
+                    Box::new(validator.clone()),
+                    PasswordData::with_password("p4t3t#none".to_string()),
+                    vec![dictionary::ERROR_CODE],
+                ),
+            ];
+            check_passwords(test_cases);
+        }
+    }
+
+    #[test]
+    fn test_load_path() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/test/policy.toml");
+        let validator = PolicyLoader::new().load_path(path).unwrap();
+        assert_eq!(5, validator.rules().len());
+        assert!(
+            !validator
+                .validate(&PasswordData::with_password("p4t3t#none".to_string()))
+                .valid()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = [
+            (
+                "rules = 1",
+                PolicyError::invalid("rules", "expected array but found integer".to_string()),
+            ),
+            (
+                "[[rules]]\nmin = 8",
+                PolicyError::invalid("rules[0]", "missing parameter `type`".to_string()),
+            ),
+            (
+                "[[rules]]\ntype = \"lenght\"",
+                PolicyError::invalid("rules[0].type", "unknown rule type \"lenght\"".to_string()),
+            ),
+            (
+                "[[rules]]\ntype = \"length\"\nmin = 8\nmaximum = 10",
+                PolicyError::invalid("rules[0].maximum", "unknown parameter".to_string()),
+            ),
+            (
+                "[[rules]]\ntype = \"length\"\nmin = 10\nmax = 8",
+                PolicyError::invalid("rules[0]", "`min` must be <= `max`".to_string()),
+            ),
+            (
+                "[[rules]]\ntype = \"character\"\ndata = \"english.digit\"\nminimum = 0",
+                PolicyError::invalid(
+                    "rules[0]",
+                    "Number of characters must be greater than zero".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"character\"\ndata = \"klingon.digit\"",
+                PolicyError::invalid(
+                    "rules[0].data",
+                    "unknown character data \"klingon.digit\"".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"illegal_sequence\"\nsequence = \"english.dvorak\"",
+                PolicyError::invalid(
+                    "rules[0].sequence",
+                    "unknown sequence data \"english.dvorak\"".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"illegal_sequence\"\nsequence = \"english.alphabetical\"\nlength = 0",
+                PolicyError::invalid(
+                    "rules[0]",
+                    "the sequence length must be at least 3".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"whitespace\"\ncharacters = \" x\"",
+                PolicyError::invalid(
+                    "rules[0].characters",
+                    "character 'x' is not whitespace".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"dictionary\"\npaths = [\"missing.txt\"]",
+                PolicyError::invalid(
+                    "rules[0].paths[0]",
+                    "cannot open missing.txt: No such file or directory (os error 2)".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"length_complexity\"\n\
+                 [[rules.ranges]]\nstart = 0\nend = 12\nrules = [{ type = \"length\", min = 8 }]\n\
+                 [[rules.ranges]]\nstart = 10\nend = 20\nrules = [{ type = \"length\", min = 8 }]",
+                PolicyError::invalid(
+                    "rules[0].ranges[1]",
+                    "Interval 10..20 intersects existing interval 0..12".to_string(),
+                ),
+            ),
+        ];
+        for (text, expected) in cases {
+            let result = PolicyLoader::new().load_str(text, PolicyFormat::Toml);
+            assert_eq!(Some(expected), result.err(), "{text}");
+        }
+        assert!(matches!(
+            PolicyLoader::new().load_str("[[rules]", PolicyFormat::Toml),
+            Err(PolicyError::Syntax(_))
+        ));
+    }
+}
@@ -0,0 +1,948 @@
+//! Conversion between validators and the `passwordrules` markup understood by browsers and password managers, e.g.
+//! `minlength: 8; required: upper; required: digit; allowed: ascii-printable;`.
+use crate::password_generator::PasswordGenerator;
+use crate::policy::{PolicyError, Unsupported};
+use crate::rule::allowed_character::{AllowedCharacter, MatchBehavior};
+use crate::rule::character::CharacterRule;
+use crate::rule::character_characteristics::CharacterCharacteristics;
+use crate::rule::character_data::{CharacterData, CustomCharacterData, EnglishCharacterData};
+use crate::rule::error_code::ErrorCode;
+use crate::rule::illegal_regex::IllegalRegexRule;
+use crate::rule::length::LengthRule;
+use crate::rule::password_validator::PasswordValidator;
+use crate::rule::repeat_character::RepeatCharacterRule;
+use crate::rule::rule_result::CountCategory;
+use crate::rule::{PasswordData, Rule};
+use fancy_regex::Regex;
+use std::collections::BTreeSet;
+
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const DIGIT: &str = "0123456789";
+/// The `special` class without the space, which the specification includes but passay's special character data do
+/// not.
+const SPECIAL: &str = "-~!@#$%^&*_+=`|(){}[:;\"'<>,.?]";
+const ASCII_PRINTABLE: &str = "ascii-printable";
+const MAX_GENERATE_ATTEMPTS: usize = 100;
+
+/// The markup and the plain-language summary of a validator.
+#[derive(Debug, Clone)]
+pub struct PasswordRulesExport {
+    markup: String,
+    summary: Vec<String>,
+    unsupported: Vec<Unsupported>,
+}
+
+impl PasswordRulesExport {
+    /// Returns the `passwordrules` markup. It may be stricter than the validator, e.g. when character data also
+    /// contain non-ASCII characters, but a password satisfying it also satisfies the exported rules.
+    pub fn markup(&self) -> &str {
+        &self.markup
+    }
+
+    /// Returns one sentence per requirement of the supported rules.
+    pub fn summary(&self) -> &[String] {
+        &self.summary
+    }
+
+    /// Returns the rules, identified by their path, that are not or only partially expressed by the markup.
+    pub fn unsupported(&self) -> &[Unsupported] {
+        &self.unsupported
+    }
+}
+
+/// Exports the [LengthRule], [CharacterRule], [CharacterCharacteristics], [AllowedCharacter] and
+/// [RepeatCharacterRule] rules of a validator, including nested validators, as `passwordrules` markup. Any other rule
+/// is reported as unsupported.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::policy::password_rules;
+///  use passay_rs::rule::character::CharacterRule;
+///  use passay_rs::rule::character_data::EnglishCharacterData;
+///  use passay_rs::rule::length::LengthRule;
+///  use passay_rs::rule::password_validator::PasswordValidator;
+///  use passay_rs::rule::Rule;
+///
+///  let rules: Vec<Box<dyn Rule>> = vec![
+///      Box::new(LengthRule::new(8, 64)),
+///      Box::new(CharacterRule::from_character_data(Box::new(EnglishCharacterData::UpperCase))),
+///      Box::new(CharacterRule::from_character_data(Box::new(EnglishCharacterData::Digit))),
+///  ];
+///  let export = password_rules::export(&PasswordValidator::new(rules));
+///  assert_eq!(
+///      "minlength: 8; maxlength: 64; required: upper; required: digit; allowed: ascii-printable;",
+///      export.markup()
+///  );
+///  assert_eq!("Must contain at least 1 uppercase letter", export.summary()[1]);
+/// ```
+pub fn export(validator: &PasswordValidator) -> PasswordRulesExport {
+    let mut exporter = Exporter::default();
+    exporter.visit(validator.rules(), "rules");
+
+    let mut properties = vec![];
+    if let Some(min_length) = exporter.min_length.filter(|&l| l > 0) {
+        properties.push(format!("minlength: {min_length}"));
+    }
+    if let Some(max_length) = exporter.max_length.filter(|&l| l < usize::MAX) {
+        properties.push(format!("maxlength: {max_length}"));
+    }
+    for classes in &exporter.required {
+        properties.push(format!("required: {}", classes.join(", ")));
+    }
+    match &exporter.allowed {
+        Some(classes) => properties.push(format!("allowed: {}", classes.join(", "))),
+        None => properties.push(format!("allowed: {ASCII_PRINTABLE}")),
+    }
+    if let Some(max_consecutive) = exporter.max_consecutive {
+        properties.push(format!("max-consecutive: {max_consecutive}"));
+    }
+    PasswordRulesExport {
+        markup: format!("{};", properties.join("; ")),
+        summary: exporter.summary,
+        unsupported: exporter.unsupported,
+    }
+}
+
+#[derive(Default)]
+struct Exporter {
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    required: Vec<Vec<String>>,
+    allowed: Option<Vec<String>>,
+    allowed_characters: Option<BTreeSet<char>>,
+    max_consecutive: Option<usize>,
+    summary: Vec<String>,
+    unsupported: Vec<Unsupported>,
+}
+
+impl Exporter {
+    fn visit(&mut self, rules: &[Box<dyn Rule>], path: &str) {
+        for (i, rule) in rules.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            let any = rule.as_any();
+            if let Some(rule) = any.and_then(|a| a.downcast_ref::<PasswordValidator>()) {
+                self.visit(rule.rules(), &format!("{path}.rules"));
+            } else if let Some(rule) = any.and_then(|a| a.downcast_ref::<LengthRule>()) {
+                self.length(rule);
+            } else if let Some(rule) = any.and_then(|a| a.downcast_ref::<CharacterRule>()) {
+                self.character(rule, &path);
+            } else if let Some(rule) =
+                any.and_then(|a| a.downcast_ref::<CharacterCharacteristics>())
+            {
+                self.characteristics(rule, &path);
+            } else if let Some(rule) = any.and_then(|a| a.downcast_ref::<AllowedCharacter>()) {
+                self.allowed(rule, &path);
+            } else if let Some(rule) = any.and_then(|a| a.downcast_ref::<RepeatCharacterRule>()) {
+                self.repeat(rule);
+            } else {
+                self.unsupported.push(Unsupported::new(
+                    &path,
+                    "rule cannot be expressed in passwordrules markup",
+                ));
+            }
+        }
+    }
+
+    fn length(&mut self, rule: &LengthRule) {
+        let (min, max) = (rule.min_length(), rule.max_length());
+        self.min_length = Some(self.min_length.map_or(min, |l| l.max(min)));
+        self.max_length = Some(self.max_length.map_or(max, |l| l.min(max)));
+        let sentence = match (min, max) {
+            (0, usize::MAX) => return,
+            (min, max) if min == max => format!("Must be exactly {min} characters long"),
+            (min, usize::MAX) => format!("Must be at least {min} characters long"),
+            (0, max) => format!("Must be at most {max} characters long"),
+            (min, max) => format!("Must be between {min} and {max} characters long"),
+        };
+        self.summary.push(sentence);
+    }
+
+    fn character(&mut self, rule: &CharacterRule, path: &str) {
+        if self.require(&[rule], path) {
+            self.summary.push(format!("Must contain {}", describe(rule)));
+        }
+    }
+
+    fn characteristics(&mut self, rule: &CharacterCharacteristics, path: &str) {
+        let rules = rule.rules();
+        let required = rule.num_characteristics();
+        let expressed = if required == rules.len() {
+            rules.iter().enumerate().fold(true, |expressed, (i, r)| {
+                self.require(&[r], &format!("{path}.rules[{i}]")) && expressed
+            })
+        } else if required == 1 {
+            self.require(&rules.iter().collect::<Vec<_>>(), path)
+        } else {
+            self.unsupported.push(Unsupported::new(
+                path,
+                &format!(
+                    "requiring {required} of {} character rules cannot be expressed",
+                    rules.len()
+                ),
+            ));
+            false
+        };
+        if expressed {
+            let descriptions = rules.iter().map(describe).collect::<Vec<_>>();
+            self.summary.push(format!(
+                "Must contain {required} of the following: {}",
+                descriptions.join(", ")
+            ));
+        }
+    }
+
+    /// Adds a `required` property for the union of the given rules, returns whether it could be expressed at all.
+    fn require(&mut self, rules: &[&CharacterRule], path: &str) -> bool {
+        let characters = rules.iter().map(|r| r.valid_characters()).collect::<String>();
+        let Some(classes) = classes(&characters) else {
+            self.unsupported.push(Unsupported::new(
+                path,
+                "character data contain no printable ASCII characters",
+            ));
+            return false;
+        };
+        if let [rule] = rules
+            && rule.num_characters() > 1
+        {
+            self.unsupported.push(Unsupported::new(
+                path,
+                &format!(
+                    "only one of {} required characters can be expressed",
+                    rule.num_characters()
+                ),
+            ));
+        }
+        self.required.push(classes);
+        true
+    }
+
+    fn allowed(&mut self, rule: &AllowedCharacter, path: &str) {
+        if *rule.match_behavior() != MatchBehavior::Contains {
+            self.unsupported.push(Unsupported::new(
+                path,
+                &format!(
+                    "match behavior {} cannot be expressed",
+                    rule.match_behavior()
+                ),
+            ));
+            return;
+        }
+        let characters: BTreeSet<char> = rule.allowed_characters().chars().collect();
+        let characters = match self.allowed_characters.take() {
+            Some(previous) => previous.intersection(&characters).copied().collect(),
+            None => characters,
+        };
+        let Some(classes) = classes(&characters.iter().collect::<String>()) else {
+            self.unsupported.push(Unsupported::new(
+                path,
+                "allowed characters contain no printable ASCII characters",
+            ));
+            return;
+        };
+        self.allowed = Some(classes);
+        self.allowed_characters = Some(characters);
+        if rule.allowed_characters().chars().all(|c| c == ' ' || c.is_ascii_graphic())
+            && (' '..='~').all(|c| rule.allowed_characters().contains(c))
+        {
+            self.summary.push("Must only contain printable ASCII characters".to_string());
+        } else {
+            self.summary.push(format!(
+                "Must only contain characters from {:?}",
+                rule.allowed_characters()
+            ));
+        }
+    }
+
+    fn repeat(&mut self, rule: &RepeatCharacterRule) {
+        let max = rule.sequence_length() - 1;
+        self.max_consecutive = Some(self.max_consecutive.map_or(max, |m| m.min(max)));
+        self.summary.push(format!(
+            "Must not contain the same character {} or more times in a row",
+            rule.sequence_length()
+        ));
+    }
+}
+
+/// Returns the character classes matching the printable ASCII characters of the given set, named classes first and
+/// the remaining characters as custom class.
+fn classes(characters: &str) -> Option<Vec<String>> {
+    let mut remaining: BTreeSet<char> =
+        characters.chars().filter(|&c| c == ' ' || c.is_ascii_graphic()).collect();
+    if remaining.is_empty() {
+        return None;
+    }
+    if (' '..='~').all(|c| remaining.contains(&c)) {
+        return Some(vec![ASCII_PRINTABLE.to_string()]);
+    }
+    let mut classes = vec![];
+    for (name, class) in
+        [("upper", UPPER), ("lower", LOWER), ("digit", DIGIT), ("special", SPECIAL)]
+    {
+        if class.chars().all(|c| remaining.contains(&c)) {
+            class.chars().for_each(|c| {
+                remaining.remove(&c);
+            });
+            classes.push(name.to_string());
+        }
+    }
+    if !remaining.is_empty() {
+        classes.push(custom_class(&remaining));
+    }
+    Some(classes)
+}
+
+/// Formats a custom class, where `-` has to come first and `]` last.
+fn custom_class(characters: &BTreeSet<char>) -> String {
+    let mut class = String::from("[");
+    if characters.contains(&'-') {
+        class.push('-');
+    }
+    class.extend(characters.iter().filter(|&&c| c != '-' && c != ']'));
+    if characters.contains(&']') {
+        class.push(']');
+    }
+    class.push(']');
+    class
+}
+
+fn describe(rule: &CharacterRule) -> String {
+    let count = rule.num_characters();
+    let plural = if count == 1 { "" } else { "s" };
+    let noun = match ErrorCode::from(rule.character_data().error_code()) {
+        ErrorCode::InsufficientLowercase => "lowercase letter",
+        ErrorCode::InsufficientUppercase => "uppercase letter",
+        ErrorCode::InsufficientAlphabetical => "letter",
+        ErrorCode::InsufficientDigit => "digit",
+        ErrorCode::InsufficientSpecial => "special character",
+        ErrorCode::InsufficientSpace => "space",
+        _ => {
+            return format!(
+                "at least {count} character{plural} from {:?}",
+                rule.valid_characters()
+            );
+        }
+    };
+    format!("at least {count} {noun}{plural}")
+}
+
+/// A set of characters named by a `required` or `allowed` property.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CharacterSet {
+    characters: BTreeSet<char>,
+    /// Set by the `unicode` class, which matches every character.
+    unicode: bool,
+}
+
+impl CharacterSet {
+    fn ascii_printable() -> Self {
+        CharacterSet {
+            characters: (' '..='~').collect(),
+            unicode: false,
+        }
+    }
+
+    fn extend(&mut self, other: &CharacterSet) {
+        self.characters.extend(&other.characters);
+        self.unicode |= other.unicode;
+    }
+
+    /// Returns the characters a generator may pick from this set.
+    fn generator_characters(&self) -> String {
+        match self.unicode {
+            true => (' '..='~').collect(),
+            false => self.characters.iter().collect(),
+        }
+    }
+
+    fn character_data(&self) -> Box<dyn CharacterData> {
+        let characters = self.characters.iter().collect::<String>();
+        match characters.as_str() {
+            UPPER => Box::new(EnglishCharacterData::UpperCase),
+            LOWER => Box::new(EnglishCharacterData::LowerCase),
+            DIGIT => Box::new(EnglishCharacterData::Digit),
+            _ if characters.chars().eq(sorted(SPECIAL)) => Box::new(CustomCharacterData::new(
+                &characters,
+                "INSUFFICIENT_SPECIAL",
+                Some(CountCategory::Special),
+            )),
+            _ => Box::new(CustomCharacterData::from_chars(
+                &characters,
+                "INSUFFICIENT_CHARACTERS",
+            )),
+        }
+    }
+}
+
+fn sorted(characters: &str) -> impl Iterator<Item = char> {
+    characters.chars().collect::<BTreeSet<char>>().into_iter()
+}
+
+/// Requirements parsed from `passwordrules` markup.
+///
+/// As in the specification, the allowed characters are the union of all `required` and `allowed` classes, and default
+/// to `ascii-printable` when neither is given. The `special` class does not contain the space.
+#[derive(Debug, Clone)]
+pub struct PasswordRules {
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    max_consecutive: Option<usize>,
+    required: Vec<CharacterSet>,
+    allowed: CharacterSet,
+}
+
+impl PasswordRules {
+    pub fn min_length(&self) -> Option<usize> {
+        self.min_length
+    }
+
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    pub fn max_consecutive(&self) -> Option<usize> {
+        self.max_consecutive
+    }
+
+    /// Builds a validator enforcing the rules.
+    pub fn validator(&self) -> PasswordValidator {
+        let mut rules: Vec<Box<dyn Rule>> = vec![];
+        if self.min_length.is_some() || self.max_length.is_some() {
+            rules.push(Box::new(LengthRule::new(
+                self.min_length.unwrap_or(0),
+                self.max_length.unwrap_or(usize::MAX),
+            )));
+        }
+        for set in self.required.iter().filter(|set| !set.unicode) {
+            rules.push(Box::new(CharacterRule::from_character_data(
+                set.character_data(),
+            )));
+        }
+        if !self.allowed.unicode {
+            let allowed = self.allowed.characters.iter().collect::<String>();
+            rules.push(Box::new(AllowedCharacter::from_chars(&allowed)));
+        }
+        if let Some(max) = self.max_consecutive {
+            match RepeatCharacterRule::with_sequence_len(max + 1) {
+                Ok(rule) => rules.push(Box::new(rule)),
+                Err(_) => {
+                    let regex = Regex::new(&format!("(.)\\1{{{max}}}")).expect("valid pattern");
+                    rules.push(Box::new(IllegalRegexRule::from(regex)));
+                }
+            }
+        }
+        PasswordValidator::new(rules)
+    }
+
+    /// Builds the character rules for [PasswordGenerator::generate_password]: one per `required` property and one for
+    /// the allowed characters, so that generated passwords only contain allowed characters. Characters are limited to
+    /// printable ASCII, also for the `unicode` class.
+    pub fn character_rules(&self) -> Vec<CharacterRule> {
+        let mut rules: Vec<CharacterRule> = self
+            .required
+            .iter()
+            .map(|set| {
+                let characters = set.generator_characters();
+                CharacterRule::from_character_data(Box::new(CustomCharacterData::from_chars(
+                    &characters,
+                    "INSUFFICIENT_CHARACTERS",
+                )))
+            })
+            .collect();
+        let required = self.required.iter().fold(CharacterSet::default(), |mut all, set| {
+            all.extend(set);
+            all
+        });
+        if self.allowed != required {
+            let characters = self.allowed.generator_characters();
+            rules.push(CharacterRule::from_character_data(Box::new(
+                CustomCharacterData::from_chars(&characters, "INSUFFICIENT_CHARACTERS"),
+            )));
+        }
+        rules
+    }
+
+    /// Generates a password of the given length that satisfies the rules. As the generator does not know about
+    /// `max-consecutive`, candidates are validated and regenerated a bounded number of times.
+    pub fn generate_password(
+        &self,
+        generator: &mut PasswordGenerator,
+        length: usize,
+    ) -> Result<String, String> {
+        if self.min_length.is_some_and(|min| length < min)
+            || self.max_length.is_some_and(|max| length > max)
+        {
+            return Err(format!(
+                "length {length} is outside of the allowed range {}..={}",
+                self.min_length.unwrap_or(0),
+                self.max_length.unwrap_or(usize::MAX)
+            ));
+        }
+        let rules = self.character_rules();
+        let validator = self.validator();
+        for _ in 0..MAX_GENERATE_ATTEMPTS {
+            let password = generator.generate_password(length, &rules)?;
+            if validator.validate(&PasswordData::with_password(password.clone())).valid() {
+                return Ok(password);
+            }
+        }
+        Err(format!(
+            "no password satisfying the rules found in {MAX_GENERATE_ATTEMPTS} attempts"
+        ))
+    }
+}
+
+/// Parses `passwordrules` markup with the properties `required`, `allowed`, `max-consecutive`, `minlength` and
+/// `maxlength`. Property names are case-insensitive and unknown properties are ignored, as the specification demands.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::password_generator::PasswordGenerator;
+///  use passay_rs::policy::password_rules;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rules = password_rules::parse("minlength: 12; required: upper; required: digit; allowed: lower, [-_];")
+///      .unwrap();
+///  let password = rules.generate_password(&mut PasswordGenerator::new(), 16).unwrap();
+///  let result = rules.validator().validate(&PasswordData::with_password(password));
+///  assert!(result.valid());
+/// ```
+pub fn parse(markup: &str) -> Result<PasswordRules, PolicyError> {
+    let mut min_length: Option<usize> = None;
+    let mut max_length: Option<usize> = None;
+    let mut max_consecutive: Option<usize> = None;
+    let mut required = vec![];
+    let mut allowed: Option<CharacterSet> = None;
+
+    for (name, value) in properties(markup)? {
+        match name.to_lowercase().as_str() {
+            "minlength" => {
+                let length = integer(&name, &value)?;
+                min_length = Some(min_length.map_or(length, |l| l.max(length)));
+            }
+            "maxlength" => {
+                let length = integer(&name, &value)?;
+                max_length = Some(max_length.map_or(length, |l| l.min(length)));
+            }
+            "max-consecutive" => {
+                let max = integer(&name, &value)?;
+                if max == 0 {
+                    return Err(PolicyError::invalid(
+                        &name,
+                        "must be greater than zero".to_string(),
+                    ));
+                }
+                max_consecutive = Some(max_consecutive.map_or(max, |m| m.min(max)));
+            }
+            "required" => required.push(character_set(&name, &value)?),
+            "allowed" => allowed
+                .get_or_insert_with(CharacterSet::default)
+                .extend(&character_set(&name, &value)?),
+            _ => {}
+        }
+    }
+    if let (Some(min), Some(max)) = (min_length, max_length)
+        && min > max
+    {
+        return Err(PolicyError::invalid(
+            "minlength",
+            format!("minlength {min} must be <= maxlength {max}"),
+        ));
+    }
+    let allowed = match allowed {
+        None if required.is_empty() => CharacterSet::ascii_printable(),
+        allowed => required.iter().fold(allowed.unwrap_or_default(), |mut all, set| {
+            all.extend(set);
+            all
+        }),
+    };
+    Ok(PasswordRules {
+        min_length,
+        max_length,
+        max_consecutive,
+        required,
+        allowed,
+    })
+}
+
+/// Splits markup into its `name: value` properties. Semicolons inside custom classes do not end a property.
+fn properties(markup: &str) -> Result<Vec<(String, String)>, PolicyError> {
+    let mut properties = vec![];
+    let mut chars = markup.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ';').is_some() {}
+        if chars.peek().is_none() {
+            return Ok(properties);
+        }
+        let mut name = String::new();
+        while let Some(c) = chars.next_if(|&c| c != ':' && c != ';') {
+            name.push(c);
+        }
+        if chars.next() != Some(':') {
+            return Err(PolicyError::Syntax(format!(
+                "expected `:` after property {:?}",
+                name.trim()
+            )));
+        }
+        let mut value = String::new();
+        let mut in_class = false;
+        while let Some(c) = chars.next_if(|&c| in_class || c != ';') {
+            value.push(c);
+            if !in_class && c == '[' {
+                in_class = true;
+            } else if in_class && c == ']' && chars.peek() != Some(&']') {
+                in_class = false;
+            }
+        }
+        if in_class {
+            return Err(PolicyError::Syntax(format!(
+                "unterminated custom class in property {:?}",
+                name.trim()
+            )));
+        }
+        properties.push((name.trim().to_string(), value.trim().to_string()));
+    }
+}
+
+fn integer(name: &str, value: &str) -> Result<usize, PolicyError> {
+    value.parse().map_err(|_| {
+        PolicyError::invalid(
+            name,
+            format!("expected a non-negative integer but found {value:?}"),
+        )
+    })
+}
+
+/// Parses a comma separated list of character classes.
+fn character_set(name: &str, value: &str) -> Result<CharacterSet, PolicyError> {
+    let mut set = CharacterSet::default();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        if let Some(class) = rest.strip_prefix('[') {
+            // a `]` directly followed by another `]` is part of the class
+            let end = match class.find("]]") {
+                Some(i) if !class[..i].contains(']') => i + 1,
+                _ => class.find(']').unwrap_or(class.len()),
+            };
+            for c in class[..end].chars() {
+                if !(c == ' ' || c.is_ascii_graphic()) {
+                    return Err(PolicyError::invalid(
+                        name,
+                        format!("custom class contains non-printable or non-ASCII character {c:?}"),
+                    ));
+                }
+                set.characters.insert(c);
+            }
+            rest = class[(end + 1).min(class.len())..].trim_start();
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            let class = rest[..end].trim();
+            match class.to_lowercase().as_str() {
+                "upper" => set.characters.extend(UPPER.chars()),
+                "lower" => set.characters.extend(LOWER.chars()),
+                "digit" => set.characters.extend(DIGIT.chars()),
+                "special" => set.characters.extend(SPECIAL.chars()),
+                "ascii-printable" => set.characters.extend(' '..='~'),
+                "unicode" => set.unicode = true,
+                _ => {
+                    return Err(PolicyError::invalid(
+                        name,
+                        format!("unknown character class {class:?}"),
+                    ));
+                }
+            }
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+        if let Some(r) = rest.strip_prefix(',') {
+            rest = r.trim_start();
+        } else if !rest.is_empty() {
+            return Err(PolicyError::invalid(
+                name,
+                format!("expected `,` before {rest:?}"),
+            ));
+        }
+    }
+    if set.characters.is_empty() && !set.unicode {
+        return Err(PolicyError::invalid(
+            name,
+            "expected at least one character class".to_string(),
+        ));
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::password_generator::PasswordGenerator;
+    use crate::policy::password_rules::{export, parse};
+    use crate::policy::{PolicyError, Unsupported};
+    use crate::rule::PasswordData;
+    use crate::rule::Rule;
+    use crate::rule::allowed_character::{AllowedCharacter, MatchBehavior};
+    use crate::rule::character::CharacterRule;
+    use crate::rule::character_characteristics::CharacterCharacteristics;
+    use crate::rule::character_data::{
+        CustomCharacterData, CyrillicCharacterData, EnglishCharacterData,
+    };
+    use crate::rule::illegal_sequence::IllegalSequenceRule;
+    use crate::rule::length::LengthRule;
+    use crate::rule::password_validator::PasswordValidator;
+    use crate::rule::repeat_character::RepeatCharacterRule;
+    use crate::rule::sequence_data::EnglishSequenceData;
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    fn characteristics(num_characteristics: usize) -> CharacterCharacteristics {
+        let rules = vec![
+            CharacterRule::new(Box::new(EnglishCharacterData::Digit), 1).unwrap(),
+            CharacterRule::new(Box::new(EnglishCharacterData::Special), 1).unwrap(),
+            CharacterRule::new(Box::new(EnglishCharacterData::UpperCase), 1).unwrap(),
+            CharacterRule::new(Box::new(EnglishCharacterData::LowerCase), 1).unwrap(),
+        ];
+        CharacterCharacteristics::with_rules_and_characteristics(rules, num_characteristics)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_export() {
+        let nested: Vec<Box<dyn Rule>> = vec![Box::new(RepeatCharacterRule::default())];
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(LengthRule::new(12, 64)),
+            Box::new(characteristics(4)),
+            Box::new(CharacterRule::new(Box::new(EnglishCharacterData::Digit), 2).unwrap()),
+            Box::new(PasswordValidator::new(nested)),
+            Box::new(AllowedCharacter::from_chars(
+                "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-_]",
+            )),
+            Box::new(IllegalSequenceRule::with_sequence_data(
+                EnglishSequenceData::USQwerty,
+            )),
+        ];
+        let export = export(&PasswordValidator::new(rules));
+        assert_eq!(
+            "minlength: 12; maxlength: 64; required: digit; required: special, [/\\]; \
+             required: upper; required: lower; required: digit; \
+             allowed: upper, lower, digit, [-_]]; max-consecutive: 4;",
+            export.markup()
+        );
+        assert_eq!(
+            vec![
+                "Must be between 12 and 64 characters long",
+                "Must contain 4 of the following: at least 1 digit, at least 1 special character, \
+                 at least 1 uppercase letter, at least 1 lowercase letter",
+                "Must contain at least 2 digits",
+                "Must not contain the same character 5 or more times in a row",
+                "Must only contain characters from \
+                 \"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-_]\"",
+            ],
+            export.summary()
+        );
+        assert_eq!(
+            &[
+                Unsupported::new(
+                    "rules[2]",
+                    "only one of 2 required characters can be expressed"
+                ),
+                Unsupported::new(
+                    "rules[5]",
+                    "rule cannot be expressed in passwordrules markup"
+                ),
+            ],
+            export.unsupported()
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(characteristics(3)),
+            Box::new(CharacterRule::from_character_data(Box::new(
+                CyrillicCharacterData::LowerCase,
+            ))),
+            Box::new(AllowedCharacter::new(
+                "abc",
+                MatchBehavior::StartsWith,
+                true,
+            )),
+            Box::new(CharacterRule::from_character_data(Box::new(
+                CustomCharacterData::from_chars("xyz", "INSUFFICIENT_XYZ"),
+            ))),
+        ];
+        let export = export(&PasswordValidator::new(rules));
+        assert_eq!(
+            "required: [xyz]; allowed: ascii-printable;",
+            export.markup()
+        );
+        assert_eq!(
+            vec!["Must contain at least 1 character from \"xyz\""],
+            export.summary()
+        );
+        assert_eq!(
+            &[
+                Unsupported::new(
+                    "rules[0]",
+                    "requiring 3 of 4 character rules cannot be expressed"
+                ),
+                Unsupported::new(
+                    "rules[1]",
+                    "character data contain no printable ASCII characters"
+                ),
+                Unsupported::new("rules[2]", "match behavior starts with cannot be expressed"),
+            ],
+            export.unsupported()
+        );
+    }
+
+    #[test]
+    fn test_single_characteristic() {
+        let rules: Vec<Box<dyn Rule>> = vec![
+            Box::new(characteristics(1)),
+            Box::new(AllowedCharacter::from_chars(
+                &(' '..='~').collect::<String>(),
+            )),
+        ];
+        let export = export(&PasswordValidator::new(rules));
+        assert_eq!(
+            "required: upper, lower, digit, special, [/\\]; allowed: ascii-printable;",
+            export.markup()
+        );
+        assert_eq!(
+            "Must only contain printable ASCII characters",
+            export.summary()[1]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let rules = parse(
+            "minlength: 10; maxlength: 20; required: lower; required: upper, digit; \
+             allowed: [-_]]; max-consecutive: 2; unknown: ignored",
+        )
+        .unwrap();
+        assert_eq!(Some(10), rules.min_length());
+        assert_eq!(Some(20), rules.max_length());
+        assert_eq!(Some(2), rules.max_consecutive());
+        let validator = || Box::new(rules.validator());
+        let test_cases = vec![
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::with_password("abcDEF12-]".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::with_password("abcdef-_]x".to_string()),
+                vec!["INSUFFICIENT_CHARACTERS"],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::with_password("ABCDEF1234!".to_string()),
+                vec!["INSUFFICIENT_LOWERCASE", "ALLOWED_CHAR"],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::with_password("aaaBCD12345".to_string()),
+                vec!["ILLEGAL_MATCH"],
+            ),
+            RulePasswordTestItem(
+                validator(),
+                PasswordData::with_password("short1A".to_string()),
+                vec!["TOO_SHORT"],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut generator = PasswordGenerator::new();
+        for markup in [
+            "minlength: 12; required: upper; required: digit; required: special; allowed: lower;",
+            "required: digit; max-consecutive: 1",
+            "required: [-]; allowed: unicode; max-consecutive: 3",
+            "maxlength: 8",
+        ] {
+            let rules = parse(markup).unwrap();
+            let validator = rules.validator();
+            for _ in 0..50 {
+                let length = rules.min_length().unwrap_or(8);
+                let password = rules.generate_password(&mut generator, length).unwrap();
+                let result = validator.validate(&PasswordData::with_password(password.clone()));
+                assert!(result.valid(), "{markup}: {password}");
+            }
+        }
+        let rules = parse("minlength: 12").unwrap();
+        assert_eq!(
+            Err("length 8 is outside of the allowed range 12..=18446744073709551615".to_string()),
+            rules.generate_password(&mut generator, 8)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let markup =
+            "minlength: 8; maxlength: 64; required: upper; required: digit; allowed: lower;";
+        let rules = parse(markup).unwrap();
+        assert_eq!(
+            "minlength: 8; maxlength: 64; required: upper; required: digit; \
+             allowed: upper, lower, digit;",
+            export(&rules.validator()).markup()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            (
+                "minlength: eight",
+                PolicyError::invalid(
+                    "minlength",
+                    "expected a non-negative integer but found \"eight\"".to_string(),
+                ),
+            ),
+            (
+                "required: upper, emoji",
+                PolicyError::invalid("required", "unknown character class \"emoji\"".to_string()),
+            ),
+            (
+                "required: [ab] upper",
+                PolicyError::invalid("required", "expected `,` before \"upper\"".to_string()),
+            ),
+            (
+                "allowed: [abc",
+                PolicyError::Syntax(
+                    "unterminated custom class in property \"allowed\"".to_string(),
+                ),
+            ),
+            (
+                "minlength 8",
+                PolicyError::Syntax("expected `:` after property \"minlength 8\"".to_string()),
+            ),
+            (
+                "minlength: 10; maxlength: 8",
+                PolicyError::invalid(
+                    "minlength",
+                    "minlength 10 must be <= maxlength 8".to_string(),
+                ),
+            ),
+            (
+                "max-consecutive: 0",
+                PolicyError::invalid("max-consecutive", "must be greater than zero".to_string()),
+            ),
+            (
+                "required: [\u{e9}]",
+                PolicyError::invalid(
+                    "required",
+                    "custom class contains non-printable or non-ASCII character '\u{e9}'"
+                        .to_string(),
+                ),
+            ),
+        ];
+        for (markup, expected) in cases {
+            assert_eq!(Some(expected), parse(markup).err(), "{markup}");
+        }
+    }
+}
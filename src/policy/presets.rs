@@ -0,0 +1,360 @@
+//! Ready made password policies following common standards. Every preset is a plain struct whose fields can be
+//! overridden before the [PasswordValidator] is built.
+//!
+//! # Example
+//!
+//! ```
+//!  use passay_rs::policy::presets::PciDss4;
+//!  use passay_rs::rule::PasswordData;
+//!  use passay_rs::rule::Rule;
+//!
+//!  let validator = PciDss4 {
+//!      min_length: 15,
+//!      ..Default::default()
+//!  }
+//!  .validator()
+//!  .unwrap();
+//!  let result = validator.validate(&PasswordData::with_password("correct4horse".to_string()));
+//!  assert!(!result.valid());
+//! ```
+use crate::dictionary::common_passwords;
+use crate::dictionary::word_lists::ArrayWordList;
+use crate::dictionary::word_lists::sort::SliceSort;
+use crate::dictionary::word_lists::word_list_dictionary::WordListDictionary;
+use crate::rule::Rule;
+use crate::rule::character::CharacterRule;
+use crate::rule::character_characteristics::CharacterCharacteristics;
+use crate::rule::character_data::EnglishCharacterData;
+use crate::rule::dictionary::DictionaryRule;
+use crate::rule::history::HistoryRule;
+use crate::rule::length::LengthRule;
+use crate::rule::password_validator::PasswordValidator;
+use crate::rule::username::UsernameRule;
+
+/// Length limits and a blocklist of commonly used passwords without composition rules, as recommended by
+/// [NIST SP 800-63B](Self::nist), [OWASP ASVS](Self::owasp) and [CIS](Self::cis). Context specific words like the
+/// username are rejected as well.
+#[derive(Debug, Clone)]
+pub struct LengthAndBlocklist {
+    pub min_length: usize,
+    pub max_length: usize,
+    /// Passwords rejected regardless of case, defaults to [common_passwords].
+    pub blocklist: Vec<String>,
+    pub check_username: bool,
+}
+
+impl LengthAndBlocklist {
+    /// NIST SP 800-63B memorized secrets: a length between 8 and 64 characters.
+    pub fn nist() -> Self {
+        Self::new(8, 64)
+    }
+
+    /// OWASP ASVS v4 password security requirements (V2.1): at least 12 characters, passwords up to 128 characters
+    /// are allowed.
+    pub fn owasp() -> Self {
+        Self::new(12, 128)
+    }
+
+    /// CIS password policy guide for password only accounts: at least 14 characters, passwords up to 64 characters
+    /// are allowed.
+    pub fn cis() -> Self {
+        Self::new(14, 64)
+    }
+
+    fn new(min_length: usize, max_length: usize) -> Self {
+        Self {
+            min_length,
+            max_length,
+            blocklist: common_passwords(),
+            check_username: true,
+        }
+    }
+
+    pub fn validator(&self) -> Result<PasswordValidator, String> {
+        let mut rules: Vec<Box<dyn Rule>> =
+            vec![Box::new(length(self.min_length, self.max_length)?)];
+        if !self.blocklist.is_empty() {
+            let word_list =
+                ArrayWordList::with_sorter(self.blocklist.clone(), false, Some(SliceSort));
+            rules.push(Box::new(DictionaryRule::from_dictionary(
+                WordListDictionary::new(word_list),
+            )));
+        }
+        if self.check_username {
+            rules.push(Box::new(
+                UsernameRule::with_match_backwards_and_ignore_case(false, true),
+            ));
+        }
+        Ok(PasswordValidator::new(rules))
+    }
+}
+
+/// PCI-DSS v4 requirements 8.3.6 and 8.3.7: at least 12 characters containing both numeric and alphabetic
+/// characters, which must not match one of the previous passwords supplied as [crate::rule::PasswordData] references.
+#[derive(Debug, Clone)]
+pub struct PciDss4 {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub min_digits: usize,
+    pub min_alphabetical: usize,
+    pub check_history: bool,
+}
+
+impl Default for PciDss4 {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            max_length: usize::MAX,
+            min_digits: 1,
+            min_alphabetical: 1,
+            check_history: true,
+        }
+    }
+}
+
+impl PciDss4 {
+    pub fn validator(&self) -> Result<PasswordValidator, String> {
+        let mut rules: Vec<Box<dyn Rule>> =
+            vec![Box::new(length(self.min_length, self.max_length)?)];
+        if self.min_digits > 0 {
+            let digits =
+                CharacterRule::new(Box::new(EnglishCharacterData::Digit), self.min_digits)?;
+            rules.push(Box::new(digits));
+        }
+        if self.min_alphabetical > 0 {
+            let alphabetical = CharacterRule::new(
+                Box::new(EnglishCharacterData::Alphabetical),
+                self.min_alphabetical,
+            )?;
+            rules.push(Box::new(alphabetical));
+        }
+        if self.check_history {
+            rules.push(Box::new(HistoryRule::default()));
+        }
+        Ok(PasswordValidator::new(rules))
+    }
+}
+
+/// Windows Active Directory "password must meet complexity requirements": characters from 3 of the 4 categories
+/// uppercase, lowercase, digits and special characters, and no account name regardless of case. The length defaults
+/// to the domain policy minimum of 7 and the maximum of 256 characters.
+#[derive(Debug, Clone)]
+pub struct ActiveDirectoryComplexity {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub num_characteristics: usize,
+    pub check_username: bool,
+}
+
+impl Default for ActiveDirectoryComplexity {
+    fn default() -> Self {
+        Self {
+            min_length: 7,
+            max_length: 256,
+            num_characteristics: 3,
+            check_username: true,
+        }
+    }
+}
+
+impl ActiveDirectoryComplexity {
+    pub fn validator(&self) -> Result<PasswordValidator, String> {
+        let char_rules = vec![
+            CharacterRule::new(Box::new(EnglishCharacterData::UpperCase), 1)?,
+            CharacterRule::new(Box::new(EnglishCharacterData::LowerCase), 1)?,
+            CharacterRule::new(Box::new(EnglishCharacterData::Digit), 1)?,
+            CharacterRule::new(Box::new(EnglishCharacterData::Special), 1)?,
+        ];
+        let characteristics = CharacterCharacteristics::with_rules_and_characteristics(
+            char_rules,
+            self.num_characteristics,
+        )?;
+        let mut rules: Vec<Box<dyn Rule>> =
+            vec![Box::new(length(self.min_length, self.max_length)?), Box::new(characteristics)];
+        if self.check_username {
+            rules.push(Box::new(
+                UsernameRule::with_match_backwards_and_ignore_case(false, true),
+            ));
+        }
+        Ok(PasswordValidator::new(rules))
+    }
+}
+
+fn length(min_length: usize, max_length: usize) -> Result<LengthRule, String> {
+    if min_length > max_length {
+        return Err(format!(
+            "min_length {min_length} must be <= max_length {max_length}"
+        ));
+    }
+    Ok(LengthRule::new(min_length, max_length))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::presets::{ActiveDirectoryComplexity, LengthAndBlocklist, PciDss4};
+    use crate::rule::history::HistoricalReference;
+    use crate::rule::reference::Reference;
+    use crate::rule::{PasswordData, Rule, character_characteristics, dictionary};
+    use crate::rule::{history, length, username};
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    fn user(password: &str, username: &str) -> PasswordData {
+        PasswordData::with_password_and_user(password.to_string(), Some(username.to_string()))
+    }
+
+    fn item(
+        rule: Box<dyn Rule>,
+        password: PasswordData,
+        codes: Vec<&'static str>,
+    ) -> RulePasswordTestItem<'static> {
+        RulePasswordTestItem(rule, password, codes)
+    }
+
+    #[test]
+    fn test_nist() {
+        let nist = || Box::new(LengthAndBlocklist::nist().validator().unwrap());
+        let test_cases = vec![
+            item(nist(), user("correct horse", "alice"), vec![]),
+            item(
+                nist(),
+                user("letmein", "alice"),
+                vec![length::ERROR_CODE_MIN, dictionary::ERROR_CODE],
+            ),
+            item(
+                nist(),
+                user("PassWord123", "alice"),
+                vec![dictionary::ERROR_CODE],
+            ),
+            item(
+                nist(),
+                user("my name is Alice", "alice"),
+                vec![username::ERROR_CODE],
+            ),
+            item(
+                nist(),
+                user(&"a".repeat(65), "alice"),
+                vec![length::ERROR_CODE_MAX],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_owasp_and_cis() {
+        let owasp = || Box::new(LengthAndBlocklist::owasp().validator().unwrap());
+        let cis = || Box::new(LengthAndBlocklist::cis().validator().unwrap());
+        let test_cases = vec![
+            item(owasp(), user("correct horse", "alice"), vec![]),
+            item(owasp(), user("correct hors", "alice"), vec![]),
+            item(
+                owasp(),
+                user("correct hor", "alice"),
+                vec![length::ERROR_CODE_MIN],
+            ),
+            item(
+                owasp(),
+                user("Qwertyuiop", "alice"),
+                vec![length::ERROR_CODE_MIN, dictionary::ERROR_CODE],
+            ),
+            item(cis(), user("correct horse!", "alice"), vec![]),
+            item(
+                cis(),
+                user("correct horse", "alice"),
+                vec![length::ERROR_CODE_MIN],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_pci_dss() {
+        let pci = || Box::new(PciDss4::default().validator().unwrap());
+        let history: Vec<Box<dyn Reference>> = vec![Box::new(HistoricalReference::with_password(
+            "correct4horse".to_string(),
+        ))];
+        let test_cases = vec![
+            item(pci(), user("correct4horse", "alice"), vec![]),
+            item(
+                pci(),
+                user("correcthorse", "alice"),
+                vec!["INSUFFICIENT_DIGIT"],
+            ),
+            item(
+                pci(),
+                user("123456789012", "alice"),
+                vec!["INSUFFICIENT_ALPHABETICAL"],
+            ),
+            item(
+                pci(),
+                PasswordData::new("correct4horse".to_string(), None, history),
+                vec![history::ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_active_directory() {
+        let ad = || Box::new(ActiveDirectoryComplexity::default().validator().unwrap());
+        let test_cases = vec![
+            item(ad(), user("Summer24", "alice"), vec![]),
+            item(
+                ad(),
+                user("summer24", "alice"),
+                vec![
+                    "INSUFFICIENT_UPPERCASE",
+                    "INSUFFICIENT_SPECIAL",
+                    character_characteristics::ERROR_CODE,
+                ],
+            ),
+            item(ad(), user("S!mmer", "alice"), vec![length::ERROR_CODE_MIN]),
+            item(
+                ad(),
+                user("ALICE-2024", "alice"),
+                vec![username::ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let ad = ActiveDirectoryComplexity {
+            num_characteristics: 4,
+            check_username: false,
+            ..Default::default()
+        };
+        let nist = LengthAndBlocklist {
+            min_length: 15,
+            blocklist: vec![],
+            ..LengthAndBlocklist::nist()
+        };
+        let test_cases = vec![
+            item(
+                Box::new(ad.validator().unwrap()),
+                user("alice-2024", "alice"),
+                vec!["INSUFFICIENT_UPPERCASE", character_characteristics::ERROR_CODE],
+            ),
+            item(
+                Box::new(nist.validator().unwrap()),
+                user("qwertyuiop12345", "bob"),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+
+        let invalid = LengthAndBlocklist {
+            min_length: 200,
+            ..LengthAndBlocklist::owasp()
+        };
+        assert_eq!(
+            Some("min_length 200 must be <= max_length 128".to_string()),
+            invalid.validator().err()
+        );
+        let invalid = ActiveDirectoryComplexity {
+            num_characteristics: 5,
+            ..Default::default()
+        };
+        assert!(invalid.validator().is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use crate::policy::value::{Params, Value};
+use crate::policy::{PolicyError, PolicyLoader, rules};
+use crate::rule::Rule;
+use crate::rule::character_data::{CharacterData, CustomCharacterData};
+use crate::rule::sequence_data::SequenceData;
+use std::collections::{BTreeMap, HashMap};
+
+const DEFAULT_CUSTOM_ERROR_CODE: &str = "INSUFFICIENT_CHARACTERS";
+
+/// Builds a rule from its parameters. The loader gives access to the registry for nested rules and data.
+pub type RuleFactory = dyn Fn(&mut Params, &PolicyLoader) -> Result<Box<dyn Rule>, PolicyError>;
+/// Builds character data from its parameters.
+pub type CharacterDataFactory = dyn Fn(&mut Params) -> Result<Box<dyn CharacterData>, PolicyError>;
+/// Builds sequence data from its parameters.
+pub type SequenceDataFactory = dyn Fn(&mut Params) -> Result<Box<dyn SequenceData>, PolicyError>;
+
+/// Maps the names used in policy documents to factories of rules, [CharacterData] and [SequenceData].
+/// [Registry::default] contains every built-in rule and data set, custom implementations can be added next to them.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::policy::registry::Registry;
+///  use passay_rs::policy::{PolicyFormat, PolicyLoader};
+///  use passay_rs::rule::rule_result::RuleResult;
+///  use passay_rs::rule::{PasswordData, Rule};
+///
+///  struct NoPrefixRule(String);
+///  impl Rule for NoPrefixRule {
+///      fn validate(&self, password_data: &PasswordData) -> RuleResult {
+///          let mut result = RuleResult::default();
+///          if password_data.password().starts_with(&self.0) {
+///              result.add_error("ILLEGAL_PREFIX", None);
+///          }
+///          result
+///      }
+///  }
+///
+///  let mut registry = Registry::default();
+///  registry.register_rule("no_prefix", |params, _| {
+///      Ok(Box::new(NoPrefixRule(params.required_string("prefix")?)))
+///  });
+///  let policy = r#"
+///      [[rules]]
+///      type = "no_prefix"
+///      prefix = "acme"
+///  "#;
+///  let loader = PolicyLoader::new().with_registry(registry);
+///  let validator = loader.load_str(policy, PolicyFormat::Toml).unwrap();
+///  assert!(!validator.validate(&PasswordData::with_password("acme-2024".to_string())).valid());
+/// ```
+pub struct Registry {
+    rules: HashMap<String, Box<RuleFactory>>,
+    character_data: HashMap<String, Box<CharacterDataFactory>>,
+    sequence_data: HashMap<String, Box<SequenceDataFactory>>,
+}
+
+impl Registry {
+    /// Creates a registry without any entries.
+    pub fn empty() -> Self {
+        Self {
+            rules: HashMap::new(),
+            character_data: HashMap::new(),
+            sequence_data: HashMap::new(),
+        }
+    }
+
+    /// Registers a rule factory under the given type name, replacing any previous factory of that name.
+    pub fn register_rule<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&mut Params, &PolicyLoader) -> Result<Box<dyn Rule>, PolicyError> + 'static,
+    {
+        self.rules.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Registers a character data factory under the given name, replacing any previous factory of that name.
+    pub fn register_character_data<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&mut Params) -> Result<Box<dyn CharacterData>, PolicyError> + 'static,
+    {
+        self.character_data.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Registers a sequence data factory under the given name, replacing any previous factory of that name.
+    pub fn register_sequence_data<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&mut Params) -> Result<Box<dyn SequenceData>, PolicyError> + 'static,
+    {
+        self.sequence_data.insert(name.to_string(), Box::new(factory));
+    }
+
+    pub fn contains_rule(&self, name: &str) -> bool {
+        self.rules.contains_key(name)
+    }
+
+    /// Builds the rule described by a table with a `type` entry.
+    pub(super) fn build_rule(
+        &self,
+        loader: &PolicyLoader,
+        path: &str,
+        spec: &Value,
+    ) -> Result<Box<dyn Rule>, PolicyError> {
+        let mut params = Params::new(path, spec)?;
+        let rule_type = params.required_string("type")?;
+        let factory = self.rules.get(&rule_type).ok_or_else(|| {
+            PolicyError::invalid(
+                &params.path_of("type"),
+                format!("unknown rule type {rule_type:?}"),
+            )
+        })?;
+        let rule = factory(&mut params, loader)?;
+        params.finish()?;
+        Ok(rule)
+    }
+
+    /// Resolves the character data of a rule. It is either named by a `data` entry, given as a string or as a table
+    /// with a `type` and parameters, or defined by a custom set of `characters` with an optional `error_code`.
+    pub fn character_data(
+        &self,
+        params: &mut Params,
+    ) -> Result<Box<dyn CharacterData>, PolicyError> {
+        if let Some((name, path, mut data_params)) = named(params, "data")? {
+            let factory = self.character_data.get(&name).ok_or_else(|| {
+                PolicyError::invalid(&path, format!("unknown character data {name:?}"))
+            })?;
+            let data = factory(&mut data_params)?;
+            data_params.finish()?;
+            return Ok(data);
+        }
+        match params.string("characters")? {
+            Some(characters) => {
+                let error_code = params
+                    .string("error_code")?
+                    .unwrap_or_else(|| DEFAULT_CUSTOM_ERROR_CODE.to_string());
+                Ok(Box::new(CustomCharacterData::from_chars(
+                    &characters,
+                    &error_code,
+                )))
+            }
+            None => Err(PolicyError::invalid(
+                params.path(),
+                "missing parameter `data` or `characters`".to_string(),
+            )),
+        }
+    }
+
+    /// Resolves the sequence data named by the given entry, either a string or a table with a `type` and parameters.
+    pub fn sequence_data(
+        &self,
+        params: &mut Params,
+        key: &str,
+    ) -> Result<Box<dyn SequenceData>, PolicyError> {
+        let (name, path, mut data_params) = named(params, key)?.ok_or_else(|| {
+            PolicyError::invalid(params.path(), format!("missing parameter `{key}`"))
+        })?;
+        let factory = self.sequence_data.get(&name).ok_or_else(|| {
+            PolicyError::invalid(&path, format!("unknown sequence data {name:?}"))
+        })?;
+        let data = factory(&mut data_params)?;
+        data_params.finish()?;
+        Ok(data)
+    }
+}
+
+impl Default for Registry {
+    /// Creates a registry with all built-in rules and data.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        rules::register_builtins(&mut registry);
+        registry
+    }
+}
+
+/// Reads an entry naming a registered item, returning the name, the path to report it at and its parameters.
+fn named(params: &mut Params, key: &str) -> Result<Option<(String, String, Params)>, PolicyError> {
+    let path = params.path_of(key);
+    match params.take(key) {
+        None => Ok(None),
+        Some(Value::String(name)) => {
+            let empty = Params::new(&path, &Value::Table(BTreeMap::new()))?;
+            Ok(Some((name, path, empty)))
+        }
+        Some(table @ Value::Table(_)) => {
+            let mut data_params = Params::new(&path, &table)?;
+            let name = data_params.required_string("type")?;
+            Ok(Some((name, data_params.path_of("type"), data_params)))
+        }
+        Some(other) => Err(PolicyError::invalid(
+            &path,
+            format!("expected string or table but found {}", other.type_name()),
+        )),
+    }
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use crate::policy::registry::Registry;
+    use crate::policy::{PolicyError, PolicyFormat, PolicyLoader};
+    use crate::rule::PasswordData;
+    use crate::rule::character_data::CustomCharacterData;
+    use crate::rule::character_sequence::CharacterSequence;
+    use crate::rule::sequence_data::SequenceData;
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    struct HexSequenceData;
+
+    impl SequenceData for HexSequenceData {
+        fn error_code(&self) -> &str {
+            "ILLEGAL_HEX_SEQUENCE"
+        }
+
+        fn get_sequences(&self) -> Vec<CharacterSequence> {
+            vec![
+                CharacterSequence::new(vec![
+                    "0123456789abcdef".to_string(),
+                    "0123456789ABCDEF".to_string(),
+                ])
+                .unwrap(),
+            ]
+        }
+    }
+
+    fn registry() -> Registry {
+        let mut registry = Registry::default();
+        registry.register_sequence_data("hex", |_| Ok(Box::new(HexSequenceData)));
+        registry.register_character_data("vowel", |params| {
+            let upper = params.bool_or("upper", false)?;
+            let characters = if upper { "AEIOU" } else { "aeiou" };
+            Ok(Box::new(CustomCharacterData::from_chars(
+                characters,
+                "INSUFFICIENT_VOWEL",
+            )))
+        });
+        registry
+    }
+
+    #[test]
+    fn test_custom_data() {
+        let policy = r#"
+            [[rules]]
+            type = "illegal_sequence"
+            sequence = "hex"
+            length = 4
+
+            [[rules]]
+            type = "character"
+            data = { type = "vowel", upper = true }
+            minimum = 2
+        "#;
+        let loader = PolicyLoader::new().with_registry(registry());
+        let validator = loader.load_str(policy, PolicyFormat::Toml).unwrap();
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                Box::new(validator.clone()),
+                PasswordData::with_password("pAssEs9a".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(validator.clone()),
+                PasswordData::with_password("pAss89abEs".to_string()),
+                vec!["ILLEGAL_HEX_SEQUENCE"],
+            ),
+            RulePasswordTestItem(
+                Box::new(validator.clone()),
+                PasswordData::with_password("passes".to_string()),
+                vec!["INSUFFICIENT_VOWEL"],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_errors() {
+        let loader = PolicyLoader::new().with_registry(registry());
+        let cases = [
+            (
+                "[[rules]]\ntype = \"character\"\ndata = { type = \"vowel\", lower = true }",
+                PolicyError::invalid("rules[0].data.lower", "unknown parameter".to_string()),
+            ),
+            (
+                "[[rules]]\ntype = \"character\"\ndata = { type = \"consonant\" }",
+                PolicyError::invalid(
+                    "rules[0].data.type",
+                    "unknown character data \"consonant\"".to_string(),
+                ),
+            ),
+            (
+                "[[rules]]\ntype = \"illegal_sequence\"\nsequence = 5",
+                PolicyError::invalid(
+                    "rules[0].sequence",
+                    "expected string or table but found integer".to_string(),
+                ),
+            ),
+        ];
+        for (text, expected) in cases {
+            let result = loader.load_str(text, PolicyFormat::Toml);
+            assert_eq!(Some(expected), result.err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let loader = PolicyLoader::new().with_registry(Registry::empty());
+        let result = loader.load_str("[[rules]]\ntype = \"length\"", PolicyFormat::Toml);
+        assert_eq!(
+            Some(PolicyError::invalid(
+                "rules[0].type",
+                "unknown rule type \"length\"".to_string()
+            )),
+            result.err()
+        );
+        assert!(Registry::default().contains_rule("length"));
+    }
+}
@@ -0,0 +1,396 @@
+use crate::dictionary::DictionaryBuilder;
+use crate::dictionary::word_lists::ArrayWordList;
+use crate::dictionary::word_lists::word_list_dictionary::WordListDictionary;
+use crate::policy::registry::Registry;
+use crate::policy::value::Params;
+use crate::policy::{PolicyError, PolicyLoader};
+use crate::rule::allowed_character::{AllowedCharacter, MatchBehavior};
+use crate::rule::allowed_regex::AllowedRegex;
+use crate::rule::character::CharacterRule;
+use crate::rule::character_characteristics::CharacterCharacteristics;
+use crate::rule::character_data::{
+    CharacterData, CyrillicCharacterData, CyrillicModernCharacterData, CzechCharacterData,
+    EnglishCharacterData, GermanCharacterData, PolishCharacterData, UnicodeCharacterData,
+};
+use crate::rule::character_occurrences::CharacterOccurrences;
+use crate::rule::dictionary::DictionaryRule;
+use crate::rule::dictionary_substring::DictionarySubstringRule;
+use crate::rule::history::HistoryRule;
+use crate::rule::illegal_character::IllegalCharacterRule;
+use crate::rule::illegal_regex::IllegalRegexRule;
+use crate::rule::illegal_sequence::{DEFAULT_SEQUENCE_LENGTH, IllegalSequenceRule};
+use crate::rule::length::LengthRule;
+use crate::rule::length_complexity::LengthComplexityRule;
+use crate::rule::number_range::NumberRangeRule;
+use crate::rule::repeat_character::RepeatCharacterRule;
+use crate::rule::repeat_characters::RepeatCharactersRule;
+use crate::rule::sequence_data::{
+    CyrillicSequenceData, CzechSequenceData, EnglishSequenceData, GermanSequenceData,
+    PolishSequenceData, SequenceData,
+};
+use crate::rule::source::SourceRule;
+use crate::rule::username::UsernameRule;
+use crate::rule::whitespace::WhitespaceRule;
+use fancy_regex::Regex;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+const DEFAULT_WHITESPACE: &str = "\t\n\u{b}\u{c}\r ";
+/// Shorter sequences would flag ordinary character pairs.
+const MINIMUM_SEQUENCE_LENGTH: usize = 3;
+
+pub(super) fn register_builtins(registry: &mut Registry) {
+    registry.register_rule("length", |params, _| Ok(Box::new(length(params)?)));
+    registry.register_rule("character", |params, loader| {
+        Ok(Box::new(character(params, loader)?))
+    });
+    registry.register_rule("character_characteristics", |params, loader| {
+        Ok(Box::new(character_characteristics(params, loader)?))
+    });
+    registry.register_rule("character_occurrences", |params, _| {
+        Ok(Box::new(CharacterOccurrences::new(
+            params.required_usize("max_occurrences")?,
+        )))
+    });
+    registry.register_rule("allowed_character", |params, _| {
+        Ok(Box::new(AllowedCharacter::new(
+            &params.required_string("characters")?,
+            match_behavior(params)?,
+            params.bool_or("report_all", true)?,
+        )))
+    });
+    registry.register_rule("illegal_character", |params, _| {
+        Ok(Box::new(IllegalCharacterRule::new(
+            params.required_string("characters")?.chars().collect(),
+            match_behavior(params)?,
+            params.bool_or("report_all", true)?,
+        )))
+    });
+    registry.register_rule("allowed_regex", |params, _| {
+        Ok(Box::new(AllowedRegex::from_regex(regex(params)?)))
+    });
+    registry.register_rule("illegal_regex", |params, _| {
+        Ok(Box::new(IllegalRegexRule::new(
+            regex(params)?,
+            params.bool_or("report_all", true)?,
+        )))
+    });
+    registry.register_rule("illegal_sequence", |params, loader| {
+        let length = params.usize_or("length", DEFAULT_SEQUENCE_LENGTH)?;
+        if length < MINIMUM_SEQUENCE_LENGTH {
+            return Err(PolicyError::invalid(
+                params.path(),
+                format!("the sequence length must be at least {MINIMUM_SEQUENCE_LENGTH}"),
+            ));
+        }
+        Ok(Box::new(IllegalSequenceRule::new(
+            loader.registry().sequence_data(params, "sequence")?,
+            length,
+            params.bool_or("wrap", false)?,
+            params.bool_or("report_all", true)?,
+        )))
+    });
+    registry.register_rule("repeat_character", |params, _| {
+        let rule = RepeatCharacterRule::new(
+            params.usize_or("sequence_length", 5)?,
+            params.bool_or("report_all", true)?,
+        )
+        .map_err(|e| PolicyError::invalid(params.path(), e))?;
+        Ok(Box::new(rule))
+    });
+    registry.register_rule("repeat_characters", |params, _| {
+        let rule = RepeatCharactersRule::new(
+            params.usize_or("sequence_length", 5)?,
+            params.usize_or("sequence_count", 1)?,
+        )
+        .map_err(|e| PolicyError::invalid(params.path(), e))?;
+        Ok(Box::new(rule))
+    });
+    registry.register_rule("number_range", |params, _| {
+        Ok(Box::new(number_range(params)?))
+    });
+    registry.register_rule("whitespace", |params, _| Ok(Box::new(whitespace(params)?)));
+    registry.register_rule("username", |params, _| {
+        Ok(Box::new(UsernameRule::new(
+            params.bool_or("match_backwards", false)?,
+            params.bool_or("ignore_case", false)?,
+            match_behavior(params)?,
+        )))
+    });
+    registry.register_rule("history", |params, _| {
+        let report_all = params.bool_or("report_all", true)?;
+        match params.usize("depth")? {
+            Some(depth) => Ok(Box::new(
+                HistoryRule::with_depth(depth, report_all)
+                    .map_err(|e| PolicyError::invalid(params.path(), e))?,
+            )),
+            None => Ok(Box::new(HistoryRule::new(report_all))),
+        }
+    });
+    registry.register_rule("source", |params, _| {
+        Ok(Box::new(SourceRule::new(
+            params.bool_or("report_all", true)?,
+        )))
+    });
+    registry.register_rule("dictionary", |params, loader| {
+        let match_backwards = params.bool_or("match_backwards", false)?;
+        Ok(Box::new(DictionaryRule::new(
+            dictionary(params, loader)?,
+            match_backwards,
+        )))
+    });
+    registry.register_rule("dictionary_substring", |params, loader| {
+        let match_backwards = params.bool_or("match_backwards", false)?;
+        Ok(Box::new(DictionarySubstringRule::new(
+            dictionary(params, loader)?,
+            match_backwards,
+        )))
+    });
+    registry.register_rule("length_complexity", |params, loader| {
+        Ok(Box::new(length_complexity(params, loader)?))
+    });
+
+    register_character_data(registry, "english.lowercase", || {
+        EnglishCharacterData::LowerCase
+    });
+    register_character_data(registry, "english.uppercase", || {
+        EnglishCharacterData::UpperCase
+    });
+    register_character_data(registry, "english.digit", || EnglishCharacterData::Digit);
+    register_character_data(registry, "english.alphabetical", || {
+        EnglishCharacterData::Alphabetical
+    });
+    register_character_data(registry, "english.special", || {
+        EnglishCharacterData::Special
+    });
+    register_character_data(registry, "cyrillic.lowercase", || {
+        CyrillicCharacterData::LowerCase
+    });
+    register_character_data(registry, "cyrillic.uppercase", || {
+        CyrillicCharacterData::UpperCase
+    });
+    register_character_data(registry, "cyrillic_modern.lowercase", || {
+        CyrillicModernCharacterData::LowerCase
+    });
+    register_character_data(registry, "cyrillic_modern.uppercase", || {
+        CyrillicModernCharacterData::UpperCase
+    });
+    register_character_data(registry, "czech.lowercase", || {
+        CzechCharacterData::LowerCase
+    });
+    register_character_data(registry, "czech.uppercase", || {
+        CzechCharacterData::UpperCase
+    });
+    register_character_data(registry, "german.lowercase", || {
+        GermanCharacterData::LowerCase
+    });
+    register_character_data(registry, "german.uppercase", || {
+        GermanCharacterData::UpperCase
+    });
+    register_character_data(registry, "polish.lowercase", || {
+        PolishCharacterData::LowerCase
+    });
+    register_character_data(registry, "polish.uppercase", || {
+        PolishCharacterData::UpperCase
+    });
+    register_character_data(registry, "unicode.uppercase", || {
+        UnicodeCharacterData::UpperCase
+    });
+    register_character_data(registry, "unicode.lowercase", || {
+        UnicodeCharacterData::LowerCase
+    });
+    register_character_data(registry, "unicode.digit", || UnicodeCharacterData::Digit);
+    register_character_data(registry, "unicode.alphabetical", || {
+        UnicodeCharacterData::Alphabetical
+    });
+    register_character_data(registry, "unicode.punctuation", || {
+        UnicodeCharacterData::Punctuation
+    });
+    register_character_data(registry, "unicode.symbol", || UnicodeCharacterData::Symbol);
+    register_character_data(registry, "unicode.special", || {
+        UnicodeCharacterData::Special
+    });
+    register_character_data(registry, "unicode.space", || UnicodeCharacterData::Space);
+
+    register_sequence_data(registry, "english.alphabetical", || {
+        EnglishSequenceData::Alphabetical
+    });
+    register_sequence_data(registry, "english.numerical", || {
+        EnglishSequenceData::Numerical
+    });
+    register_sequence_data(registry, "english.usqwerty", || {
+        EnglishSequenceData::USQwerty
+    });
+    register_sequence_data(registry, "cyrillic.alphabetical", || {
+        CyrillicSequenceData::Alphabetical
+    });
+    register_sequence_data(registry, "czech.alphabetical", || {
+        CzechSequenceData::Alphabetical
+    });
+    register_sequence_data(registry, "german.alphabetical", || {
+        GermanSequenceData::Alphabetical
+    });
+    register_sequence_data(registry, "german.deqwertz", || GermanSequenceData::DEQwertz);
+    register_sequence_data(registry, "polish.alphabetical", || {
+        PolishSequenceData::Alphabetical
+    });
+}
+
+fn register_character_data<C, F>(registry: &mut Registry, name: &str, data: F)
+where
+    C: CharacterData + 'static,
+    F: Fn() -> C + 'static,
+{
+    registry.register_character_data(name, move |_| Ok(Box::new(data())));
+}
+
+fn register_sequence_data<S, F>(registry: &mut Registry, name: &str, data: F)
+where
+    S: SequenceData + 'static,
+    F: Fn() -> S + 'static,
+{
+    registry.register_sequence_data(name, move |_| Ok(Box::new(data())));
+}
+
+fn length(params: &mut Params) -> Result<LengthRule, PolicyError> {
+    if let Some(length) = params.usize("length")? {
+        return Ok(LengthRule::with_exact_length(length));
+    }
+    let min = params.usize_or("min", 0)?;
+    let max = params.usize_or("max", usize::MAX)?;
+    if min > max {
+        return Err(PolicyError::invalid(
+            params.path(),
+            "`min` must be <= `max`".to_string(),
+        ));
+    }
+    Ok(LengthRule::new(min, max))
+}
+
+fn character(params: &mut Params, loader: &PolicyLoader) -> Result<CharacterRule, PolicyError> {
+    let data = loader.registry().character_data(params)?;
+    CharacterRule::new(data, params.usize_or("minimum", 1)?)
+        .map_err(|e| PolicyError::invalid(params.path(), e))
+}
+
+fn character_characteristics(
+    params: &mut Params,
+    loader: &PolicyLoader,
+) -> Result<CharacterCharacteristics, PolicyError> {
+    let specs = params.array("rules")?.unwrap_or_default();
+    let mut rules = Vec::with_capacity(specs.len());
+    for (path, spec) in specs {
+        let mut rule_params = Params::new(&path, &spec)?;
+        rules.push(character(&mut rule_params, loader)?);
+        rule_params.finish()?;
+    }
+    let num_characteristics = params.usize_or("num_characteristics", 1)?;
+    CharacterCharacteristics::new(
+        rules,
+        num_characteristics,
+        params.bool_or("report_failure", true)?,
+        params.bool_or("report_rule_failures", true)?,
+    )
+    .map_err(|e| PolicyError::invalid(params.path(), e))
+}
+
+fn match_behavior(params: &mut Params) -> Result<MatchBehavior, PolicyError> {
+    match params.string("match_behavior")?.as_deref() {
+        None | Some("contains") => Ok(MatchBehavior::Contains),
+        Some("starts_with") => Ok(MatchBehavior::StartsWith),
+        Some("ends_with") => Ok(MatchBehavior::EndsWith),
+        Some(other) => Err(PolicyError::invalid(
+            &params.path_of("match_behavior"),
+            format!(
+                "unknown match behavior {other:?}, expected \"contains\", \"starts_with\" or \"ends_with\""
+            ),
+        )),
+    }
+}
+
+fn regex(params: &mut Params) -> Result<Regex, PolicyError> {
+    let pattern = params.required_string("pattern")?;
+    Regex::new(&pattern)
+        .map_err(|e| PolicyError::invalid(&params.path_of("pattern"), e.to_string()))
+}
+
+fn number_range(params: &mut Params) -> Result<NumberRangeRule, PolicyError> {
+    let mut bound = |key: &str| -> Result<isize, PolicyError> {
+        let value = params.integer(key)?.ok_or_else(|| {
+            PolicyError::invalid(params.path(), format!("missing parameter `{key}`"))
+        })?;
+        isize::try_from(value)
+            .map_err(|e| PolicyError::invalid(&params.path_of(key), e.to_string()))
+    };
+    let start = bound("start")?;
+    let end = bound("end")?;
+    Ok(NumberRangeRule::new(
+        start..end,
+        match_behavior(params)?,
+        params.bool_or("report_all", true)?,
+    ))
+}
+
+fn whitespace(params: &mut Params) -> Result<WhitespaceRule, PolicyError> {
+    let characters = params.string("characters")?.unwrap_or_else(|| DEFAULT_WHITESPACE.to_string());
+    if let Some(c) = characters.chars().find(|c| !c.is_whitespace()) {
+        return Err(PolicyError::invalid(
+            &params.path_of("characters"),
+            format!("character {c:?} is not whitespace"),
+        ));
+    }
+    Ok(WhitespaceRule::new(
+        characters.chars().collect(),
+        match_behavior(params)?,
+        params.bool_or("report_all", true)?,
+    ))
+}
+
+fn dictionary(
+    params: &mut Params,
+    loader: &PolicyLoader,
+) -> Result<WordListDictionary<ArrayWordList>, PolicyError> {
+    let paths = params.strings("paths")?.ok_or_else(|| {
+        PolicyError::invalid(params.path(), "missing parameter `paths`".to_string())
+    })?;
+    let mut builder =
+        DictionaryBuilder::new().case_sensitive(params.bool_or("case_sensitive", false)?);
+    for (i, path) in paths.iter().enumerate() {
+        let file = File::open(loader.resolve_path(path)).map_err(|e| {
+            PolicyError::invalid(
+                &params.path_of(&format!("paths[{i}]")),
+                format!("cannot open {path}: {e}"),
+            )
+        })?;
+        builder = builder.add_read(Box::new(file) as Box<dyn Read>);
+    }
+    Ok(builder.build())
+}
+
+fn length_complexity(
+    params: &mut Params,
+    loader: &PolicyLoader,
+) -> Result<LengthComplexityRule, PolicyError> {
+    let mut rule = LengthComplexityRule::new(
+        HashMap::new(),
+        params.bool_or("report_failure", true)?,
+        params.bool_or("report_rule_failures", true)?,
+    );
+    for (path, spec) in params.array("ranges")?.unwrap_or_default() {
+        let mut range_params = Params::new(&path, &spec)?;
+        let start = range_params.required_usize("start")?;
+        let end = range_params.required_usize("end")?;
+        if start >= end {
+            return Err(PolicyError::invalid(
+                &path,
+                "`start` must be < `end`".to_string(),
+            ));
+        }
+        let specs = range_params.array("rules")?.unwrap_or_default();
+        let rules = loader.build_rules(specs)?;
+        range_params.finish()?;
+        rule.add_rules(start..end, rules).map_err(|e| PolicyError::invalid(&path, e))?;
+    }
+    Ok(rule)
+}
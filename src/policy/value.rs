@@ -0,0 +1,321 @@
+use crate::policy::PolicyError;
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Format independent representation of a policy document. TOML, JSON and YAML documents are all read into this
+/// structure before rules are built from it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    Table(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Returns a short name of the value type used in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Bool(_) => "boolean",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Table(_) => "table",
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::Array(values) => write!(f, "array of {} values", values.len()),
+            Value::Table(table) => write!(f, "table of {} entries", table.len()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("a boolean, number, string, array or table")
+    }
+
+    fn visit_bool<E: Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Integer(v))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Value, E> {
+        i64::try_from(v)
+            .map(Value::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is too large")))
+    }
+
+    fn visit_f64<E: Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Float(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut table = BTreeMap::new();
+        while let Some((key, value)) = map.next_entry::<String, Value>()? {
+            table.insert(key, value);
+        }
+        Ok(Value::Table(table))
+    }
+}
+
+/// Parameters of a single table in a policy document. Every accessor removes the parameter it reads, so that
+/// [Params::finish] can report parameters that no rule understood.
+#[derive(Debug, Clone)]
+pub struct Params {
+    path: String,
+    table: BTreeMap<String, Value>,
+}
+
+impl Params {
+    /// Creates parameters from a table value found at the given document path.
+    pub fn new(path: &str, value: &Value) -> Result<Self, PolicyError> {
+        match value {
+            Value::Table(table) => Ok(Params {
+                path: path.to_string(),
+                table: table.clone(),
+            }),
+            other => Err(PolicyError::invalid(
+                path,
+                format!("expected a table but found {}", other.type_name()),
+            )),
+        }
+    }
+
+    /// Returns the document path of these parameters, e.g. `rules[2]`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the document path of the given parameter.
+    pub fn path_of(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    /// Returns whether a parameter with the given name is present and has not been read yet.
+    pub fn contains(&self, key: &str) -> bool {
+        self.table.contains_key(key)
+    }
+
+    /// Removes and returns the raw value of a parameter.
+    pub fn take(&mut self, key: &str) -> Option<Value> {
+        self.table.remove(key)
+    }
+
+    /// Removes and returns the raw value of a required parameter.
+    pub fn take_required(&mut self, key: &str) -> Result<Value, PolicyError> {
+        self.take(key)
+            .ok_or_else(|| PolicyError::invalid(&self.path, format!("missing parameter `{key}`")))
+    }
+
+    pub fn bool(&mut self, key: &str) -> Result<Option<bool>, PolicyError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Bool(b)) => Ok(Some(b)),
+            Some(other) => Err(self.type_error(key, "boolean", &other)),
+        }
+    }
+
+    pub fn bool_or(&mut self, key: &str, default: bool) -> Result<bool, PolicyError> {
+        Ok(self.bool(key)?.unwrap_or(default))
+    }
+
+    pub fn integer(&mut self, key: &str) -> Result<Option<i64>, PolicyError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Integer(i)) => Ok(Some(i)),
+            Some(other) => Err(self.type_error(key, "integer", &other)),
+        }
+    }
+
+    pub fn usize(&mut self, key: &str) -> Result<Option<usize>, PolicyError> {
+        match self.integer(key)? {
+            None => Ok(None),
+            Some(i) => usize::try_from(i).map(Some).map_err(|_| {
+                PolicyError::invalid(
+                    &self.path_of(key),
+                    format!("expected a non-negative integer but found {i}"),
+                )
+            }),
+        }
+    }
+
+    pub fn usize_or(&mut self, key: &str, default: usize) -> Result<usize, PolicyError> {
+        Ok(self.usize(key)?.unwrap_or(default))
+    }
+
+    pub fn required_usize(&mut self, key: &str) -> Result<usize, PolicyError> {
+        self.usize(key)?
+            .ok_or_else(|| PolicyError::invalid(&self.path, format!("missing parameter `{key}`")))
+    }
+
+    pub fn float(&mut self, key: &str) -> Result<Option<f64>, PolicyError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Float(x)) => Ok(Some(x)),
+            Some(Value::Integer(i)) => Ok(Some(i as f64)),
+            Some(other) => Err(self.type_error(key, "number", &other)),
+        }
+    }
+
+    pub fn string(&mut self, key: &str) -> Result<Option<String>, PolicyError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(other) => Err(self.type_error(key, "string", &other)),
+        }
+    }
+
+    pub fn required_string(&mut self, key: &str) -> Result<String, PolicyError> {
+        self.string(key)?
+            .ok_or_else(|| PolicyError::invalid(&self.path, format!("missing parameter `{key}`")))
+    }
+
+    pub fn strings(&mut self, key: &str) -> Result<Option<Vec<String>>, PolicyError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Array(values)) => {
+                let mut strings = Vec::with_capacity(values.len());
+                for (i, value) in values.into_iter().enumerate() {
+                    match value {
+                        Value::String(s) => strings.push(s),
+                        other => {
+                            return Err(self.type_error(&format!("{key}[{i}]"), "string", &other));
+                        }
+                    }
+                }
+                Ok(Some(strings))
+            }
+            Some(other) => Err(self.type_error(key, "array", &other)),
+        }
+    }
+
+    /// Removes an array parameter and returns its elements together with their document paths.
+    pub fn array(&mut self, key: &str) -> Result<Option<Vec<(String, Value)>>, PolicyError> {
+        let path = self.path_of(key);
+        match self.take(key) {
+            None => Ok(None),
+            Some(Value::Array(values)) => Ok(Some(
+                values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, value)| (format!("{path}[{i}]"), value))
+                    .collect(),
+            )),
+            Some(other) => Err(self.type_error(key, "array", &other)),
+        }
+    }
+
+    /// Fails if any parameter has not been read.
+    pub fn finish(self) -> Result<(), PolicyError> {
+        match self.table.keys().next() {
+            None => Ok(()),
+            Some(key) => Err(PolicyError::invalid(
+                &self.path_of(key),
+                "unknown parameter".to_string(),
+            )),
+        }
+    }
+
+    fn type_error(&self, key: &str, expected: &str, found: &Value) -> PolicyError {
+        PolicyError::invalid(
+            &self.path_of(key),
+            format!("expected {expected} but found {}", found.type_name()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::policy::PolicyError;
+    use crate::policy::value::{Params, Value};
+    use std::collections::BTreeMap;
+
+    fn params() -> Params {
+        let mut table = BTreeMap::new();
+        table.insert("min".to_string(), Value::Integer(8));
+        table.insert("negative".to_string(), Value::Integer(-1));
+        table.insert("wrap".to_string(), Value::String("yes".to_string()));
+        Params::new("rules[0]", &Value::Table(table)).unwrap()
+    }
+
+    #[test]
+    fn read_parameters() {
+        let mut params = params();
+        assert_eq!(Some(8), params.usize("min").unwrap());
+        assert_eq!(None, params.usize("min").unwrap());
+        assert_eq!(
+            Err(PolicyError::invalid(
+                "rules[0].negative",
+                "expected a non-negative integer but found -1".to_string()
+            )),
+            params.usize("negative")
+        );
+        assert_eq!(
+            Err(PolicyError::invalid(
+                "rules[0].wrap",
+                "expected boolean but found string".to_string()
+            )),
+            params.bool("wrap")
+        );
+        assert!(params.finish().is_ok());
+    }
+
+    #[test]
+    fn unknown_parameter() {
+        let mut params = params();
+        params.usize("min").unwrap();
+        assert_eq!(
+            Err(PolicyError::invalid(
+                "rules[0].negative",
+                "unknown parameter".to_string()
+            )),
+            params.finish()
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use crate::rule::allowed_character::MatchBehavior::Contains;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::password_utils::count_matching_characters;
 use crate::rule::rule_result::{CountCategory, RuleResult, RuleResultMetadata};
 use crate::rule::{HasCharacters, PasswordData, Rule};
 use MatchBehavior::{EndsWith, StartsWith};
+use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
-const ERROR_CODE: &str = "ALLOWED_CHAR";
+const ERROR_CODE: &str = ErrorCode::AllowedChar.as_str();
 
 /// Rule for determining if a password contains allowed characters. Validation will fail unless the
 /// password contains only allowed characters.
@@ -57,6 +59,14 @@ impl AllowedCharacter {
         }
     }
 
+    pub fn allowed_characters(&self) -> &str {
+        &self.allowed_characters
+    }
+
+    pub fn match_behavior(&self) -> &MatchBehavior {
+        &self.match_behavior
+    }
+
     fn create_rule_result_detail_parameters(&self, c: char) -> HashMap<String, String> {
         let mut map = HashMap::with_capacity(2);
         map.insert("illegalCharacter".to_string(), c.to_string());
@@ -101,6 +111,9 @@ impl Rule for AllowedCharacter {
     fn as_has_characters(&self) -> Option<&dyn HasCharacters> {
         Some(self)
     }
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 impl HasCharacters for AllowedCharacter {
@@ -264,7 +277,7 @@ mod tests {
         assert_eq!(8, result.metadata().get_count(category));
 
         let result = rule.validate(&PasswordData::with_password("metaDATA".to_string()));
-        assert_eq!(false, result.valid());
+        assert!(!result.valid());
         assert_eq!(4, result.metadata().get_count(category));
     }
 }
@@ -1,9 +1,10 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use fancy_regex::Regex;
 use std::collections::HashMap;
 
-const ERROR_CODE: &str = "ALLOWED_MATCH";
+const ERROR_CODE: &str = ErrorCode::AllowedMatch.as_str();
 
 /// Rule for determining if a password matches an allowed regular expression. Passwords must match
 /// the expression or validation will fail.
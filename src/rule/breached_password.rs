@@ -0,0 +1,247 @@
+use crate::breach::{PREFIX_LENGTH, RangeSource, to_hex};
+use crate::hash::{Hasher, Sha1Hasher};
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use log::warn;
+use std::collections::HashMap;
+
+pub const ERROR_CODE: &str = ErrorCode::BreachedPassword.as_str();
+pub const ERROR_CODE_CHECK_FAILED: &str = ErrorCode::BreachCheckFailed.as_str();
+
+/// Rule for determining if a password appears in a breach corpus. The password is hashed with SHA-1 (or the given
+/// [Hasher]) and only the first 5 hex digits of the hash are passed to the [RangeSource]. The password fails when the
+/// count of its hash is at least the minimum count.
+///
+/// When the source fails, e.g. because the service is unreachable, the password passes unless `allow_on_error` is
+/// disabled, in which case `BREACH_CHECK_FAILED` is reported.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::breach::{RangeEntry, RangeSource};
+///  use passay_rs::rule::breached_password::BreachedPasswordRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  struct Corpus;
+///  impl RangeSource for Corpus {
+///      fn range(&self, prefix: &str) -> Result<Vec<RangeEntry>, String> {
+///          // SHA-1 of "password" is 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8
+///          assert_eq!("5BAA6", prefix);
+///          let suffix = "1E4C9B93F3F0682250B6CF8331B7EE68FD8".to_string();
+///          Ok(vec![RangeEntry { suffix, count: 10_434_004 }])
+///      }
+///  }
+///
+///  let rule = BreachedPasswordRule::new(Corpus);
+///  let result = rule.validate(&PasswordData::with_password("password".to_string()));
+///  assert!(!result.valid());
+/// ```
+pub struct BreachedPasswordRule<S, H = Sha1Hasher>
+where
+    S: RangeSource,
+    H: Hasher<String>,
+{
+    source: S,
+    hasher: H,
+    minimum_count: u64,
+    allow_on_error: bool,
+}
+
+impl<S: RangeSource> BreachedPasswordRule<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            hasher: Sha1Hasher,
+            minimum_count: 1,
+            allow_on_error: true,
+        }
+    }
+
+    pub fn with_minimum_count(source: S, minimum_count: u64) -> Result<Self, String> {
+        Self::with_hasher(source, Sha1Hasher, minimum_count, true)
+    }
+}
+
+impl<S, H> BreachedPasswordRule<S, H>
+where
+    S: RangeSource,
+    H: Hasher<String>,
+{
+    pub fn with_hasher(
+        source: S,
+        hasher: H,
+        minimum_count: u64,
+        allow_on_error: bool,
+    ) -> Result<Self, String> {
+        if minimum_count == 0 {
+            return Err("minimum count must be greater than zero".to_string());
+        }
+        Ok(Self {
+            source,
+            hasher,
+            minimum_count,
+            allow_on_error,
+        })
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    pub fn minimum_count(&self) -> u64 {
+        self.minimum_count
+    }
+
+    pub fn allow_on_error(&self) -> bool {
+        self.allow_on_error
+    }
+
+    /// Returns how often the password was seen in the breach corpus.
+    fn count(&self, password: &str) -> Result<u64, String> {
+        let hash = to_hex(&self.hasher.hash(password.as_bytes())?);
+        if hash.len() <= PREFIX_LENGTH {
+            return Err(format!("hash {hash:?} is too short"));
+        }
+        let (prefix, suffix) = hash.split_at(PREFIX_LENGTH);
+        let count = self
+            .source
+            .range(prefix)?
+            .into_iter()
+            .find(|entry| entry.suffix.eq_ignore_ascii_case(suffix))
+            .map_or(0, |entry| entry.count);
+        Ok(count)
+    }
+}
+
+impl<S, H> Rule for BreachedPasswordRule<S, H>
+where
+    S: RangeSource,
+    H: Hasher<String>,
+{
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        match self.count(password_data.password()) {
+            Ok(count) if count >= self.minimum_count => {
+                let mut map = HashMap::new();
+                map.insert("count".to_string(), count.to_string());
+                map.insert("minimumCount".to_string(), self.minimum_count.to_string());
+                result.add_error(ERROR_CODE, Some(map));
+            }
+            Ok(_) => {}
+            Err(e) if self.allow_on_error => warn!("breached password check failed: {e}"),
+            Err(e) => {
+                let mut map = HashMap::new();
+                map.insert("error".to_string(), e);
+                result.add_error(ERROR_CODE_CHECK_FAILED, Some(map));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::breach::{RangeEntry, RangeSource};
+    use crate::hash::Sha1Hasher;
+    use crate::rule::PasswordData;
+    use crate::rule::breached_password::{
+        BreachedPasswordRule, ERROR_CODE, ERROR_CODE_CHECK_FAILED,
+    };
+    use crate::test::{RulePasswordTestItem, check_passwords};
+    use std::cell::RefCell;
+
+    /// Corpus with "password" seen 10 times and "p@ssw0rd" seen once, recording the requested prefixes.
+    #[derive(Default)]
+    struct Corpus {
+        prefixes: RefCell<Vec<String>>,
+    }
+
+    impl RangeSource for Corpus {
+        fn range(&self, prefix: &str) -> Result<Vec<RangeEntry>, String> {
+            self.prefixes.borrow_mut().push(prefix.to_string());
+            let entries = [
+                ("5BAA6", "1E4C9B93F3F0682250B6CF8331B7EE68FD8", 10),
+                ("5BAA6", "0018A45C4D1DEF81644B54AB7F969B88D65", 0),
+                ("57B2A", "D99044D337197C0C39FD3823568FF81E48A", 1),
+            ];
+            Ok(entries
+                .into_iter()
+                .filter(|(p, _, _)| *p == prefix)
+                .map(|(_, suffix, count)| RangeEntry {
+                    suffix: suffix.to_ascii_lowercase(),
+                    count,
+                })
+                .collect())
+        }
+    }
+
+    struct Unavailable;
+
+    impl RangeSource for Unavailable {
+        fn range(&self, _prefix: &str) -> Result<Vec<RangeEntry>, String> {
+            Err("service unavailable".to_string())
+        }
+    }
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::new(Corpus::default())),
+                password("password"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::new(Corpus::default())),
+                password("p@ssw0rd"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::new(Corpus::default())),
+                password("correct horse battery staple"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::with_minimum_count(Corpus::default(), 5).unwrap()),
+                password("p@ssw0rd"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(BreachedPasswordRule::new(Unavailable)),
+                password("password"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(
+                    BreachedPasswordRule::with_hasher(Unavailable, Sha1Hasher, 1, false).unwrap(),
+                ),
+                password("password"),
+                vec![ERROR_CODE_CHECK_FAILED],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_detail() {
+        let rule = BreachedPasswordRule::new(Corpus::default());
+        let result = crate::rule::Rule::validate(&rule, &password("password"));
+        assert_eq!(vec!["5BAA6"], *rule.source().prefixes.borrow());
+        let detail = format!("{}", result.details()[0]);
+        assert!(detail.contains("\"count\": \"10\""), "{detail}");
+        assert!(detail.contains("\"minimumCount\": \"1\""), "{detail}");
+
+        let rule = BreachedPasswordRule::new(Corpus::default());
+        assert_eq!(Ok(10), rule.count("password"));
+        assert_eq!(
+            Some("minimum count must be greater than zero".to_string()),
+            BreachedPasswordRule::with_minimum_count(Corpus::default(), 0).err()
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::rule::character_data::CharacterData;
-use crate::rule::password_utils::{count_matching_characters, get_matching_characters};
+use crate::rule::password_utils::{count_contained_characters, get_matching_characters};
 use crate::rule::rule_result::{RuleResult, RuleResultDetail, RuleResultMetadata};
 use crate::rule::{HasCharacters, PasswordData, Rule};
+use std::any::Any;
 use std::collections::HashMap;
 
 /// Validates whether a password contains a certain number of a type of character.
@@ -31,6 +32,11 @@ impl CharacterRule {
             num_characters: 1,
         }
     }
+
+    pub fn character_data(&self) -> &dyn CharacterData {
+        self.character_data.as_ref()
+    }
+
     fn create_rule_result_detail_parameters(
         &self,
         matching_chars: String,
@@ -42,12 +48,13 @@ impl CharacterRule {
         );
         map.insert(
             "matchingCharacterCount".to_string(),
-            matching_chars.len().to_string(),
-        );
-        map.insert(
-            "validCharacters".to_string(),
-            self.character_data.characters().to_string(),
+            matching_chars.chars().count().to_string(),
         );
+        let valid_characters = match self.character_data.category() {
+            Some(category) => category,
+            None => self.character_data.characters(),
+        };
+        map.insert("validCharacters".to_string(), valid_characters.to_string());
         map.insert("matchingCharacters".to_string(), matching_chars);
         map
     }
@@ -55,10 +62,7 @@ impl CharacterRule {
         if let Some(cc) = self.character_data.count_category() {
             return RuleResultMetadata::new(
                 cc,
-                count_matching_characters(
-                    self.character_data.characters(),
-                    password_data.password(),
-                ),
+                count_contained_characters(self.character_data.as_ref(), password_data.password()),
             );
         }
         RuleResultMetadata::default()
@@ -76,11 +80,11 @@ impl CharacterRule {
 impl Rule for CharacterRule {
     fn validate(&self, password_data: &PasswordData) -> RuleResult {
         let matching_chars = get_matching_characters(
-            self.character_data.characters(),
+            self.character_data.as_ref(),
             password_data.password(),
             self.num_characters,
         );
-        if matching_chars.len() < self.num_characters {
+        if matching_chars.chars().count() < self.num_characters {
             let mut result = RuleResult::new(false);
             let detail = RuleResultDetail::new(
                 vec![self.character_data.error_code().to_string()],
@@ -98,10 +102,25 @@ impl Rule for CharacterRule {
     fn as_has_characters(&self) -> Option<&dyn HasCharacters> {
         Some(self)
     }
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
+/// Character data defined by a [category](CharacterData::category) is returned by
+/// [categories](HasCharacters::categories) instead of listing its characters.
 impl HasCharacters for CharacterRule {
     fn characters(&self) -> String {
-        self.valid_characters().to_string()
+        match self.character_data.category() {
+            Some(_) => String::new(),
+            None => self.valid_characters().to_string(),
+        }
+    }
+
+    fn categories(&self) -> Vec<&dyn CharacterData> {
+        match self.character_data.category() {
+            Some(_) => vec![self.character_data.as_ref()],
+            None => Vec::new(),
+        }
     }
 }
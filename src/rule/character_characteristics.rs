@@ -1,9 +1,12 @@
 use crate::rule::character::CharacterRule;
+use crate::rule::character_data::CharacterData;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{HasCharacters, PasswordData, Rule};
+use std::any::Any;
 use std::collections::HashMap;
 
-pub const ERROR_CODE: &str = "INSUFFICIENT_CHARACTERISTICS";
+pub const ERROR_CODE: &str = ErrorCode::InsufficientCharacteristics.as_str();
 
 /// Rule for determining if a password contains the desired mix of character types. In order to meet the criteria of this
 /// rule, passwords must meet any number of supplied character rules.
@@ -66,6 +69,14 @@ impl CharacterCharacteristics {
     pub fn from_rules(rules: Vec<CharacterRule>) -> Result<CharacterCharacteristics, String> {
         Self::with_rules_and_characteristics(rules, 1)
     }
+
+    pub fn rules(&self) -> &[CharacterRule] {
+        &self.rules
+    }
+
+    pub fn num_characteristics(&self) -> usize {
+        self.num_characteristics
+    }
     fn create_rule_result_detail_parameters(&self, success: usize) -> HashMap<String, String> {
         let mut map = HashMap::with_capacity(3);
         map.insert("successCount".to_string(), success.to_string());
@@ -105,19 +116,26 @@ impl Rule for CharacterCharacteristics {
     fn as_has_characters(&self) -> Option<&dyn HasCharacters> {
         Some(self)
     }
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 impl HasCharacters for CharacterCharacteristics {
     fn characters(&self) -> String {
         self.rules.iter().map(CharacterRule::characters).collect::<String>()
     }
+
+    fn categories(&self) -> Vec<&dyn CharacterData> {
+        self.rules.iter().flat_map(CharacterRule::categories).collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::rule::character::CharacterRule;
     use crate::rule::character_characteristics::{CharacterCharacteristics, ERROR_CODE};
-    use crate::rule::character_data::{CharacterData, EnglishCharacterData};
+    use crate::rule::character_data::{CharacterData, EnglishCharacterData, UnicodeCharacterData};
     use crate::rule::rule_result::CountCategory;
     use crate::rule::{PasswordData, Rule};
     use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
@@ -260,6 +278,44 @@ mod tests {
         assert_eq!(6, result.metadata().get_count(CountCategory::LowerCase));
     }
 
+    #[test]
+    fn check_unicode() {
+        let rule = || {
+            let rules = vec![
+                CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 2).unwrap(),
+                CharacterRule::new(Box::new(UnicodeCharacterData::LowerCase), 2).unwrap(),
+                CharacterRule::new(Box::new(UnicodeCharacterData::Digit), 1).unwrap(),
+                CharacterRule::new(Box::new(UnicodeCharacterData::Special), 1).unwrap(),
+            ];
+            Box::new(CharacterCharacteristics::with_rules_and_characteristics(rules, 4).unwrap())
+        };
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                rule(),
+                PasswordData::with_password("ÄrgerЖук٣€".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(),
+                PasswordData::with_password("Ωmega-ωmega1".to_string()),
+                vec![ERROR_CODE, UnicodeCharacterData::UpperCase.error_code()],
+            ),
+        ];
+        check_passwords(test_cases);
+
+        let result = rule().validate(&PasswordData::with_password("ΩΣ-日本 ١٢".to_string()));
+        assert_eq!(2, result.metadata().get_count(CountCategory::UpperCase));
+        assert_eq!(2, result.metadata().get_count(CountCategory::Digit));
+        assert_eq!(1, result.metadata().get_count(CountCategory::Special));
+
+        // the category is reported instead of its characters
+        check_messages(vec![RulePasswordTestItem(
+            Box::new(CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 2).unwrap()),
+            PasswordData::with_password("Ωmega".to_string()),
+            vec![r#"INSUFFICIENT_UPPERCASE,"validCharacters": "\\p{Lu}""#],
+        )]);
+    }
+
     fn create_rule1() -> Box<CharacterCharacteristics> {
         let char_rules = vec![
             CharacterRule::new(Box::new(EnglishCharacterData::Alphabetical), 4).unwrap(),
@@ -1,4 +1,7 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::CountCategory;
+use std::sync::OnceLock;
+use unicode_general_category::{GeneralCategory, get_general_category};
 
 /// Input data used by [CharacterRule](crate::rule::character::CharacterRule)
 pub trait CharacterData {
@@ -6,6 +9,79 @@ pub trait CharacterData {
     fn error_code(&self) -> &str;
 
     fn count_category(&self) -> Option<CountCategory>;
+
+    /// Returns whether the character belongs to this data. Implementations should override the default, which
+    /// searches [characters](Self::characters), when they can test a character directly.
+    fn contains(&self, c: char) -> bool {
+        self.characters().contains(c)
+    }
+
+    /// Returns the name of the character category for data defined by a category rather than a short list of
+    /// characters. Its [characters](Self::characters) are then only listed where they are drawn from, and the name
+    /// takes their place in messages.
+    fn category(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the number of characters of this data, which
+    /// [RandomPasswordEntropy](crate::entropy::RandomPasswordEntropy) counts for data defined by a
+    /// [category](Self::category).
+    fn size(&self) -> usize {
+        self.characters().chars().count()
+    }
+}
+
+/// Character data defined by an arbitrary set of characters.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::character::CharacterRule;
+///  use passay_rs::rule::character_data::CustomCharacterData;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let data = CustomCharacterData::from_chars("!@#", "INSUFFICIENT_SYMBOL");
+///  let rule = CharacterRule::new(Box::new(data), 1).unwrap();
+///  let result = rule.validate(&PasswordData::with_password("p4ssw0rd".to_string()));
+///  assert!(!result.valid());
+/// ```
+pub struct CustomCharacterData {
+    characters: String,
+    error_code: String,
+    count_category: Option<CountCategory>,
+}
+
+impl CustomCharacterData {
+    pub fn new(
+        characters: &str,
+        error_code: &str,
+        count_category: Option<CountCategory>,
+    ) -> CustomCharacterData {
+        CustomCharacterData {
+            characters: characters.to_string(),
+            error_code: error_code.to_string(),
+            count_category,
+        }
+    }
+
+    pub fn from_chars(characters: &str, error_code: &str) -> CustomCharacterData {
+        Self::new(characters, error_code, None)
+    }
+}
+
+impl CharacterData for CustomCharacterData {
+    fn characters(&self) -> &str {
+        &self.characters
+    }
+
+    fn error_code(&self) -> &str {
+        &self.error_code
+    }
+
+    fn count_category(&self) -> Option<CountCategory> {
+        self.count_category
+    }
 }
 
 /// English language character data.
@@ -53,11 +129,13 @@ impl CharacterData for EnglishCharacterData {
 
     fn error_code(&self) -> &str {
         match self {
-            EnglishCharacterData::LowerCase => "INSUFFICIENT_LOWERCASE",
-            EnglishCharacterData::UpperCase => "INSUFFICIENT_UPPERCASE",
-            EnglishCharacterData::Digit => "INSUFFICIENT_DIGIT",
-            EnglishCharacterData::Alphabetical => "INSUFFICIENT_ALPHABETICAL",
-            EnglishCharacterData::Special => "INSUFFICIENT_SPECIAL",
+            EnglishCharacterData::LowerCase => const { ErrorCode::InsufficientLowercase.as_str() },
+            EnglishCharacterData::UpperCase => const { ErrorCode::InsufficientUppercase.as_str() },
+            EnglishCharacterData::Digit => const { ErrorCode::InsufficientDigit.as_str() },
+            EnglishCharacterData::Alphabetical => {
+                const { ErrorCode::InsufficientAlphabetical.as_str() }
+            }
+            EnglishCharacterData::Special => const { ErrorCode::InsufficientSpecial.as_str() },
         }
     }
     fn count_category(&self) -> Option<CountCategory> {
@@ -69,6 +147,16 @@ impl CharacterData for EnglishCharacterData {
             EnglishCharacterData::Special => Some(CountCategory::Special),
         }
     }
+
+    fn contains(&self, c: char) -> bool {
+        match self {
+            EnglishCharacterData::LowerCase => c.is_ascii_lowercase(),
+            EnglishCharacterData::UpperCase => c.is_ascii_uppercase(),
+            EnglishCharacterData::Digit => c.is_ascii_digit(),
+            EnglishCharacterData::Alphabetical => c.is_ascii_alphabetic(),
+            EnglishCharacterData::Special => self.characters().contains(c),
+        }
+    }
 }
 
 /// Cyrillic character data.
@@ -86,8 +174,8 @@ impl CharacterData for CyrillicCharacterData {
 
     fn error_code(&self) -> &str {
         match self {
-            CyrillicCharacterData::LowerCase => "INSUFFICIENT_LOWERCASE",
-            CyrillicCharacterData::UpperCase => "INSUFFICIENT_UPPERCASE",
+            CyrillicCharacterData::LowerCase => const { ErrorCode::InsufficientLowercase.as_str() },
+            CyrillicCharacterData::UpperCase => const { ErrorCode::InsufficientUppercase.as_str() },
         }
     }
 
@@ -114,8 +202,12 @@ impl CharacterData for CyrillicModernCharacterData {
 
     fn error_code(&self) -> &str {
         match self {
-            CyrillicModernCharacterData::LowerCase => "INSUFFICIENT_LOWERCASE",
-            CyrillicModernCharacterData::UpperCase => "INSUFFICIENT_UPPERCASE",
+            CyrillicModernCharacterData::LowerCase => {
+                const { ErrorCode::InsufficientLowercase.as_str() }
+            }
+            CyrillicModernCharacterData::UpperCase => {
+                const { ErrorCode::InsufficientUppercase.as_str() }
+            }
         }
     }
     fn count_category(&self) -> Option<CountCategory> {
@@ -142,8 +234,8 @@ impl CharacterData for CzechCharacterData {
 
     fn error_code(&self) -> &str {
         match self {
-            CzechCharacterData::LowerCase => "INSUFFICIENT_LOWERCASE",
-            CzechCharacterData::UpperCase => "INSUFFICIENT_UPPERCASE",
+            CzechCharacterData::LowerCase => const { ErrorCode::InsufficientLowercase.as_str() },
+            CzechCharacterData::UpperCase => const { ErrorCode::InsufficientUppercase.as_str() },
         }
     }
     fn count_category(&self) -> Option<CountCategory> {
@@ -170,8 +262,8 @@ impl CharacterData for GermanCharacterData {
 
     fn error_code(&self) -> &str {
         match self {
-            GermanCharacterData::LowerCase => "INSUFFICIENT_LOWERCASE",
-            GermanCharacterData::UpperCase => "INSUFFICIENT_UPPERCASE",
+            GermanCharacterData::LowerCase => const { ErrorCode::InsufficientLowercase.as_str() },
+            GermanCharacterData::UpperCase => const { ErrorCode::InsufficientUppercase.as_str() },
         }
     }
     fn count_category(&self) -> Option<CountCategory> {
@@ -198,8 +290,8 @@ impl CharacterData for PolishCharacterData {
 
     fn error_code(&self) -> &str {
         match self {
-            PolishCharacterData::LowerCase => "INSUFFICIENT_LOWERCASE",
-            PolishCharacterData::UpperCase => "INSUFFICIENT_UPPERCASE",
+            PolishCharacterData::LowerCase => const { ErrorCode::InsufficientLowercase.as_str() },
+            PolishCharacterData::UpperCase => const { ErrorCode::InsufficientUppercase.as_str() },
         }
     }
     fn count_category(&self) -> Option<CountCategory> {
@@ -209,3 +301,148 @@ impl CharacterData for PolishCharacterData {
         }
     }
 }
+
+/// Character data defined by Unicode general categories, so that letters and digits of every language count, e.g.
+/// `Ä`, `Ж` and `Ω` are all [UpperCase](UnicodeCharacterData::UpperCase).
+///
+/// [characters](CharacterData::characters) lists every character of the categories, which the
+/// [PasswordGenerator](crate::password_generator::PasswordGenerator) draws from; the list is built on first use and
+/// holds up to about 140,000 characters. Messages name the category instead, e.g. `\p{Lu}`, and
+/// [RandomPasswordEntropy](crate::entropy::RandomPasswordEntropy) counts the characters by their
+/// [size](CharacterData::size).
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::character::CharacterRule;
+///  use passay_rs::rule::character_data::UnicodeCharacterData;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = CharacterRule::new(Box::new(UnicodeCharacterData::UpperCase), 2).unwrap();
+///  let result = rule.validate(&PasswordData::with_password("Ärger-Жук".to_string()));
+///  assert!(result.valid());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeCharacterData {
+    /// Uppercase letters, `Lu`.
+    UpperCase,
+    /// Lowercase letters, `Ll`.
+    LowerCase,
+    /// Decimal digits, `Nd`.
+    Digit,
+    /// Letters, `L*`.
+    Alphabetical,
+    /// Punctuation, `P*`.
+    Punctuation,
+    /// Symbols, `S*`.
+    Symbol,
+    /// Punctuation and symbols, `P*` and `S*`.
+    Special,
+    /// Space separators, `Zs`.
+    Space,
+}
+
+impl UnicodeCharacterData {
+    const ALL: [UnicodeCharacterData; 8] = [
+        UnicodeCharacterData::UpperCase,
+        UnicodeCharacterData::LowerCase,
+        UnicodeCharacterData::Digit,
+        UnicodeCharacterData::Alphabetical,
+        UnicodeCharacterData::Punctuation,
+        UnicodeCharacterData::Symbol,
+        UnicodeCharacterData::Special,
+        UnicodeCharacterData::Space,
+    ];
+
+    fn contains_category(&self, category: GeneralCategory) -> bool {
+        use GeneralCategory::*;
+        let punctuation = matches!(
+            category,
+            ConnectorPunctuation
+                | DashPunctuation
+                | OpenPunctuation
+                | ClosePunctuation
+                | InitialPunctuation
+                | FinalPunctuation
+                | OtherPunctuation
+        );
+        let symbol = matches!(
+            category,
+            MathSymbol | CurrencySymbol | ModifierSymbol | OtherSymbol
+        );
+        match self {
+            UnicodeCharacterData::UpperCase => category == UppercaseLetter,
+            UnicodeCharacterData::LowerCase => category == LowercaseLetter,
+            UnicodeCharacterData::Digit => category == DecimalNumber,
+            UnicodeCharacterData::Alphabetical => matches!(
+                category,
+                UppercaseLetter | LowercaseLetter | TitlecaseLetter | ModifierLetter | OtherLetter
+            ),
+            UnicodeCharacterData::Punctuation => punctuation,
+            UnicodeCharacterData::Symbol => symbol,
+            UnicodeCharacterData::Special => punctuation || symbol,
+            UnicodeCharacterData::Space => category == SpaceSeparator,
+        }
+    }
+}
+
+impl CharacterData for UnicodeCharacterData {
+    fn characters(&self) -> &str {
+        static CHARACTERS: [OnceLock<String>; UnicodeCharacterData::ALL.len()] =
+            [const { OnceLock::new() }; UnicodeCharacterData::ALL.len()];
+        let index = Self::ALL.iter().position(|data| data == self).unwrap();
+        // planes 4 to 13 are unassigned and planes 15 and 16 are private use
+        CHARACTERS[index].get_or_init(|| {
+            ('\0'..='\u{3ffff}')
+                .chain('\u{e0000}'..='\u{effff}')
+                .filter(|c| self.contains(*c))
+                .collect()
+        })
+    }
+
+    fn error_code(&self) -> &str {
+        match self {
+            UnicodeCharacterData::UpperCase => const { ErrorCode::InsufficientUppercase.as_str() },
+            UnicodeCharacterData::LowerCase => const { ErrorCode::InsufficientLowercase.as_str() },
+            UnicodeCharacterData::Digit => const { ErrorCode::InsufficientDigit.as_str() },
+            UnicodeCharacterData::Alphabetical => {
+                const { ErrorCode::InsufficientAlphabetical.as_str() }
+            }
+            UnicodeCharacterData::Punctuation
+            | UnicodeCharacterData::Symbol
+            | UnicodeCharacterData::Special => const { ErrorCode::InsufficientSpecial.as_str() },
+            UnicodeCharacterData::Space => const { ErrorCode::InsufficientSpace.as_str() },
+        }
+    }
+
+    fn count_category(&self) -> Option<CountCategory> {
+        match self {
+            UnicodeCharacterData::UpperCase => Some(CountCategory::UpperCase),
+            UnicodeCharacterData::LowerCase => Some(CountCategory::LowerCase),
+            UnicodeCharacterData::Digit => Some(CountCategory::Digit),
+            UnicodeCharacterData::Special => Some(CountCategory::Special),
+            UnicodeCharacterData::Space => Some(CountCategory::Whitespace),
+            UnicodeCharacterData::Alphabetical
+            | UnicodeCharacterData::Punctuation
+            | UnicodeCharacterData::Symbol => None,
+        }
+    }
+
+    fn contains(&self, c: char) -> bool {
+        self.contains_category(get_general_category(c))
+    }
+
+    fn category(&self) -> Option<&str> {
+        Some(match self {
+            UnicodeCharacterData::UpperCase => r"\p{Lu}",
+            UnicodeCharacterData::LowerCase => r"\p{Ll}",
+            UnicodeCharacterData::Digit => r"\p{Nd}",
+            UnicodeCharacterData::Alphabetical => r"\p{L}",
+            UnicodeCharacterData::Punctuation => r"\p{P}",
+            UnicodeCharacterData::Symbol => r"\p{S}",
+            UnicodeCharacterData::Special => r"\p{P}\p{S}",
+            UnicodeCharacterData::Space => r"\p{Zs}",
+        })
+    }
+}
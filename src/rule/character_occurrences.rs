@@ -1,8 +1,9 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use std::collections::HashMap;
 
-const ERROR_CODE: &str = "TOO_MANY_OCCURRENCES";
+const ERROR_CODE: &str = ErrorCode::TooManyOccurrences.as_str();
 
 /// Validates that a password does not contain too many occurrences of the same character.
 ///
@@ -0,0 +1,309 @@
+use crate::rule::character_data::{CharacterData, EnglishCharacterData};
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use fancy_regex::Regex;
+use std::collections::HashMap;
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalCharacterPattern.as_str();
+pub const ERROR_CODE_REGEX: &str = ErrorCode::RegexError.as_str();
+/// Class symbol of characters not contained in any of the character classes.
+pub const OTHER_CLASS: char = 'O';
+
+/// Structures of the class pattern of a password, see [CharacterPatternRule].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternStructure {
+    /// Digits only follow the last letter, e.g. `Password1!` or `summer2024`.
+    DigitsAtEnd,
+    /// The first character is the only uppercase one, e.g. `Password1!`.
+    UppercaseOnlyFirst,
+    /// Special characters only form a suffix, e.g. `Password1!`.
+    SpecialsAtEnd,
+    /// A regular expression matched against the whole class pattern, e.g. `^U+D+$`.
+    Custom { name: String, regex: String },
+}
+
+impl PatternStructure {
+    pub fn name(&self) -> &str {
+        match self {
+            PatternStructure::DigitsAtEnd => "digitsAtEnd",
+            PatternStructure::UppercaseOnlyFirst => "uppercaseOnlyFirst",
+            PatternStructure::SpecialsAtEnd => "specialsAtEnd",
+            PatternStructure::Custom { name, .. } => name,
+        }
+    }
+
+    /// Returns the expression over the default class symbols `U`, `L`, `D`, `S` and `O`.
+    pub fn regex(&self) -> &str {
+        match self {
+            PatternStructure::DigitsAtEnd => "^[^D]*[UL][^UL]*D[^UL]*$",
+            PatternStructure::UppercaseOnlyFirst => "^U[^U]+$",
+            PatternStructure::SpecialsAtEnd => "^[^S]*[^S]S+$",
+            PatternStructure::Custom { regex, .. } => regex,
+        }
+    }
+}
+
+/// Rule for determining if the character classes of a password follow a predictable structure. Each character is
+/// replaced by the symbol of the first [CharacterData] containing it, or `O` if there is none, giving a class pattern
+/// such as `ULLLLLLLDS` for `Password1!`. The password fails for each configured [PatternStructure] matching the
+/// pattern. A structure that cannot be matched, e.g. because a custom expression exceeds the backtrack limit, is
+/// reported as `REGEX_ERROR` instead of being ignored.
+///
+/// By default the classes are `U` for uppercase, `L` for lowercase, `D` for digits and `S` for special characters of
+/// [EnglishCharacterData], and all built-in structures are rejected.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::character_pattern::{CharacterPatternRule, PatternStructure};
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = CharacterPatternRule::with_structures(vec![PatternStructure::DigitsAtEnd]).unwrap();
+///  let result = rule.validate(&PasswordData::with_password("Password1!".to_string()));
+///  assert!(!result.valid());
+///  let result = rule.validate(&PasswordData::with_password("Pass4word!".to_string()));
+///  assert!(result.valid());
+/// ```
+pub struct CharacterPatternRule {
+    classes: Vec<(char, Box<dyn CharacterData>)>,
+    structures: Vec<(PatternStructure, Regex)>,
+}
+
+impl CharacterPatternRule {
+    pub fn new(
+        classes: Vec<(char, Box<dyn CharacterData>)>,
+        structures: Vec<PatternStructure>,
+    ) -> Result<Self, String> {
+        for (i, (symbol, _)) in classes.iter().enumerate() {
+            if *symbol == OTHER_CLASS {
+                return Err(format!("class symbol {OTHER_CLASS:?} is reserved"));
+            }
+            if classes[..i].iter().any(|(s, _)| s == symbol) {
+                return Err(format!("class symbol {symbol:?} is used twice"));
+            }
+        }
+        let structures = structures
+            .into_iter()
+            .map(|structure| {
+                let regex = Regex::new(structure.regex())
+                    .map_err(|e| format!("invalid structure {}: {e}", structure.name()))?;
+                Ok((structure, regex))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self {
+            classes,
+            structures,
+        })
+    }
+
+    pub fn with_structures(structures: Vec<PatternStructure>) -> Result<Self, String> {
+        let classes: Vec<(char, Box<dyn CharacterData>)> = vec![
+            ('U', Box::new(EnglishCharacterData::UpperCase)),
+            ('L', Box::new(EnglishCharacterData::LowerCase)),
+            ('D', Box::new(EnglishCharacterData::Digit)),
+            ('S', Box::new(EnglishCharacterData::Special)),
+        ];
+        Self::new(classes, structures)
+    }
+
+    pub fn structures(&self) -> impl Iterator<Item = &PatternStructure> {
+        self.structures.iter().map(|(structure, _)| structure)
+    }
+
+    /// Returns the class pattern of the password.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use passay_rs::rule::character_pattern::CharacterPatternRule;
+    ///
+    ///  let rule = CharacterPatternRule::default();
+    ///  assert_eq!("ULLLLLLLDS", rule.pattern("Password1!"));
+    ///  assert_eq!("LOL", rule.pattern("a\u{e9}b"));
+    /// ```
+    pub fn pattern(&self, password: &str) -> String {
+        password
+            .chars()
+            .map(|c| {
+                self.classes
+                    .iter()
+                    .find(|(_, data)| data.contains(c))
+                    .map_or(OTHER_CLASS, |(symbol, _)| *symbol)
+            })
+            .collect()
+    }
+}
+
+impl Default for CharacterPatternRule {
+    fn default() -> Self {
+        Self::with_structures(vec![
+            PatternStructure::DigitsAtEnd,
+            PatternStructure::UppercaseOnlyFirst,
+            PatternStructure::SpecialsAtEnd,
+        ])
+        .unwrap()
+    }
+}
+
+impl Rule for CharacterPatternRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let pattern = self.pattern(password_data.password());
+        for (structure, regex) in &self.structures {
+            match regex.is_match(&pattern) {
+                Ok(true) => {
+                    let mut map = HashMap::with_capacity(2);
+                    map.insert("pattern".to_string(), pattern.clone());
+                    map.insert("structure".to_string(), structure.name().to_string());
+                    result.add_error(ERROR_CODE, Some(map));
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    let mut map = HashMap::with_capacity(2);
+                    map.insert("structure".to_string(), structure.name().to_string());
+                    map.insert("error".to_string(), e.to_string());
+                    result.add_error(ERROR_CODE_REGEX, Some(map));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::character_data::{CharacterData, CustomCharacterData, EnglishCharacterData};
+    use crate::rule::character_pattern::{
+        CharacterPatternRule, ERROR_CODE, ERROR_CODE_REGEX, PatternStructure,
+    };
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    fn rule(structure: PatternStructure) -> Box<CharacterPatternRule> {
+        Box::new(CharacterPatternRule::with_structures(vec![structure]).unwrap())
+    }
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                Box::new(CharacterPatternRule::default()),
+                password("Password1!"),
+                vec![ERROR_CODE, ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(CharacterPatternRule::default()),
+                password("p4zRcv8#n65"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::DigitsAtEnd),
+                password("summer2024"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::DigitsAtEnd),
+                password("Summer#2024!"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::DigitsAtEnd),
+                password("2024summer"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::DigitsAtEnd),
+                password("summer"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::UppercaseOnlyFirst),
+                password("Summer2024"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::UppercaseOnlyFirst),
+                password("SummeR2024"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::SpecialsAtEnd),
+                password("summer2024!!"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::SpecialsAtEnd),
+                password("sum!mer2024!"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::SpecialsAtEnd),
+                password("!!!"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(PatternStructure::Custom {
+                    name: "lettersThenDigits".to_string(),
+                    regex: "^[UL]+D+$".to_string(),
+                }),
+                password("abcDEF123"),
+                vec![ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_regex_error() {
+        let backtracking = PatternStructure::Custom {
+            name: "backtracking".to_string(),
+            regex: "^(L+)+(?=X)".to_string(),
+        };
+        let test_cases: Vec<RulePasswordTestItem> = vec![RulePasswordTestItem(
+            rule(backtracking),
+            password(&"a".repeat(30)),
+            vec![ERROR_CODE_REGEX],
+        )];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![RulePasswordTestItem(
+            rule(PatternStructure::DigitsAtEnd),
+            password("Password1!"),
+            vec!["ILLEGAL_CHARACTER_PATTERN,ULLLLLLLDS,digitsAtEnd"],
+        )];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_classes() {
+        let classes: Vec<(char, Box<dyn CharacterData>)> = vec![
+            ('A', Box::new(EnglishCharacterData::Alphabetical)),
+            (
+                'V',
+                Box::new(CustomCharacterData::from_chars("äöü", "UMLAUT")),
+            ),
+        ];
+        let rule = CharacterPatternRule::new(classes, vec![]).unwrap();
+        assert_eq!("AAVOA", rule.pattern("abü1Z"));
+
+        let classes: Vec<(char, Box<dyn CharacterData>)> = vec![
+            ('A', Box::new(EnglishCharacterData::UpperCase)),
+            ('A', Box::new(EnglishCharacterData::LowerCase)),
+        ];
+        assert!(CharacterPatternRule::new(classes, vec![]).is_err());
+        let invalid = PatternStructure::Custom {
+            name: "invalid".to_string(),
+            regex: "(".to_string(),
+        };
+        assert!(CharacterPatternRule::with_structures(vec![invalid]).is_err());
+    }
+}
@@ -76,6 +76,6 @@ mod tests {
         assert!(sequence.matches(0, '1'));
         assert!(sequence.matches(0, 'A'));
         assert!(sequence.matches(0, 'a'));
-        assert_eq!(false, sequence.matches(4, 'z'));
+        assert!(!sequence.matches(4, 'z'));
     }
 }
@@ -0,0 +1,241 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::transforming_dictionary::Transformations;
+use crate::rule::{PasswordData, Rule};
+use std::collections::HashMap;
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalContextWord.as_str();
+pub const DEFAULT_MAX_DISTANCE: usize = 1;
+/// Each edit allowed for a word requires this many characters in the word, so that short words must match exactly.
+const CHARACTERS_PER_EDIT: usize = 4;
+
+/// Rule for determining if a password contains a word specific to the service, e.g. the product or company name, its
+/// domain or slogans. The words are supplied at runtime and matched case-insensitively anywhere in the password, also
+/// after undoing leetspeak, e.g. `4cm3corp` contains `acme`. Whitespace is ignored, so slogans match when typed
+/// without spaces.
+///
+/// Matches may differ from a word by up to `max_distance` inserted, deleted or substituted characters. The distance
+/// is limited to one edit per 4 characters of the word, so a word of 3 characters has to match exactly.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::context_words::ContextWordsRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let words = vec!["Acme".to_string(), "rocket".to_string()];
+///  let rule = ContextWordsRule::with_words(words).unwrap();
+///  let result = rule.validate(&PasswordData::with_password("4cm3corp!".to_string()));
+///  assert!(!result.valid());
+///  let result = rule.validate(&PasswordData::with_password("R0ckit-Launch".to_string()));
+///  assert!(!result.valid());
+/// ```
+pub struct ContextWordsRule {
+    words: Vec<String>,
+    normalized_words: Vec<Vec<char>>,
+    max_distance: usize,
+    transformations: Transformations,
+}
+
+/// A context word found in a password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WordMatch {
+    distance: usize,
+    start: usize,
+    end: usize,
+}
+
+impl ContextWordsRule {
+    pub fn new(
+        words: Vec<String>,
+        max_distance: usize,
+        leet: HashMap<char, Vec<char>>,
+    ) -> Result<Self, String> {
+        if words.iter().any(|w| w.trim().is_empty()) {
+            return Err("context words must not be empty".to_string());
+        }
+        let normalized_words = words
+            .iter()
+            .map(|w| w.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect())
+            .collect();
+        Ok(Self {
+            words,
+            normalized_words,
+            max_distance,
+            transformations: Transformations::new(leet, false, true, false),
+        })
+    }
+
+    pub fn with_words(words: Vec<String>) -> Result<Self, String> {
+        Self::new(words, DEFAULT_MAX_DISTANCE, Transformations::default_leet())
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    /// Returns the closest match of the word in the text, if it is within the allowed distance.
+    fn search(&self, word: &[char], text: &[char]) -> Option<WordMatch> {
+        let allowed = self.max_distance.min(word.len() / CHARACTERS_PER_EDIT);
+        let found = closest_substring(word, text)?;
+        (found.distance <= allowed).then_some(found)
+    }
+}
+
+/// Finds the substring of the text with the smallest edit distance to the word, preferring the earliest one.
+fn closest_substring(word: &[char], text: &[char]) -> Option<WordMatch> {
+    // distances and start positions of the best alignment of each word prefix ending at the current text position
+    let mut distances: Vec<usize> = (0..=word.len()).collect();
+    let mut starts = vec![0; word.len() + 1];
+    let mut best: Option<WordMatch> = None;
+    for (j, &c) in text.iter().enumerate() {
+        let mut next_distances = vec![0; word.len() + 1];
+        let mut next_starts = vec![j + 1; word.len() + 1];
+        for i in 1..=word.len() {
+            let substitution = distances[i - 1] + usize::from(word[i - 1] != c);
+            let insertion = distances[i] + 1;
+            let deletion = next_distances[i - 1] + 1;
+            (next_distances[i], next_starts[i]) =
+                if substitution <= insertion && substitution <= deletion {
+                    (substitution, starts[i - 1])
+                } else if insertion <= deletion {
+                    (insertion, starts[i])
+                } else {
+                    (deletion, next_starts[i - 1])
+                };
+        }
+        distances = next_distances;
+        starts = next_starts;
+        let distance = distances[word.len()];
+        if best.as_ref().is_none_or(|b| distance < b.distance) {
+            best = Some(WordMatch {
+                distance,
+                start: starts[word.len()],
+                end: j + 1,
+            });
+        }
+    }
+    best
+}
+
+impl Rule for ContextWordsRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let password: Vec<char> = password_data.password().chars().collect();
+        // whitespace is removed from the candidates, the positions refer to the characters of the password
+        let candidates: Vec<(Vec<char>, Vec<usize>)> = self
+            .transformations
+            .candidates_with_positions(password_data.password())
+            .into_iter()
+            .map(|(c, positions)| {
+                c.text.chars().zip(positions).filter(|(c, _)| !c.is_whitespace()).unzip()
+            })
+            .collect();
+        for (word, normalized) in self.words.iter().zip(&self.normalized_words) {
+            let found = candidates
+                .iter()
+                .filter_map(|(text, positions)| {
+                    self.search(normalized, text).map(|m| (positions, m))
+                })
+                .min_by_key(|(_, m)| m.distance);
+            let Some((positions, found)) = found else {
+                continue;
+            };
+            let mut map = HashMap::new();
+            map.insert("word".to_string(), word.clone());
+            // the match is shown as typed
+            map.insert(
+                "match".to_string(),
+                password[positions[found.start]..=positions[found.end - 1]].iter().collect(),
+            );
+            map.insert("distance".to_string(), found.distance.to_string());
+            result.add_error(ERROR_CODE, Some(map));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::context_words::{ContextWordsRule, ERROR_CODE, closest_substring};
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+    use std::collections::HashMap;
+
+    fn rule() -> Box<ContextWordsRule> {
+        let words = ["Acme", "acmecorp.example", "Reach for the stars", "zap"];
+        Box::new(ContextWordsRule::with_words(words.map(String::from).to_vec()).unwrap())
+    }
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let test_cases = vec![
+            RulePasswordTestItem(rule(), password("Acme2024"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("4cm3corp"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("xAkme!!"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("reach4thestars"), vec![]),
+            RulePasswordTestItem(rule(), password("reachforthestars"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("Reach for the stars!"), vec![ERROR_CODE]),
+            RulePasswordTestItem(
+                rule(),
+                password("ACMECORP.EXAMPLE"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            // short words must match exactly
+            RulePasswordTestItem(rule(), password("zip-zop"), vec![]),
+            RulePasswordTestItem(rule(), password("Z4p-it"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("Tr0ub4dor&3"), vec![]),
+            RulePasswordTestItem(
+                Box::new(
+                    ContextWordsRule::new(vec!["acme".to_string()], 0, HashMap::new()).unwrap(),
+                ),
+                password("4cm3-Akme"),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+        assert!(ContextWordsRule::with_words(vec![" ".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases = vec![
+            RulePasswordTestItem(
+                rule(),
+                password("xx4cm3corp"),
+                vec!["ILLEGAL_CONTEXT_WORD,\"Acme\",\"4cm3\",\"0\""],
+            ),
+            RulePasswordTestItem(
+                rule(),
+                password("xAkme!!"),
+                vec!["ILLEGAL_CONTEXT_WORD,\"Acme\",\"Akme\",\"1\""],
+            ),
+            // `İ` lower-cases to two characters and the space is removed, which keeps the length
+            RulePasswordTestItem(
+                rule(),
+                password("İ4CME x"),
+                vec!["ILLEGAL_CONTEXT_WORD,\"Acme\",\"4CME\",\"0\""],
+            ),
+        ];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_closest_substring() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        let found = closest_substring(&chars("acme"), &chars("xxacmyy")).unwrap();
+        assert_eq!((1, 2, 5), (found.distance, found.start, found.end));
+        let found = closest_substring(&chars("acme"), &chars("the acme")).unwrap();
+        assert_eq!((0, 4, 8), (found.distance, found.start, found.end));
+        assert_eq!(None, closest_substring(&chars("acme"), &[]));
+    }
+}
@@ -0,0 +1,427 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use fancy_regex::Regex;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalDate.as_str();
+pub const ERROR_CODE_BIRTHDATE: &str = ErrorCode::IllegalBirthdate.as_str();
+pub const DEFAULT_YEARS: RangeInclusive<u16> = 1900..=2099;
+
+const SEPARATOR_GROUP: &str = "(?P<s>[-./ _])";
+const MONTH_NAME_GROUP: &str = "(?P<n>(?i:january|jan|february|feb|march|mar|april|apr|may|june|jun|july|jul|august|aug|\
+    september|sept|sep|october|oct|november|nov|december|dec))";
+
+/// The date formats in order of preference, used when several formats match the same characters. `-` stands for any
+/// separator, the same separator has to be used twice.
+const FORMATS: &[&str] = &[
+    "yyyy-mm-dd",
+    "dd-mm-yyyy",
+    "mm-dd-yyyy",
+    "yyyymmdd",
+    "ddmmyyyy",
+    "mmddyyyy",
+    "ddmmmyyyy",
+    "mmmddyyyy",
+    "mmmyyyy",
+    "dd-mm-yy",
+    "mm-dd-yy",
+    "yy-mm-dd",
+    "ddmmyy",
+    "mmddyy",
+    "yymmdd",
+    "ddmmmyy",
+    "mmmddyy",
+    "yyyy",
+];
+
+/// A calendar date, e.g. the birthdate of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, String> {
+        if !(1..=12).contains(&month) {
+            return Err(format!("invalid month {month}"));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(format!("invalid day {day} of month {month}"));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// A date or year found in a password. Parts missing from the format, e.g. the day of `mmmyyyy`, are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateMatch {
+    /// Index of the first character of the match in the password.
+    pub start: usize,
+    /// Index after the last character of the match in the password.
+    pub end: usize,
+    pub text: String,
+    /// The recognized format with the separator of the match, e.g. `dd.mm.yyyy`.
+    pub format: String,
+    /// The year, two digit years are in the range 0..100.
+    pub year: u16,
+    pub two_digit_year: bool,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+}
+
+impl DateMatch {
+    /// Returns whether this match refers to the given date, as far as the format allows.
+    pub fn is_date(&self, date: &Date) -> bool {
+        let year = if self.two_digit_year {
+            date.year % 100
+        } else {
+            date.year
+        };
+        self.year == year
+            && self.month.is_none_or(|m| m == date.month)
+            && self.day.is_none_or(|d| d == date.day)
+    }
+}
+
+/// Builds the expression of a format. Digit groups must not be surrounded by other digits.
+fn format_regex(format: &str) -> Regex {
+    let mut pattern = String::from(r"(?<!\d)");
+    let mut rest = format;
+    let mut separated = false;
+    while !rest.is_empty() {
+        let (token, group) = [
+            ("yyyy", r"(?P<y>\d{4})"),
+            ("mmm", MONTH_NAME_GROUP),
+            ("yy", r"(?P<y>\d{2})"),
+            ("mm", r"(?P<m>\d{1,2})"),
+            ("dd", r"(?P<d>\d{1,2})"),
+            ("-", ""),
+        ]
+        .into_iter()
+        .find(|(token, _)| rest.starts_with(token))
+        .unwrap();
+        if token == "-" {
+            pattern.push_str(if separated { "(?P=s)" } else { SEPARATOR_GROUP });
+            separated = true;
+        } else if !format.contains('-') {
+            // without separators, days and months always have two digits
+            pattern.push_str(&group.replace(r"\d{1,2}", r"\d{2}"));
+        } else {
+            pattern.push_str(group);
+        }
+        rest = &rest[token.len()..];
+    }
+    pattern.push_str(r"(?!\d)");
+    Regex::new(&pattern).unwrap()
+}
+
+fn month_number(name: &str) -> u8 {
+    let prefix = name[..3].to_ascii_lowercase();
+    let months = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    months.iter().position(|m| *m == prefix).unwrap() as u8 + 1
+}
+
+/// Rule for determining if a password contains a year or a date. Years of the configured range are recognized on
+/// their own, e.g. `Summer2024!`, and dates in day, month and year orders with or without separators, e.g.
+/// `01.01.1990`, `jan0190` or `19900101`.
+///
+/// When the password data has a birthdate, dates referring to it are reported as `ILLEGAL_BIRTHDATE`. The rule can
+/// also be restricted to the birthdate, allowing all other dates.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::date_pattern::{Date, DatePatternRule};
+///  use passay_rs::rule::Rule;
+///
+///  let rule = DatePatternRule::default();
+///  let result = rule.validate(&PasswordData::with_password("Summer2024!".to_string()));
+///  assert!(!result.valid());
+///
+///  let rule = DatePatternRule::with_birthdate_only();
+///  let mut password = PasswordData::with_password("alice-3.7.85".to_string());
+///  password.set_birthdate(Some(Date::new(1985, 7, 3).unwrap()));
+///  assert!(!rule.validate(&password).valid());
+/// ```
+pub struct DatePatternRule {
+    years: RangeInclusive<u16>,
+    birthdate_only: bool,
+    formats: Vec<(&'static str, Regex)>,
+}
+
+impl DatePatternRule {
+    pub fn new(years: RangeInclusive<u16>, birthdate_only: bool) -> Result<Self, String> {
+        if years.is_empty() || *years.start() < 1000 || *years.end() > 9999 {
+            return Err(format!(
+                "year range must contain four digit years: {years:?}"
+            ));
+        }
+        let formats = FORMATS.iter().map(|f| (*f, format_regex(f))).collect();
+        Ok(Self {
+            years,
+            birthdate_only,
+            formats,
+        })
+    }
+
+    pub fn with_birthdate_only() -> Self {
+        Self::new(DEFAULT_YEARS, true).unwrap()
+    }
+
+    pub fn years(&self) -> &RangeInclusive<u16> {
+        &self.years
+    }
+
+    pub fn birthdate_only(&self) -> bool {
+        self.birthdate_only
+    }
+
+    /// Finds the dates in the given text. Overlapping matches are resolved in favor of the earlier, then the longer
+    /// match.
+    pub fn find_dates(&self, text: &str) -> Vec<DateMatch> {
+        self.find(text, None)
+    }
+
+    /// Finds the dates in the given text, preferring interpretations of ambiguous dates that refer to the birthdate.
+    fn find(&self, text: &str, birthdate: Option<&Date>) -> Vec<DateMatch> {
+        let mut candidates = Vec::new();
+        for (index, (format, regex)) in self.formats.iter().enumerate() {
+            for captures in regex.captures_iter(text).flatten() {
+                let whole = captures.get(0).unwrap();
+                let year_text = captures.name("y").unwrap().as_str();
+                let year: u16 = year_text.parse().unwrap();
+                let two_digit_year = year_text.len() == 2;
+                if !two_digit_year && !self.years.contains(&year) {
+                    continue;
+                }
+                let month = match (captures.name("m"), captures.name("n")) {
+                    (Some(m), _) => Some(m.as_str().parse::<u8>().unwrap()),
+                    (_, Some(n)) => Some(month_number(n.as_str())),
+                    _ => None,
+                };
+                let day = captures.name("d").map(|d| d.as_str().parse::<u8>().unwrap());
+                let valid = match (month, day) {
+                    (Some(m), Some(d)) => Date::new(year, m, d).is_ok(),
+                    (Some(m), None) => (1..=12).contains(&m),
+                    _ => true,
+                };
+                if !valid {
+                    continue;
+                }
+                let format = match captures.name("s") {
+                    Some(s) => format.replace('-', s.as_str()),
+                    None => format.to_string(),
+                };
+                let start = text[..whole.start()].chars().count();
+                let date = DateMatch {
+                    start,
+                    end: start + whole.as_str().chars().count(),
+                    text: whole.as_str().to_string(),
+                    format,
+                    year,
+                    two_digit_year,
+                    month,
+                    day,
+                };
+                candidates.push((index, date));
+            }
+        }
+        candidates.sort_by_key(|(index, date)| {
+            let other = !birthdate.is_some_and(|b| date.is_date(b));
+            (date.start, usize::MAX - date.end, other, *index)
+        });
+        let mut dates: Vec<DateMatch> = Vec::new();
+        for (_, date) in candidates {
+            if dates.last().is_none_or(|last| date.start >= last.end) {
+                dates.push(date);
+            }
+        }
+        dates
+    }
+}
+
+impl Default for DatePatternRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_YEARS, false).unwrap()
+    }
+}
+
+impl Rule for DatePatternRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let birthdate = password_data.birthdate();
+        for date in self.find(password_data.password(), birthdate) {
+            let code = if birthdate.is_some_and(|b| date.is_date(b)) {
+                ERROR_CODE_BIRTHDATE
+            } else if self.birthdate_only {
+                continue;
+            } else {
+                ERROR_CODE
+            };
+            let mut map = HashMap::new();
+            map.insert("match".to_string(), date.text);
+            map.insert("start".to_string(), date.start.to_string());
+            map.insert("end".to_string(), date.end.to_string());
+            map.insert("format".to_string(), date.format);
+            result.add_error(code, Some(map));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::Rule;
+    use crate::rule::date_pattern::{Date, DatePatternRule, ERROR_CODE, ERROR_CODE_BIRTHDATE};
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    fn born(password: &str, birthdate: Date) -> PasswordData {
+        let mut data = PasswordData::with_password(password.to_string());
+        data.set_birthdate(Some(birthdate));
+        data
+    }
+
+    #[test]
+    fn test_passwords() {
+        let birthdate = Date::new(1985, 7, 3).unwrap();
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::default()),
+                password("Summer2024!"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::default()),
+                password("jan0190"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::default()),
+                password("x1990-12-31y31121999"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::default()),
+                password("Tr0ub4dor&3"),
+                vec![],
+            ),
+            // not a valid date and the year is out of range
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::default()),
+                password("pass3112-2150"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::new(1950..=2000, false).unwrap()),
+                password("Summer2024!"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::default()),
+                born("alice-3.7.85!1999", birthdate),
+                vec![ERROR_CODE_BIRTHDATE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::with_birthdate_only()),
+                born("alice-3.7.85!1999", birthdate),
+                vec![ERROR_CODE_BIRTHDATE],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::with_birthdate_only()),
+                born("alice07031985", birthdate),
+                vec![ERROR_CODE_BIRTHDATE],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::with_birthdate_only()),
+                born("alice1999", birthdate),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(DatePatternRule::with_birthdate_only()),
+                password("alice-3.7.85"),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_find_dates() {
+        let rule = DatePatternRule::default();
+        let found = |text: &str| {
+            rule.find_dates(text)
+                .into_iter()
+                .map(|d| (d.text, d.format))
+                .collect::<Vec<_>>()
+        };
+        let expected = |pairs: &[(&str, &str)]| {
+            pairs.iter().map(|(t, f)| (t.to_string(), f.to_string())).collect::<Vec<_>>()
+        };
+        assert_eq!(expected(&[("jan0190", "mmmddyy")]), found("jan0190"));
+        assert_eq!(expected(&[("310190", "ddmmyy")]), found("310190"));
+        assert_eq!(expected(&[("12311990", "mmddyyyy")]), found("a12311990"));
+        assert_eq!(
+            expected(&[("2024/02/29", "yyyy/mm/dd")]),
+            found("2024/02/29")
+        );
+        assert_eq!(expected(&[("2023", "yyyy")]), found("2023/02/29"));
+        assert_eq!(expected(&[("1.5.1990", "dd.mm.yyyy")]), found("1.5.1990"));
+        assert_eq!(expected(&[("March2021", "mmmyyyy")]), found("March2021"));
+        // mixed separators are not a date
+        assert_eq!(expected(&[]), found("01-05.90"));
+        assert_eq!(expected(&[]), found("123456789"));
+
+        let result = rule.validate(&password("Summer2024!"));
+        let detail = format!("{}", result.details()[0]);
+        assert!(detail.contains("\"match\": \"2024\""), "{detail}");
+        assert!(detail.contains("\"start\": \"6\""), "{detail}");
+        assert!(detail.contains("\"end\": \"10\""), "{detail}");
+        assert!(detail.contains("\"format\": \"yyyy\""), "{detail}");
+
+        assert!(Date::new(2023, 2, 29).is_err());
+        assert!(DatePatternRule::new(1900..=10000, false).is_err());
+        assert!(DatePatternRule::new(0..=99, false).is_err());
+    }
+}
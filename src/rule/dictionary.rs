@@ -1,10 +1,11 @@
 use crate::dictionary::Dictionary;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{DictionaryRuleTrait, PasswordData, Rule};
 use std::collections::HashMap;
 
-pub(crate) const ERROR_CODE: &str = "ILLEGAL_WORD";
-pub(crate) const ERROR_CODE_REVERSED: &str = "ILLEGAL_WORD_REVERSED";
+pub(crate) const ERROR_CODE: &str = ErrorCode::IllegalWord.as_str();
+pub(crate) const ERROR_CODE_REVERSED: &str = ErrorCode::IllegalWordReversed.as_str();
 
 /// Rule for determining if a password matches a dictionary word. This rule will optionally also check for reversed words.
 ///
@@ -1,10 +1,11 @@
 use crate::dictionary::Dictionary;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{DictionaryRuleTrait, PasswordData, Rule};
 use std::collections::HashMap;
 
-pub(crate) const ERROR_CODE: &str = "ILLEGAL_WORD";
-const ERROR_CODE_REVERSED: &str = "ILLEGAL_WORD_REVERSED";
+pub(crate) const ERROR_CODE: &str = ErrorCode::IllegalWord.as_str();
+const ERROR_CODE_REVERSED: &str = ErrorCode::IllegalWordReversed.as_str();
 
 /// Rule for determining if a password contains a dictionary word with optional checking for reversed words.
 ///
@@ -46,17 +47,7 @@ impl<D: Dictionary> DictionarySubstringRule<D> {
         }
     }
     fn do_word_search(&self, text: &str) -> Option<String> {
-        for i in 1..=text.len() {
-            let mut j = 0usize;
-            while j + i <= text.len() {
-                let s = &text[j..j + i];
-                if self.dictionary.search(s) {
-                    return Some(s.to_string());
-                }
-                j += 1;
-            }
-        }
-        None
+        search_substrings(&self.dictionary, text)
     }
     fn create_rule_result_detail_parameters(&self, matching_word: &str) -> HashMap<String, String> {
         let mut map = HashMap::with_capacity(1);
@@ -97,6 +88,21 @@ impl<D: Dictionary> DictionaryRuleTrait for DictionarySubstringRule<D> {
         &self.dictionary
     }
 }
+
+/// Returns the shortest substring of text found in the dictionary, the leftmost one if several have the same length.
+pub(crate) fn search_substrings(dictionary: &dyn Dictionary, text: &str) -> Option<String> {
+    let boundaries: Vec<usize> =
+        text.char_indices().map(|(i, _)| i).chain(std::iter::once(text.len())).collect();
+    for len in 1..boundaries.len() {
+        for start in 0..boundaries.len() - len {
+            let s = &text[boundaries[start]..boundaries[start + len]];
+            if dictionary.search(s) {
+                return Some(s.to_string());
+            }
+        }
+    }
+    None
+}
 #[cfg(test)]
 mod tests {
     use crate::dictionary::word_lists::sort::SliceSort;
@@ -235,6 +241,28 @@ mod tests {
         check_passwords(test_cases);
     }
 
+    #[test]
+    fn test_non_ascii() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                create_all_rule(),
+                PasswordData::with_password("p4t日本語#7wé5gew".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                create_all_rule(),
+                PasswordData::with_password("ü4tlancelyé5gew".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                create_all_rule(),
+                PasswordData::with_password("p4日ylecnal本5gew".to_string()),
+                vec![ERROR_CODE_REVERSED],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
     #[test]
     fn test_messages() {
         let test_cases: Vec<RulePasswordTestItem> = vec![
@@ -7,7 +7,8 @@ use crate::rule::{PasswordData, Rule};
 /// Rule for determining if a password matches one of any previous digested password a user has chosen. If no password
 /// reference has been set that matches the label on the rule, then passwords will meet this rule.
 /// You need to bring an implementation of [Hasher].
-/// See also [PasswordData::password_references]
+/// See also [PasswordData::password_references]. Creating the references with [HistoricalReference::with_digest] keeps
+/// rules that compare cleartext, like the password similarity rule, away from them.
 ///
 /// # Example
 ///
@@ -31,10 +32,11 @@ use crate::rule::{PasswordData, Rule};
 ///  use passay_rs::rule::PasswordData;
 ///  use passay_rs::rule::Rule;
 ///
-///  let sha1_ref = Box::new(HistoricalReference::with_password_label(
-///                  "safx/LW8+SsSy/o3PmCNy4VEm5s=".to_string(),
-///                 "history".to_string(),
-///             ));
+///  let sha1_ref = Box::new(HistoricalReference::with_digest(
+///      "safx/LW8+SsSy/o3PmCNy4VEm5s=".to_string(),
+///      Some("history".to_string()),
+///      None,
+///  ));
 ///  let rule = DigestHistoryRule::new(Sha1Hasher, true);
 ///  let password = PasswordData::new("t3stUs3r01".to_string(), Some("testuser".to_string()), vec![sha1_ref]);
 ///  let result = rule.validate(&password);
@@ -46,6 +48,7 @@ where
 {
     hasher: H,
     report_all: bool,
+    depth: Option<usize>,
 }
 
 impl<H> DigestHistoryRule<H>
@@ -53,7 +56,27 @@ where
     H: Hasher<String>,
 {
     pub fn new(hasher: H, report_all: bool) -> Self {
-        Self { hasher, report_all }
+        Self {
+            hasher,
+            report_all,
+            depth: None,
+        }
+    }
+
+    /// Creates a rule checking only the given number of most recent references.
+    pub fn with_depth(hasher: H, depth: usize, report_all: bool) -> Result<Self, String> {
+        if depth == 0 {
+            return Err("history depth must be greater than zero".to_string());
+        }
+        Ok(Self {
+            hasher,
+            report_all,
+            depth: Some(depth),
+        })
+    }
+
+    pub fn depth(&self) -> Option<usize> {
+        self.depth
     }
 }
 
@@ -72,7 +95,7 @@ where
             h.compare(rf.password().as_bytes(), undigested.as_bytes()).unwrap_or(false)
         };
 
-        validate_with_history_references(self.report_all, password_data, matcher)
+        validate_with_history_references(self.report_all, self.depth, password_data, matcher)
     }
 }
 
@@ -89,9 +112,10 @@ pub(crate) mod test {
 
     #[test]
     fn test_passwords() {
-        let bcrypt_ref = Box::new(HistoricalReference::with_password_label(
+        let bcrypt_ref = Box::new(HistoricalReference::with_digest(
             "$2a$5$bvIG6Nmid91Mu9RcmmWZfO5HJIMCT8riNW0hEp8f6/FuA2/mHZFpe".to_string(),
-            "bcrypt-history".to_string(),
+            Some("bcrypt-history".to_string()),
+            None,
         ));
         let test_cases: Vec<RulePasswordTestItem> = vec![
             RulePasswordTestItem(
@@ -305,35 +329,60 @@ pub(crate) mod test {
         )];
         check_messages(test_cases);
     }
+    #[test]
+    fn test_depth() {
+        let rule = || Box::new(DigestHistoryRule::with_depth(Sha1Hasher, 1, true).unwrap());
+        let password = |password: &str| {
+            PasswordData::new(
+                password.to_string(),
+                Some("testuser".to_string()),
+                create_digest_refs(),
+            )
+        };
+        check_passwords(vec![
+            RulePasswordTestItem(rule(), password("t3stUs3r01"), vec![]),
+            RulePasswordTestItem(rule(), password("t3stUs3r03"), vec![ERROR_CODE]),
+        ]);
+        check_messages(vec![RulePasswordTestItem(
+            rule(),
+            password("t3stUs3r03"),
+            vec!["HISTORY_VIOLATION,1"],
+        )]);
+        assert!(DigestHistoryRule::with_depth(Sha1Hasher, 0, true).is_err());
+    }
+
     fn create_digest_refs() -> Vec<Box<dyn Reference>> {
         vec![
-            Box::new(HistoricalReference::with_password_label(
+            Box::new(HistoricalReference::with_digest(
                 "safx/LW8+SsSy/o3PmCNy4VEm5s=".to_string(),
-                "history".to_string(),
+                Some("history".to_string()),
+                None,
             )),
-            Box::new(HistoricalReference::with_password_label(
+            Box::new(HistoricalReference::with_digest(
                 "zurb9DyQ5nooY1la8h86Bh0n1iw=".to_string(),
-                "history".to_string(),
+                Some("history".to_string()),
+                None,
             )),
-            Box::new(HistoricalReference::with_password_label(
+            Box::new(HistoricalReference::with_digest(
                 "bhqabXwE3S8E6xNJfX/d76MFOCs=".to_string(),
-                "history".to_string(),
+                Some("history".to_string()),
+                None,
             )),
         ]
     }
     fn create_prefix_salted_digest_refs() -> Vec<Box<dyn Reference>> {
         vec![
-            Box::new(HistoricalReference::new(
+            Box::new(HistoricalReference::with_digest(
                 "lHGQFf9tTVUOCG3CoNqdKaiCThA=".to_string(),
                 Some("pre-salt-history".to_string()),
                 Some(Prefix("xyz".to_string())),
             )),
-            Box::new(HistoricalReference::new(
+            Box::new(HistoricalReference::with_digest(
                 "GtEfsfrBomR/3aD5RfBGWPOKlYc=".to_string(),
                 Some("pre-salt-history".to_string()),
                 Some(Prefix("xyz".to_string())),
             )),
-            Box::new(HistoricalReference::new(
+            Box::new(HistoricalReference::with_digest(
                 "XZ2CO63FrS5N7wvCmyzkiBAYNoY=".to_string(),
                 Some("pre-salt-history".to_string()),
                 Some(Prefix("xyz".to_string())),
@@ -342,17 +391,17 @@ pub(crate) mod test {
     }
     fn create_suffix_salted_digest_refs() -> Vec<Box<dyn Reference>> {
         vec![
-            Box::new(HistoricalReference::new(
+            Box::new(HistoricalReference::with_digest(
                 "HnBhNzaSRdKqmIZbau97E++rysM=".to_string(),
                 Some("suf-salt-history".to_string()),
                 Some(Suffix("xyz".to_string())),
             )),
-            Box::new(HistoricalReference::new(
+            Box::new(HistoricalReference::with_digest(
                 "ScDf3gIY16LF6UAeWVr7nZHSvbE=".to_string(),
                 Some("suf-salt-history".to_string()),
                 Some(Suffix("xyz".to_string())),
             )),
-            Box::new(HistoricalReference::new(
+            Box::new(HistoricalReference::with_digest(
                 "apjCHJyez2IvOlBM5mqD2DvSk6o=".to_string(),
                 Some("suf-salt-history".to_string()),
                 Some(Suffix("xyz".to_string())),
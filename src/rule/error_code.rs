@@ -0,0 +1,312 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Error codes reported in [RuleResultDetail](crate::rule::rule_result::RuleResultDetail) by the built-in rules.
+///
+/// Rules outside this crate report their own codes, which are represented by [ErrorCode::Custom]. Converting a string
+/// always succeeds, known codes are mapped to their variant and everything else becomes a custom code.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///  use passay_rs::rule::error_code::ErrorCode;
+///  use passay_rs::rule::length::LengthRule;
+///
+///  let result = LengthRule::new(8, 16).validate(&PasswordData::with_password("secret".to_string()));
+///  let message = match result.details()[0].code() {
+///      ErrorCode::TooShort => "too short",
+///      ErrorCode::TooLong => "too long",
+///      _ => "invalid",
+///  };
+///  assert_eq!("too short", message);
+///  assert_eq!(ErrorCode::Custom("ILLEGAL_PREFIX".to_string()), ErrorCode::from("ILLEGAL_PREFIX"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// `TOO_SHORT`, the password is shorter than the minimum length.
+    TooShort,
+    /// `TOO_LONG`, the password is longer than the maximum length.
+    TooLong,
+    /// `INSUFFICIENT_LOWERCASE`, not enough lowercase characters.
+    InsufficientLowercase,
+    /// `INSUFFICIENT_UPPERCASE`, not enough uppercase characters.
+    InsufficientUppercase,
+    /// `INSUFFICIENT_DIGIT`, not enough digits.
+    InsufficientDigit,
+    /// `INSUFFICIENT_ALPHABETICAL`, not enough alphabetical characters.
+    InsufficientAlphabetical,
+    /// `INSUFFICIENT_SPECIAL`, not enough special characters.
+    InsufficientSpecial,
+    /// `INSUFFICIENT_SPACE`, not enough space characters.
+    InsufficientSpace,
+    /// `INSUFFICIENT_CHARACTERISTICS`, too few of the character rules of a characteristics rule are met.
+    InsufficientCharacteristics,
+    /// `INSUFFICIENT_COMPLEXITY`, the rules for the length of the password are not met.
+    InsufficientComplexity,
+    /// `INSUFFICIENT_COMPLEXITY_RULES`, no rules are configured for the length of the password.
+    InsufficientComplexityRules,
+    /// `INSUFFICIENT_UNIQUE_CHARACTERS`, the password has too few distinct characters.
+    InsufficientUniqueCharacters,
+    /// `INSUFFICIENT_ENTROPY`, the estimated entropy of the password is too low.
+    InsufficientEntropy,
+    /// `ENTROPY_ESTIMATE_FAILED`, the entropy of the password could not be estimated.
+    EntropyEstimateFailed,
+    /// `ALLOWED_CHAR`, the password contains a character that is not allowed.
+    AllowedChar,
+    /// `ALLOWED_MATCH`, the password does not match the allowed pattern.
+    AllowedMatch,
+    /// `ILLEGAL_CHAR`, the password contains an illegal character.
+    IllegalChar,
+    /// `ILLEGAL_CODE_POINT`, the password contains a code point of an illegal class, e.g. a control character.
+    IllegalCodePoint,
+    /// `ILLEGAL_MATCH`, the password matches an illegal pattern or repeats a character.
+    IllegalMatch,
+    /// `ILLEGAL_CHARACTER_PATTERN`, the character classes of the password follow a predictable structure.
+    IllegalCharacterPattern,
+    /// `ILLEGAL_MIXED_SCRIPT`, the password mixes too many Unicode scripts.
+    IllegalMixedScript,
+    /// `ILLEGAL_CONFUSABLE`, the password contains a character resembling one of another script.
+    IllegalConfusable,
+    /// `REGEX_ERROR`, a regular expression could not be matched against the password or its class pattern.
+    RegexError,
+    /// `ILLEGAL_WORD`, the password contains a dictionary word.
+    IllegalWord,
+    /// `ILLEGAL_WORD_REVERSED`, the password contains a reversed dictionary word.
+    IllegalWordReversed,
+    /// `ILLEGAL_CONTEXT_WORD`, the password contains a word specific to the service.
+    IllegalContextWord,
+    /// `ILLEGAL_USERNAME`, the password contains the username.
+    IllegalUsername,
+    /// `ILLEGAL_USERNAME_REVERSED`, the password contains the reversed username.
+    IllegalUsernameReversed,
+    /// `ILLEGAL_WHITESPACE`, the password contains whitespace.
+    IllegalWhitespace,
+    /// `ILLEGAL_NUMBER_RANGE`, the password contains a number of an illegal range.
+    IllegalNumberRange,
+    /// `ILLEGAL_REPEATED_CHARS`, the password contains too many repeated character sequences.
+    IllegalRepeatedChars,
+    /// `ILLEGAL_REPEATED_SUBSTRING`, the password contains a block of characters repeated in a row.
+    IllegalRepeatedSubstring,
+    /// `TOO_MANY_OCCURRENCES`, a character occurs too often.
+    TooManyOccurrences,
+    /// `ILLEGAL_ALPHABETICAL_SEQUENCE`, the password contains an alphabetical sequence.
+    IllegalAlphabeticalSequence,
+    /// `ILLEGAL_NUMERICAL_SEQUENCE`, the password contains a numerical sequence.
+    IllegalNumericalSequence,
+    /// `ILLEGAL_QWERTY_SEQUENCE`, the password contains a keyboard sequence.
+    IllegalQwertySequence,
+    /// `ILLEGAL_KEYBOARD_WALK`, the password contains a walk across adjacent keyboard keys.
+    IllegalKeyboardWalk,
+    /// `ILLEGAL_DATE`, the password contains a date or a year.
+    IllegalDate,
+    /// `ILLEGAL_BIRTHDATE`, the password contains the birthdate of the user.
+    IllegalBirthdate,
+    /// `HISTORY_VIOLATION`, the password matches a previous password.
+    HistoryViolation,
+    /// `SIMILARITY_VIOLATION`, the password is too similar to a previous password.
+    SimilarityViolation,
+    /// `PASSWORD_TOO_RECENT`, the password was changed too recently.
+    PasswordTooRecent,
+    /// `SOURCE_VIOLATION`, the password matches a password from another source.
+    SourceViolation,
+    /// `BREACHED_PASSWORD`, the password appears in a breach corpus.
+    BreachedPassword,
+    /// `BREACH_CHECK_FAILED`, the breach corpus could not be queried.
+    BreachCheckFailed,
+    /// A code reported by a rule outside this crate.
+    Custom(String),
+}
+
+impl ErrorCode {
+    /// All codes reported by the built-in rules.
+    pub const BUILT_IN: &'static [ErrorCode] = &[
+        ErrorCode::TooShort,
+        ErrorCode::TooLong,
+        ErrorCode::InsufficientLowercase,
+        ErrorCode::InsufficientUppercase,
+        ErrorCode::InsufficientDigit,
+        ErrorCode::InsufficientAlphabetical,
+        ErrorCode::InsufficientSpecial,
+        ErrorCode::InsufficientSpace,
+        ErrorCode::InsufficientCharacteristics,
+        ErrorCode::InsufficientComplexity,
+        ErrorCode::InsufficientComplexityRules,
+        ErrorCode::InsufficientUniqueCharacters,
+        ErrorCode::InsufficientEntropy,
+        ErrorCode::EntropyEstimateFailed,
+        ErrorCode::AllowedChar,
+        ErrorCode::AllowedMatch,
+        ErrorCode::IllegalChar,
+        ErrorCode::IllegalCodePoint,
+        ErrorCode::IllegalMatch,
+        ErrorCode::IllegalCharacterPattern,
+        ErrorCode::IllegalMixedScript,
+        ErrorCode::IllegalConfusable,
+        ErrorCode::RegexError,
+        ErrorCode::IllegalWord,
+        ErrorCode::IllegalWordReversed,
+        ErrorCode::IllegalContextWord,
+        ErrorCode::IllegalUsername,
+        ErrorCode::IllegalUsernameReversed,
+        ErrorCode::IllegalWhitespace,
+        ErrorCode::IllegalNumberRange,
+        ErrorCode::IllegalRepeatedChars,
+        ErrorCode::IllegalRepeatedSubstring,
+        ErrorCode::TooManyOccurrences,
+        ErrorCode::IllegalAlphabeticalSequence,
+        ErrorCode::IllegalNumericalSequence,
+        ErrorCode::IllegalQwertySequence,
+        ErrorCode::IllegalKeyboardWalk,
+        ErrorCode::IllegalDate,
+        ErrorCode::IllegalBirthdate,
+        ErrorCode::HistoryViolation,
+        ErrorCode::SimilarityViolation,
+        ErrorCode::PasswordTooRecent,
+        ErrorCode::SourceViolation,
+        ErrorCode::BreachedPassword,
+        ErrorCode::BreachCheckFailed,
+    ];
+
+    pub const fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::TooShort => "TOO_SHORT",
+            ErrorCode::TooLong => "TOO_LONG",
+            ErrorCode::InsufficientLowercase => "INSUFFICIENT_LOWERCASE",
+            ErrorCode::InsufficientUppercase => "INSUFFICIENT_UPPERCASE",
+            ErrorCode::InsufficientDigit => "INSUFFICIENT_DIGIT",
+            ErrorCode::InsufficientAlphabetical => "INSUFFICIENT_ALPHABETICAL",
+            ErrorCode::InsufficientSpecial => "INSUFFICIENT_SPECIAL",
+            ErrorCode::InsufficientSpace => "INSUFFICIENT_SPACE",
+            ErrorCode::InsufficientCharacteristics => "INSUFFICIENT_CHARACTERISTICS",
+            ErrorCode::InsufficientComplexity => "INSUFFICIENT_COMPLEXITY",
+            ErrorCode::InsufficientComplexityRules => "INSUFFICIENT_COMPLEXITY_RULES",
+            ErrorCode::InsufficientUniqueCharacters => "INSUFFICIENT_UNIQUE_CHARACTERS",
+            ErrorCode::InsufficientEntropy => "INSUFFICIENT_ENTROPY",
+            ErrorCode::EntropyEstimateFailed => "ENTROPY_ESTIMATE_FAILED",
+            ErrorCode::AllowedChar => "ALLOWED_CHAR",
+            ErrorCode::AllowedMatch => "ALLOWED_MATCH",
+            ErrorCode::IllegalChar => "ILLEGAL_CHAR",
+            ErrorCode::IllegalCodePoint => "ILLEGAL_CODE_POINT",
+            ErrorCode::IllegalMatch => "ILLEGAL_MATCH",
+            ErrorCode::IllegalCharacterPattern => "ILLEGAL_CHARACTER_PATTERN",
+            ErrorCode::IllegalMixedScript => "ILLEGAL_MIXED_SCRIPT",
+            ErrorCode::IllegalConfusable => "ILLEGAL_CONFUSABLE",
+            ErrorCode::RegexError => "REGEX_ERROR",
+            ErrorCode::IllegalWord => "ILLEGAL_WORD",
+            ErrorCode::IllegalWordReversed => "ILLEGAL_WORD_REVERSED",
+            ErrorCode::IllegalContextWord => "ILLEGAL_CONTEXT_WORD",
+            ErrorCode::IllegalUsername => "ILLEGAL_USERNAME",
+            ErrorCode::IllegalUsernameReversed => "ILLEGAL_USERNAME_REVERSED",
+            ErrorCode::IllegalWhitespace => "ILLEGAL_WHITESPACE",
+            ErrorCode::IllegalNumberRange => "ILLEGAL_NUMBER_RANGE",
+            ErrorCode::IllegalRepeatedChars => "ILLEGAL_REPEATED_CHARS",
+            ErrorCode::IllegalRepeatedSubstring => "ILLEGAL_REPEATED_SUBSTRING",
+            ErrorCode::TooManyOccurrences => "TOO_MANY_OCCURRENCES",
+            ErrorCode::IllegalAlphabeticalSequence => "ILLEGAL_ALPHABETICAL_SEQUENCE",
+            ErrorCode::IllegalNumericalSequence => "ILLEGAL_NUMERICAL_SEQUENCE",
+            ErrorCode::IllegalQwertySequence => "ILLEGAL_QWERTY_SEQUENCE",
+            ErrorCode::IllegalKeyboardWalk => "ILLEGAL_KEYBOARD_WALK",
+            ErrorCode::IllegalDate => "ILLEGAL_DATE",
+            ErrorCode::IllegalBirthdate => "ILLEGAL_BIRTHDATE",
+            ErrorCode::HistoryViolation => "HISTORY_VIOLATION",
+            ErrorCode::SimilarityViolation => "SIMILARITY_VIOLATION",
+            ErrorCode::PasswordTooRecent => "PASSWORD_TOO_RECENT",
+            ErrorCode::SourceViolation => "SOURCE_VIOLATION",
+            ErrorCode::BreachedPassword => "BREACHED_PASSWORD",
+            ErrorCode::BreachCheckFailed => "BREACH_CHECK_FAILED",
+            ErrorCode::Custom(code) => code.as_str(),
+        }
+    }
+
+    /// Returns whether this code is not reported by a built-in rule.
+    pub fn is_custom(&self) -> bool {
+        matches!(self, ErrorCode::Custom(_))
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for ErrorCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ErrorCode::from(s))
+    }
+}
+
+impl From<&str> for ErrorCode {
+    fn from(code: &str) -> Self {
+        ErrorCode::BUILT_IN
+            .iter()
+            .find(|c| c.as_str() == code)
+            .cloned()
+            .unwrap_or_else(|| ErrorCode::Custom(code.to_string()))
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        match ErrorCode::from(code.as_str()) {
+            ErrorCode::Custom(_) => ErrorCode::Custom(code),
+            built_in => built_in,
+        }
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::Custom(code) => code,
+            built_in => built_in.as_str().to_string(),
+        }
+    }
+}
+
+impl PartialEq<str> for ErrorCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ErrorCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::error_code::ErrorCode;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_conversions() {
+        for code in ErrorCode::BUILT_IN {
+            assert!(!code.is_custom());
+            assert_eq!(code, &ErrorCode::from(code.as_str()));
+            assert_eq!(code, &ErrorCode::from(code.to_string()));
+            assert_eq!(code.as_str(), String::from(code.clone()));
+        }
+        let codes: HashSet<&str> = ErrorCode::BUILT_IN.iter().map(ErrorCode::as_str).collect();
+        assert_eq!(ErrorCode::BUILT_IN.len(), codes.len());
+
+        let custom: ErrorCode = "INSUFFICIENT_VOWEL".parse().unwrap();
+        assert_eq!(ErrorCode::Custom("INSUFFICIENT_VOWEL".to_string()), custom);
+        assert!(custom.is_custom());
+        assert_eq!("INSUFFICIENT_VOWEL", custom.to_string());
+        assert_eq!(custom, "INSUFFICIENT_VOWEL");
+    }
+}
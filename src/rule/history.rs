@@ -1,15 +1,19 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::reference::{Reference, Salt};
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::time::SystemTime;
 
-pub const ERROR_CODE: &str = "HISTORY_VIOLATION";
+pub const ERROR_CODE: &str = ErrorCode::HistoryViolation.as_str();
 
 /// Rule for determining if a password matches one of any previous password a user has chosen. If no historical password
 /// reference has been set, then passwords will meet this rule. See also [PasswordData::password_references]
 ///
+/// With a history depth only the most recent references are checked, see [most_recent_references].
+///
 /// # Example
 ///
 /// ```
@@ -36,11 +40,30 @@ pub const ERROR_CODE: &str = "HISTORY_VIOLATION";
 #[derive(Clone)]
 pub struct HistoryRule {
     report_all: bool,
+    depth: Option<usize>,
 }
 
 impl HistoryRule {
     pub fn new(report_all: bool) -> HistoryRule {
-        HistoryRule { report_all }
+        HistoryRule {
+            report_all,
+            depth: None,
+        }
+    }
+
+    /// Creates a rule checking only the given number of most recent references.
+    pub fn with_depth(depth: usize, report_all: bool) -> Result<HistoryRule, String> {
+        if depth == 0 {
+            return Err("history depth must be greater than zero".to_string());
+        }
+        Ok(HistoryRule {
+            report_all,
+            depth: Some(depth),
+        })
+    }
+
+    pub fn depth(&self) -> Option<usize> {
+        self.depth
     }
 }
 
@@ -51,32 +74,45 @@ pub fn create_rule_result_detail_parameters(len: usize) -> HashMap<String, Strin
 }
 impl Rule for HistoryRule {
     fn validate(&self, password_data: &PasswordData) -> RuleResult {
-        validate_with_history_references(self.report_all, password_data, matches)
+        validate_with_history_references(self.report_all, self.depth, password_data, matches)
     }
 }
 
+/// Returns the historical references of the password data, the most recent first. References are ordered by their
+/// creation time; references without one are older than all timestamped references and are ordered by their position,
+/// the last one being the most recent.
+pub fn most_recent_references(password_data: &PasswordData) -> Vec<&HistoricalReference> {
+    let mut references: Vec<(usize, &HistoricalReference)> = password_data
+        .password_references()
+        .iter()
+        .filter_map(|rf| rf.as_any().downcast_ref::<HistoricalReference>())
+        .enumerate()
+        .collect();
+    references
+        .sort_by(|(a_index, a), (b_index, b)| (b.created, b_index).cmp(&(a.created, a_index)));
+    references.into_iter().map(|(_, rf)| rf).collect()
+}
+
 pub(super) fn validate_with_history_references<F: Fn(&str, &HistoricalReference) -> bool>(
     report_all: bool,
+    depth: Option<usize>,
     password_data: &PasswordData,
     matcher: F,
 ) -> RuleResult {
     let mut result = RuleResult::default();
 
-    let mut len = 0;
-    for rf in password_data.password_references() {
-        if rf.as_any().downcast_ref::<HistoricalReference>().is_some() {
-            len += 1;
-        }
+    let mut references = most_recent_references(password_data);
+    if let Some(depth) = depth {
+        references.truncate(depth);
     }
+    let len = references.len();
 
-    for rf in password_data.password_references() {
-        if let Some(rf) = rf.as_any().downcast_ref::<HistoricalReference>() {
-            let cleartext = password_data.password();
-            if matcher(cleartext, rf) {
-                result.add_error(ERROR_CODE, Some(create_rule_result_detail_parameters(len)));
-                if !report_all {
-                    return result;
-                }
+    for rf in references {
+        let cleartext = password_data.password();
+        if matcher(cleartext, rf) {
+            result.add_error(ERROR_CODE, Some(create_rule_result_detail_parameters(len)));
+            if !report_all {
+                return result;
             }
         }
     }
@@ -85,7 +121,7 @@ pub(super) fn validate_with_history_references<F: Fn(&str, &HistoricalReference)
 
 impl Default for HistoryRule {
     fn default() -> Self {
-        Self { report_all: true }
+        Self::new(true)
     }
 }
 fn matches(password: &str, rf: &HistoricalReference) -> bool {
@@ -96,6 +132,8 @@ pub struct HistoricalReference {
     label: Option<String>,
     password: String,
     salt: Option<Salt>,
+    created: Option<SystemTime>,
+    digest: bool,
 }
 
 impl HistoricalReference {
@@ -104,6 +142,21 @@ impl HistoricalReference {
             password,
             label,
             salt,
+            created: None,
+            digest: false,
+        }
+    }
+
+    /// Creates a reference to a password stored as a digest, see
+    /// [DigestHistoryRule](crate::rule::digest_history::DigestHistoryRule).
+    pub fn with_digest(
+        digest: String,
+        label: Option<String>,
+        salt: Option<Salt>,
+    ) -> HistoricalReference {
+        HistoricalReference {
+            digest: true,
+            ..Self::new(digest, label, salt)
         }
     }
 
@@ -113,6 +166,24 @@ impl HistoricalReference {
     pub fn with_password_label(password: String, label: String) -> HistoricalReference {
         Self::new(password, Some(label), None)
     }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Whether the password is stored as a digest instead of cleartext.
+    pub fn is_digest(&self) -> bool {
+        self.digest
+    }
+
+    /// When the password was set, if known.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.created
+    }
+
+    pub fn set_created(&mut self, created: Option<SystemTime>) {
+        self.created = created;
+    }
 }
 
 impl Debug for HistoricalReference {
@@ -120,6 +191,8 @@ impl Debug for HistoricalReference {
         f.debug_struct("HistoricalReference")
             .field("password", &self.password)
             .field("label", &self.label)
+            .field("created", &self.created)
+            .field("digest", &self.digest)
             .finish()
     }
 }
@@ -144,6 +217,7 @@ mod test {
     use crate::rule::history::{ERROR_CODE, HistoricalReference, HistoryRule};
     use crate::rule::reference::Reference;
     use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+    use std::time::SystemTime;
 
     #[test]
     fn test_passwords() {
@@ -299,6 +373,56 @@ mod test {
         check_messages(test_cases);
     }
 
+    #[test]
+    fn test_depth() {
+        let rule = HistoryRule::with_depth(2, true).unwrap();
+        let password = |password: &str, history| {
+            PasswordData::new(password.to_string(), Some("testuser".to_string()), history)
+        };
+        // without timestamps the last references are the most recent
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                Box::new(rule.clone()),
+                password("t3stUs3r01", setup_history()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(rule.clone()),
+                password("t3stUs3r02", setup_history()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(rule.clone()),
+                password("t3stUs3r03", setup_history()),
+                vec![ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+
+        // timestamped references are more recent than the others
+        let mut history = setup_history();
+        let mut first = HistoricalReference::with_password("t3stUs3r01".to_string());
+        first.set_created(Some(SystemTime::now()));
+        history[0] = Box::new(first);
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                Box::new(rule.clone()),
+                password("t3stUs3r01", history),
+                vec!["HISTORY_VIOLATION,2"],
+            ),
+            RulePasswordTestItem(
+                Box::new(rule.clone()),
+                password("t3stUs3r03", setup_history()),
+                vec!["HISTORY_VIOLATION,2"],
+            ),
+        ];
+        check_messages(test_cases);
+
+        assert!(HistoryRule::with_depth(0, true).is_err());
+        assert_eq!(Some(2), rule.depth());
+        assert_eq!(None, HistoryRule::default().depth());
+    }
+
     fn setup_history() -> Vec<Box<dyn Reference>> {
         vec![
             Box::new(HistoricalReference::with_password_label(
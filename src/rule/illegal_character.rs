@@ -1,11 +1,12 @@
 use crate::rule::allowed_character::MatchBehavior;
 use crate::rule::allowed_character::MatchBehavior::Contains;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::password_utils::count_matching_characters;
 use crate::rule::rule_result::{CountCategory, RuleResult, RuleResultMetadata};
 use crate::rule::{PasswordData, Rule};
 use std::collections::{HashMap, HashSet};
 
-const ERROR_CODE: &str = "ILLEGAL_CHAR";
+const ERROR_CODE: &str = ErrorCode::IllegalChar.as_str();
 
 /// Rule for determining if a password contains an illegal character. Validation will fail if the password contains any
 /// of the illegal characters.
@@ -216,7 +217,7 @@ mod tests {
         assert_eq!(0, option);
 
         let result = rule.validate(&PasswordData::with_password("meta@data$".to_string()));
-        assert_eq!(false, result.valid());
+        assert!(!result.valid());
         let option = result.metadata().get_count(Illegal);
         assert_eq!(2, option);
     }
@@ -0,0 +1,354 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::{CountCategory, RuleResult, RuleResultMetadata};
+use crate::rule::{PasswordData, Rule};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use unicode_general_category::{GeneralCategory, get_general_category};
+use unicode_properties::{EmojiStatus, UnicodeEmoji};
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalCodePoint.as_str();
+
+/// Classes of code points that are hard to see or type, or have no meaning in a password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodePointClass {
+    /// C0 and C1 control characters, e.g. `U+0000` or `U+0085`.
+    Control,
+    /// Invisible format characters, e.g. zero-width spaces and joiners, the byte order mark or the soft hyphen, and
+    /// the Hangul fillers.
+    Invisible,
+    /// Bidirectional marks, embeddings, overrides and isolates, e.g. `U+202E RIGHT-TO-LEFT OVERRIDE`.
+    Bidi,
+    /// Private use characters, e.g. `U+E000`.
+    PrivateUse,
+    /// Code points not assigned in Unicode 16.
+    Unassigned,
+    /// The 66 noncharacters, e.g. `U+FFFF`.
+    Noncharacter,
+    /// Emoji and their modifiers, e.g. `🦀` or `❤️`.
+    Emoji,
+}
+
+impl CodePointClass {
+    pub const ALL: [CodePointClass; 7] = [
+        CodePointClass::Control,
+        CodePointClass::Invisible,
+        CodePointClass::Bidi,
+        CodePointClass::PrivateUse,
+        CodePointClass::Unassigned,
+        CodePointClass::Noncharacter,
+        CodePointClass::Emoji,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodePointClass::Control => "control",
+            CodePointClass::Invisible => "invisible",
+            CodePointClass::Bidi => "bidi",
+            CodePointClass::PrivateUse => "privateUse",
+            CodePointClass::Unassigned => "unassigned",
+            CodePointClass::Noncharacter => "noncharacter",
+            CodePointClass::Emoji => "emoji",
+        }
+    }
+
+    /// Returns the class of a single code point, if any. Emoji are only recognized when they are presented as emoji by
+    /// default, see [IllegalCodePointRule::classify] for code points in context.
+    pub fn of(c: char) -> Option<CodePointClass> {
+        if is_noncharacter(c) {
+            return Some(CodePointClass::Noncharacter);
+        }
+        if is_bidi(c) {
+            return Some(CodePointClass::Bidi);
+        }
+        if is_emoji_presentation(c) {
+            return Some(CodePointClass::Emoji);
+        }
+        match get_general_category(c) {
+            GeneralCategory::Control => Some(CodePointClass::Control),
+            GeneralCategory::Format => Some(CodePointClass::Invisible),
+            GeneralCategory::PrivateUse => Some(CodePointClass::PrivateUse),
+            GeneralCategory::Unassigned => Some(CodePointClass::Unassigned),
+            _ if matches!(c, '\u{115f}' | '\u{1160}' | '\u{3164}' | '\u{ffa0}') => {
+                Some(CodePointClass::Invisible)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for CodePointClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+fn is_noncharacter(c: char) -> bool {
+    matches!(c, '\u{fdd0}'..='\u{fdef}') || (c as u32) & 0xfffe == 0xfffe
+}
+
+fn is_bidi(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061c}' | '\u{200e}' | '\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+fn is_emoji_presentation(c: char) -> bool {
+    matches!(
+        c.emoji_status(),
+        EmojiStatus::EmojiPresentation
+            | EmojiStatus::EmojiPresentationAndModifierBase
+            | EmojiStatus::EmojiPresentationAndEmojiComponent
+            | EmojiStatus::EmojiPresentationAndModifierAndEmojiComponent
+    )
+}
+
+/// Returns whether the code point continues an emoji sequence, e.g. a zero width joiner, a variation selector, a skin
+/// tone modifier or a tag of a subdivision flag.
+fn is_emoji_continuation(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200d}' | '\u{fe0f}' | '\u{20e3}' | '\u{e0020}'..='\u{e007f}'
+    ) || c.emoji_status() == EmojiStatus::EmojiPresentationAndModifierAndEmojiComponent
+}
+
+/// Returns the name of the code point, or its code point label for code points without a name, e.g.
+/// `<control-0007>`.
+pub fn code_point_name(c: char) -> String {
+    if let Some(name) = unicode_names2::name(c) {
+        return name.to_string();
+    }
+    let label = match CodePointClass::of(c) {
+        Some(CodePointClass::Control) => "control",
+        Some(CodePointClass::PrivateUse) => "private-use",
+        Some(CodePointClass::Noncharacter) => "noncharacter",
+        _ => "reserved",
+    };
+    format!("<{label}-{:04X}>", c as u32)
+}
+
+/// Rule for determining if a password contains code points of illegal classes, such as control characters,
+/// zero-width and bidi override characters that hide or reorder parts of the password, or unassigned code points.
+/// Noncharacters are also illegal by default, `U+FFFF` is used as a sentinel by
+/// [IllegalSequenceRule](crate::rule::illegal_sequence::IllegalSequenceRule).
+///
+/// Emoji are allowed unless [CodePointClass::Emoji] is one of the illegal classes. Zero width joiners, variation
+/// selectors and other parts of emoji sequences such as `👩‍💻` belong to the emoji and are never reported as invisible.
+///
+/// Each illegal code point is reported once with its name, its code point and its class. The error codes are
+/// `ILLEGAL_CODE_POINT.<class>` and `ILLEGAL_CODE_POINT`, e.g. `ILLEGAL_CODE_POINT.bidi`.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::illegal_code_point::{CodePointClass, IllegalCodePointRule};
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = IllegalCodePointRule::default();
+///  let result = rule.validate(&PasswordData::with_password("pass\u{200b}word".to_string()));
+///  assert!(!result.valid());
+///  let result = rule.validate(&PasswordData::with_password("crab🦀rave".to_string()));
+///  assert!(result.valid());
+///
+///  let rule = IllegalCodePointRule::new(CodePointClass::ALL.to_vec(), true);
+///  let result = rule.validate(&PasswordData::with_password("crab🦀rave".to_string()));
+///  assert!(!result.valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IllegalCodePointRule {
+    classes: Vec<CodePointClass>,
+    report_all: bool,
+}
+
+impl IllegalCodePointRule {
+    pub fn new(classes: Vec<CodePointClass>, report_all: bool) -> Self {
+        Self {
+            classes,
+            report_all,
+        }
+    }
+
+    pub fn classes(&self) -> &[CodePointClass] {
+        &self.classes
+    }
+
+    /// Returns the code points of the text together with their classes, taking emoji sequences into account.
+    pub fn classify(text: &str) -> Vec<(char, Option<CodePointClass>)> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut classified = Vec::with_capacity(chars.len());
+        // whether the previous code points form an emoji sequence
+        let mut in_emoji = false;
+        for (i, &c) in chars.iter().enumerate() {
+            if in_emoji && is_emoji_continuation(c) {
+                classified.push((c, None));
+                continue;
+            }
+            // text style emoji such as `♀` count as emoji when joined to one or followed by the presentation selector
+            let joined = in_emoji && i > 0 && chars[i - 1] == '\u{200d}';
+            let presented = chars.get(i + 1) == Some(&'\u{fe0f}');
+            let class = if (joined || presented) && c.is_emoji_char() && !c.is_emoji_component() {
+                Some(CodePointClass::Emoji)
+            } else {
+                CodePointClass::of(c)
+            };
+            in_emoji = class == Some(CodePointClass::Emoji);
+            classified.push((c, class));
+        }
+        classified
+    }
+
+    fn create_rule_result_detail_parameters(
+        &self,
+        c: char,
+        class: CodePointClass,
+    ) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(3);
+        map.insert("codePoint".to_string(), format!("U+{:04X}", c as u32));
+        map.insert("name".to_string(), code_point_name(c));
+        map.insert("class".to_string(), class.to_string());
+        map
+    }
+}
+
+impl Default for IllegalCodePointRule {
+    fn default() -> Self {
+        let classes = CodePointClass::ALL
+            .into_iter()
+            .filter(|class| *class != CodePointClass::Emoji)
+            .collect();
+        Self::new(classes, true)
+    }
+}
+
+impl Rule for IllegalCodePointRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let mut count = 0;
+        let mut reported = HashSet::new();
+        for (c, class) in Self::classify(password_data.password()) {
+            let Some(class) = class.filter(|class| self.classes.contains(class)) else {
+                continue;
+            };
+            count += 1;
+            if (self.report_all || result.valid()) && reported.insert(c) {
+                let codes = vec![format!("{ERROR_CODE}.{class}"), ERROR_CODE.to_string()];
+                result.add_error_with_codes(
+                    &codes,
+                    Some(self.create_rule_result_detail_parameters(c, class)),
+                );
+            }
+        }
+        result.set_metadata(RuleResultMetadata::new(CountCategory::Illegal, count));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::Rule;
+    use crate::rule::illegal_code_point::{
+        CodePointClass, ERROR_CODE, IllegalCodePointRule, code_point_name,
+    };
+    use crate::rule::rule_result::CountCategory;
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    fn no_emoji() -> Box<IllegalCodePointRule> {
+        Box::new(IllegalCodePointRule::new(
+            CodePointClass::ALL.to_vec(),
+            true,
+        ))
+    }
+
+    #[test]
+    fn test_passwords() {
+        let rule = || Box::new(IllegalCodePointRule::default());
+        let test_cases = vec![
+            RulePasswordTestItem(rule(), password("p4zRcv8#n65"), vec![]),
+            RulePasswordTestItem(rule(), password("Ärger-Жук ½"), vec![]),
+            RulePasswordTestItem(rule(), password("bell\u{7}"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("next\u{85}line"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("zero\u{200b}width"), vec![ERROR_CODE]),
+            RulePasswordTestItem(
+                rule(),
+                password("\u{feff}bom\u{ad}"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(rule(), password("abc\u{202e}fed"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("private\u{e000}"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("unassigned\u{378}"), vec![ERROR_CODE]),
+            RulePasswordTestItem(
+                rule(),
+                password("sentinel\u{ffff}\u{fdd0}"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            // each code point is reported once
+            RulePasswordTestItem(rule(), password("a\u{200b}b\u{200b}c"), vec![ERROR_CODE]),
+            RulePasswordTestItem(
+                Box::new(IllegalCodePointRule::new(
+                    CodePointClass::ALL.to_vec(),
+                    false,
+                )),
+                password("\u{7}\u{200b}\u{202e}"),
+                vec![ERROR_CODE],
+            ),
+            // emoji sequences are allowed by default, including their joiners and selectors
+            RulePasswordTestItem(rule(), password("crab🦀 dev👩‍💻 run🏃‍♀️ love❤️ 👍🏽"), vec![]),
+            RulePasswordTestItem(no_emoji(), password("crab🦀"), vec![ERROR_CODE]),
+            RulePasswordTestItem(no_emoji(), password("dev👩‍💻"), vec![ERROR_CODE, ERROR_CODE]),
+            RulePasswordTestItem(no_emoji(), password("love❤️"), vec![ERROR_CODE]),
+            // text style symbols and keycap digits are no emoji
+            RulePasswordTestItem(no_emoji(), password("©♀ 1\u{fe0f}\u{20e3}"), vec![]),
+            // a joiner outside of an emoji sequence is invisible
+            RulePasswordTestItem(rule(), password("a\u{200d}b"), vec![ERROR_CODE]),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(IllegalCodePointRule::default()),
+                password("abc\u{202e}fed"),
+                vec!["ILLEGAL_CODE_POINT.bidi,U+202E,RIGHT-TO-LEFT OVERRIDE"],
+            ),
+            RulePasswordTestItem(
+                Box::new(IllegalCodePointRule::default()),
+                password("bell\u{7}"),
+                vec!["ILLEGAL_CODE_POINT.control,U+0007,<control-0007>"],
+            ),
+            RulePasswordTestItem(
+                no_emoji(),
+                password("crab🦀"),
+                vec!["ILLEGAL_CODE_POINT.emoji,U+1F980,CRAB"],
+            ),
+        ];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_classes() {
+        assert_eq!(
+            Some(CodePointClass::Invisible),
+            CodePointClass::of('\u{3164}')
+        );
+        assert_eq!(
+            Some(CodePointClass::Noncharacter),
+            CodePointClass::of('\u{10ffff}')
+        );
+        assert_eq!(None, CodePointClass::of('a'));
+        assert_eq!("ZERO WIDTH SPACE", code_point_name('\u{200b}'));
+        assert_eq!("<private-use-E000>", code_point_name('\u{e000}'));
+        assert_eq!("<reserved-0378>", code_point_name('\u{378}'));
+        assert_eq!("<noncharacter-FFFF>", code_point_name('\u{ffff}'));
+
+        let result = IllegalCodePointRule::default().validate(&password("a\u{200b}b\u{200b}c"));
+        assert_eq!(2, result.metadata().get_count(CountCategory::Illegal));
+    }
+}
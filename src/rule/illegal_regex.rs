@@ -1,10 +1,11 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use fancy_regex::Regex;
 use std::collections::{HashMap, HashSet};
 
-const ERROR_CODE: &str = "ILLEGAL_MATCH";
-const REGEX_ERROR: &str = "REGEX_ERROR";
+const ERROR_CODE: &str = ErrorCode::IllegalMatch.as_str();
+const REGEX_ERROR: &str = ErrorCode::RegexError.as_str();
 
 /// Rule for determining if a password matches an illegal regular expression. Passwords which match the expression will
 /// fail validation.
@@ -0,0 +1,422 @@
+use crate::rule::character_sequence::CharacterSequence;
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::sequence_data::{EnglishSequenceData, GermanSequenceData, SequenceData};
+use crate::rule::{PasswordData, Rule};
+use std::collections::{HashMap, HashSet};
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalKeyboardWalk.as_str();
+pub const DEFAULT_MIN_LENGTH: usize = 4;
+pub const DEFAULT_MAX_TURNS: usize = 2;
+
+/// Keyboard layouts with a known physical geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// The rows of [EnglishSequenceData::USQwerty].
+    USQwerty,
+    /// The rows of [GermanSequenceData::DEQwertz].
+    DEQwertz,
+}
+
+impl KeyboardLayout {
+    /// Horizontal position of the first key of each row, in key widths from the left edge of the number row.
+    fn row_offsets(&self) -> &'static [f32] {
+        match self {
+            KeyboardLayout::USQwerty => &[0.0, 1.5, 1.75, 2.25],
+            // the ISO layout has a short left shift key followed by '<'
+            KeyboardLayout::DEQwertz => &[0.0, 1.5, 1.75, 1.25],
+        }
+    }
+
+    pub fn graph(&self) -> KeyboardGraph {
+        let sequences = match self {
+            KeyboardLayout::USQwerty => EnglishSequenceData::USQwerty.get_sequences(),
+            KeyboardLayout::DEQwertz => GermanSequenceData::DEQwertz.get_sequences(),
+        };
+        KeyboardGraph::new(&sequences, self.row_offsets()).unwrap()
+    }
+}
+
+/// Direction of a step between two adjacent keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Key {
+    row: usize,
+    position: f32,
+}
+
+/// A run of adjacent keys found in a password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardWalk {
+    /// Index of the first character of the walk in the password.
+    pub start: usize,
+    pub sequence: String,
+    /// Number of keys in the walk.
+    pub length: usize,
+    /// Number of direction changes in the walk.
+    pub turns: usize,
+}
+
+/// Adjacency graph of the keys of a staggered keyboard. Keys are adjacent when they are next to each other in a row,
+/// or when they are in neighbouring rows and overlap horizontally. All forms of a key, e.g. its shifted character,
+/// map to the same key.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::keyboard_walk::{Direction, KeyboardLayout};
+///
+///  let graph = KeyboardLayout::USQwerty.graph();
+///  assert_eq!(Some(Direction::DownRight), graph.direction('1', 'q'));
+///  assert_eq!(Some(Direction::UpLeft), graph.direction('Z', 'a'));
+///  assert_eq!(None, graph.direction('q', 'p'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyboardGraph {
+    keys: HashMap<char, Key>,
+}
+
+impl KeyboardGraph {
+    /// Creates a graph from one sequence per keyboard row, top row first. Only the unshifted and shifted forms of
+    /// each sequence are used. `row_offsets` contains the horizontal position of the first key of each row in key
+    /// widths.
+    pub fn new(rows: &[CharacterSequence], row_offsets: &[f32]) -> Result<Self, String> {
+        if rows.len() != row_offsets.len() {
+            return Err(format!(
+                "expected {} row offsets but got {}",
+                rows.len(),
+                row_offsets.len()
+            ));
+        }
+        let mut keys = HashMap::new();
+        // map all unshifted characters first, so that a shifted character never replaces a key of its own
+        for form in 0..2 {
+            for (row, (sequence, offset)) in rows.iter().zip(row_offsets).enumerate() {
+                let Some(chars) = sequence.get_forms().get(form) else {
+                    continue;
+                };
+                for (column, c) in chars.chars().enumerate() {
+                    if c != '\u{0}' {
+                        keys.entry(c).or_insert(Key {
+                            row,
+                            position: offset + column as f32,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(Self { keys })
+    }
+
+    /// Returns the direction of the step from one key to another, or `None` if the keys are not adjacent.
+    pub fn direction(&self, from: char, to: char) -> Option<Direction> {
+        let (from, to) = (self.keys.get(&from)?, self.keys.get(&to)?);
+        let dx = to.position - from.position;
+        if from.row == to.row {
+            return if dx == 1.0 {
+                Some(Direction::Right)
+            } else if dx == -1.0 {
+                Some(Direction::Left)
+            } else {
+                None
+            };
+        }
+        if dx.abs() >= 1.0 {
+            return None;
+        }
+        let direction = if to.row + 1 == from.row {
+            match dx {
+                dx if dx < 0.0 => Direction::UpLeft,
+                dx if dx > 0.0 => Direction::UpRight,
+                _ => Direction::Up,
+            }
+        } else if from.row + 1 == to.row {
+            match dx {
+                dx if dx < 0.0 => Direction::DownLeft,
+                dx if dx > 0.0 => Direction::DownRight,
+                _ => Direction::Down,
+            }
+        } else {
+            return None;
+        };
+        Some(direction)
+    }
+
+    /// Returns the number of distinct keys, counting all forms of a key once.
+    pub fn key_count(&self) -> usize {
+        self.distinct_keys().len()
+    }
+
+    /// Returns the average number of keys adjacent to a key.
+    pub fn average_degree(&self) -> f64 {
+        let keys = self.distinct_keys();
+        if keys.is_empty() {
+            return 0.0;
+        }
+        let degrees: usize = keys
+            .iter()
+            .map(|&from| keys.iter().filter(|&&to| self.direction(from, to).is_some()).count())
+            .sum();
+        degrees as f64 / keys.len() as f64
+    }
+
+    /// Returns one character for each distinct key.
+    fn distinct_keys(&self) -> Vec<char> {
+        let mut seen = HashSet::new();
+        self.keys
+            .iter()
+            .filter(|(_, key)| seen.insert((key.row, key.position.to_bits())))
+            .map(|(c, _)| *c)
+            .collect()
+    }
+
+    fn same_key(&self, a: char, b: char) -> bool {
+        match (self.keys.get(&a), self.keys.get(&b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Finds the longest walks of at least `min_length` keys with at most `max_turns` direction changes. A walk ends
+    /// when it steps back onto the key it just left. Walks contained in a previously found walk are not reported.
+    pub fn walks(&self, text: &str, min_length: usize, max_turns: usize) -> Vec<KeyboardWalk> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut walks = Vec::new();
+        let mut last_end = None;
+        for start in 0..chars.len() {
+            let mut end = start;
+            let mut direction = None;
+            let mut turns = 0;
+            while end + 1 < chars.len() {
+                let Some(next) = self.direction(chars[end], chars[end + 1]) else {
+                    break;
+                };
+                if end > start && self.same_key(chars[end - 1], chars[end + 1]) {
+                    break;
+                }
+                if direction.is_some_and(|d| d != next) {
+                    if turns == max_turns {
+                        break;
+                    }
+                    turns += 1;
+                }
+                direction = Some(next);
+                end += 1;
+            }
+            let length = end - start + 1;
+            if length >= min_length && last_end.is_none_or(|last| end > last) {
+                walks.push(KeyboardWalk {
+                    start,
+                    sequence: chars[start..=end].iter().collect(),
+                    length,
+                    turns,
+                });
+                last_end = Some(end);
+            }
+        }
+        walks
+    }
+}
+
+/// Password validation rule that prevents walks across adjacent keys of a keyboard, including diagonal steps and
+/// changes of direction, e.g. `1qaz2wsx` or `zaq12wsx`.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::keyboard_walk::{KeyboardLayout, KeyboardWalkRule};
+///  use passay_rs::rule::Rule;
+///
+///  let rule = KeyboardWalkRule::new(KeyboardLayout::USQwerty, 4, 2).unwrap();
+///  let result = rule.validate(&PasswordData::with_password("zaq12wsx".to_string()));
+///  assert!(!result.valid());
+///  let result = rule.validate(&PasswordData::with_password("Tr0ub4dor&3".to_string()));
+///  assert!(result.valid());
+/// ```
+pub struct KeyboardWalkRule {
+    graph: KeyboardGraph,
+    min_length: usize,
+    max_turns: usize,
+}
+
+impl KeyboardWalkRule {
+    pub fn new(
+        layout: KeyboardLayout,
+        min_length: usize,
+        max_turns: usize,
+    ) -> Result<Self, String> {
+        Self::with_graph(layout.graph(), min_length, max_turns)
+    }
+
+    pub fn with_graph(
+        graph: KeyboardGraph,
+        min_length: usize,
+        max_turns: usize,
+    ) -> Result<Self, String> {
+        if min_length < 3 {
+            return Err("minimum length must be at least 3".to_string());
+        }
+        Ok(Self {
+            graph,
+            min_length,
+            max_turns,
+        })
+    }
+
+    pub fn graph(&self) -> &KeyboardGraph {
+        &self.graph
+    }
+
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    pub fn max_turns(&self) -> usize {
+        self.max_turns
+    }
+}
+
+impl Default for KeyboardWalkRule {
+    fn default() -> Self {
+        Self::new(
+            KeyboardLayout::USQwerty,
+            DEFAULT_MIN_LENGTH,
+            DEFAULT_MAX_TURNS,
+        )
+        .unwrap()
+    }
+}
+
+impl Rule for KeyboardWalkRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        for walk in self.graph.walks(password_data.password(), self.min_length, self.max_turns) {
+            let mut map = HashMap::new();
+            map.insert("sequence".to_string(), walk.sequence);
+            map.insert("length".to_string(), walk.length.to_string());
+            map.insert("turns".to_string(), walk.turns.to_string());
+            result.add_error(ERROR_CODE, Some(map));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::Rule;
+    use crate::rule::keyboard_walk::{
+        Direction, ERROR_CODE, KeyboardLayout, KeyboardWalk, KeyboardWalkRule,
+    };
+    use crate::test::{RulePasswordTestItem, check_passwords};
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    fn rule(layout: KeyboardLayout, max_turns: usize) -> Box<KeyboardWalkRule> {
+        Box::new(KeyboardWalkRule::new(layout, 4, max_turns).unwrap())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let test_cases = vec![
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 2),
+                password("1qaz2wsx"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 2),
+                password("zaq12wsx"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 1),
+                password("zaq12wsx"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 0),
+                password("asdfghjkl;'"),
+                vec![ERROR_CODE],
+            ),
+            // shifted keys
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 2),
+                password("!QAZ@wsx"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            // 'y' is next to 'a' on a German keyboard only
+            RulePasswordTestItem(
+                rule(KeyboardLayout::DEQwertz, 2),
+                password("1qay2wsx"),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 2),
+                password("1qay2wsx"),
+                vec![ERROR_CODE],
+            ),
+            // stepping back and forth is not a walk
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 2),
+                password("werewolf"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                rule(KeyboardLayout::USQwerty, 2),
+                password("Tr0ub4dor&3"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(KeyboardWalkRule::default()),
+                password("p4ss-qwer-word"),
+                vec![ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_walks() {
+        let graph = KeyboardLayout::USQwerty.graph();
+        assert_eq!(Some(Direction::Right), graph.direction('a', 'S'));
+        assert_eq!(Some(Direction::DownLeft), graph.direction('w', 'a'));
+        assert_eq!(Some(Direction::UpRight), graph.direction('x', 'd'));
+        assert_eq!(None, graph.direction('a', 'a'));
+        assert_eq!(None, graph.direction('`', 'q'));
+        assert_eq!(
+            vec![KeyboardWalk {
+                start: 2,
+                sequence: "zaq12wsx".to_string(),
+                length: 8,
+                turns: 2,
+            }],
+            graph.walks("##zaq12wsx", 4, 2)
+        );
+
+        let result = rule(KeyboardLayout::USQwerty, 2).validate(&password("1qaz"));
+        let detail = format!("{}", result.details()[0]);
+        assert!(detail.contains("\"sequence\": \"1qaz\""), "{detail}");
+        assert!(detail.contains("\"length\": \"4\""), "{detail}");
+        assert!(detail.contains("\"turns\": \"0\""), "{detail}");
+
+        assert_eq!(
+            Some("minimum length must be at least 3".to_string()),
+            KeyboardWalkRule::new(KeyboardLayout::USQwerty, 2, 0).err()
+        );
+    }
+}
@@ -1,10 +1,12 @@
+use crate::rule::error_code::ErrorCode;
 use std::collections::HashMap;
 
 use crate::rule::rule_result::{CountCategory, RuleResult, RuleResultMetadata};
 use crate::rule::{PasswordData, Rule};
+use std::any::Any;
 
-pub const ERROR_CODE_MIN: &str = "TOO_SHORT";
-pub const ERROR_CODE_MAX: &str = "TOO_LONG";
+pub const ERROR_CODE_MIN: &str = ErrorCode::TooShort.as_str();
+pub const ERROR_CODE_MAX: &str = ErrorCode::TooLong.as_str();
 
 /// Rule for determining if a password is within a desired length. The minimum and maximum lengths are used inclusively
 /// to determine if a password meets this rule.
@@ -40,6 +42,14 @@ impl LengthRule {
         }
     }
 
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
     fn create_rule_result_detail_parameters(&self) -> HashMap<String, String> {
         let mut map = HashMap::new();
         map.insert("min_length".to_string(), self.min_length.to_string());
@@ -78,6 +88,9 @@ impl Rule for LengthRule {
         result.set_metadata(Self::create_rule_result_metadata(password_data));
         result
     }
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,11 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use std::collections::HashMap;
 use std::ops::Range;
 
-const ERROR_CODE: &str = "INSUFFICIENT_COMPLEXITY";
-const ERROR_CODE_RULES: &str = "INSUFFICIENT_COMPLEXITY_RULES";
+const ERROR_CODE: &str = ErrorCode::InsufficientComplexity.as_str();
+const ERROR_CODE_RULES: &str = ErrorCode::InsufficientComplexityRules.as_str();
 
 /// Rule for determining if a password contains the desired complexity for a certain length. In order to meet the
 /// criteria of this rule, passwords must meet all the supplied rules for a given password length.
@@ -537,8 +538,10 @@ mod tests {
             )),
             Box::new(RepeatCharacterRule::default()),
         ];
-        let mut rule = LengthComplexityRule::default();
-        rule.report_failure = false;
+        let mut rule = LengthComplexityRule {
+            report_failure: false,
+            ..Default::default()
+        };
         let _ = rule.add_rules(0..20, rules);
 
         let rules: Vec<Box<dyn Rule>> = vec![
@@ -0,0 +1,235 @@
+use crate::entropy::Entropy;
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::{RuleResult, RuleResultMetadata};
+use crate::rule::{PasswordData, Rule};
+use std::collections::HashMap;
+
+pub const ERROR_CODE: &str = ErrorCode::InsufficientEntropy.as_str();
+pub const ERROR_CODE_ESTIMATE_FAILED: &str = ErrorCode::EntropyEstimateFailed.as_str();
+
+/// Creates the entropy estimator for the password being validated.
+pub type EntropyFactory = Box<dyn Fn(&PasswordData) -> Result<Box<dyn Entropy>, String>>;
+
+/// Rule for determining if the estimated entropy of a password reaches a minimum number of bits. An estimator is
+/// created for each password by the factory, e.g. a [ShannonEntropy](crate::entropy::ShannonEntropy) or a
+/// [RandomPasswordEntropy](crate::entropy::RandomPasswordEntropy). The estimated bits are recorded in the metadata, see
+/// [RuleResultMetadata::entropy].
+///
+/// When the factory fails or the estimate is not a number, `ENTROPY_ESTIMATE_FAILED` is reported.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::entropy::{Entropy, ShannonEntropy};
+///  use passay_rs::rule::minimum_entropy::MinimumEntropyRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = MinimumEntropyRule::new(
+///      |password_data| Ok(Box::new(ShannonEntropy::new(false, password_data)) as Box<dyn Entropy>),
+///      20.0,
+///  )
+///  .unwrap();
+///  let result = rule.validate(&PasswordData::with_password("hello".to_string()));
+///  assert!(!result.valid());
+///  assert_eq!(Some(12.0), result.metadata().entropy());
+/// ```
+pub struct MinimumEntropyRule {
+    factory: EntropyFactory,
+    min_bits: f64,
+}
+
+impl MinimumEntropyRule {
+    pub fn new<F>(factory: F, min_bits: f64) -> Result<Self, String>
+    where
+        F: Fn(&PasswordData) -> Result<Box<dyn Entropy>, String> + 'static,
+    {
+        if !min_bits.is_finite() || min_bits < 0.0 {
+            return Err("minimum entropy bits must be a non-negative number".to_string());
+        }
+        Ok(Self {
+            factory: Box::new(factory),
+            min_bits,
+        })
+    }
+
+    pub fn min_bits(&self) -> f64 {
+        self.min_bits
+    }
+
+    fn create_rule_result_detail_parameters(&self, bits: f64) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(2);
+        map.insert("entropy".to_string(), format!("{bits:.2}"));
+        map.insert(
+            "minimumEntropy".to_string(),
+            format!("{:.2}", self.min_bits),
+        );
+        map
+    }
+}
+
+impl Rule for MinimumEntropyRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let estimate = (self.factory)(password_data).map(|estimator| estimator.estimate());
+        match estimate {
+            Ok(bits) if bits.is_nan() => {
+                let mut map = HashMap::new();
+                map.insert("error".to_string(), format!("estimated {bits} bits"));
+                result.add_error(ERROR_CODE_ESTIMATE_FAILED, Some(map));
+            }
+            Ok(bits) => {
+                if bits < self.min_bits {
+                    result.add_error(
+                        ERROR_CODE,
+                        Some(self.create_rule_result_detail_parameters(bits)),
+                    );
+                }
+                result.set_metadata(RuleResultMetadata::with_entropy(bits));
+            }
+            Err(e) => {
+                let mut map = HashMap::new();
+                map.insert("error".to_string(), e);
+                result.add_error(ERROR_CODE_ESTIMATE_FAILED, Some(map));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entropy::{Entropy, RandomPasswordEntropy, ShannonEntropy};
+    use crate::rule::allowed_character::AllowedCharacter;
+    use crate::rule::character::CharacterRule;
+    use crate::rule::character_data::EnglishCharacterData;
+    use crate::rule::length::LengthRule;
+    use crate::rule::length_complexity::LengthComplexityRule;
+    use crate::rule::minimum_entropy::{
+        ERROR_CODE, ERROR_CODE_ESTIMATE_FAILED, MinimumEntropyRule,
+    };
+    use crate::rule::password_validator::PasswordValidator;
+    use crate::rule::{PasswordData, Rule};
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    fn shannon(min_bits: f64) -> Box<MinimumEntropyRule> {
+        let rule = MinimumEntropyRule::new(
+            |password_data| {
+                Ok(Box::new(ShannonEntropy::new(false, password_data)) as Box<dyn Entropy>)
+            },
+            min_bits,
+        );
+        Box::new(rule.unwrap())
+    }
+
+    /// Estimates random passwords of lowercase letters and digits.
+    fn random(min_bits: f64) -> Box<MinimumEntropyRule> {
+        let rule = MinimumEntropyRule::new(
+            |password_data| {
+                let rules: Vec<Box<dyn Rule>> = vec![
+                    Box::new(CharacterRule::from_character_data(Box::new(
+                        EnglishCharacterData::LowerCase,
+                    ))),
+                    Box::new(CharacterRule::from_character_data(Box::new(
+                        EnglishCharacterData::Digit,
+                    ))),
+                ];
+                let entropy = RandomPasswordEntropy::new(&rules, password_data)?;
+                Ok(Box::new(entropy) as Box<dyn Entropy>)
+            },
+            min_bits,
+        );
+        Box::new(rule.unwrap())
+    }
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let failing = MinimumEntropyRule::new(|_| Err("no estimator".to_string()), 10.0).unwrap();
+        let test_cases = vec![
+            RulePasswordTestItem(shannon(20.0), password("hello"), vec![ERROR_CODE]),
+            RulePasswordTestItem(shannon(20.0), password("correcthorse"), vec![]),
+            RulePasswordTestItem(random(50.0), password("p4zrcv8n"), vec![ERROR_CODE]),
+            RulePasswordTestItem(random(50.0), password("p4zrcv8n65"), vec![]),
+            RulePasswordTestItem(
+                Box::new(failing),
+                password("p4zrcv8n65"),
+                vec![ERROR_CODE_ESTIMATE_FAILED],
+            ),
+        ];
+        check_passwords(test_cases);
+        assert!(MinimumEntropyRule::new(|_| Err(String::new()), -1.0).is_err());
+        assert!(MinimumEntropyRule::new(|_| Err(String::new()), f64::NAN).is_err());
+    }
+
+    struct FixedEntropy(f64);
+
+    impl Entropy for FixedEntropy {
+        fn estimate(&self) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_non_finite_estimate() {
+        let rule = |bits: f64| {
+            MinimumEntropyRule::new(
+                move |_| Ok(Box::new(FixedEntropy(bits)) as Box<dyn Entropy>),
+                10.0,
+            )
+            .unwrap()
+        };
+        let result = rule(f64::NAN).validate(&password("p4zrcv8n65"));
+        assert!(!result.valid());
+        assert_eq!(ERROR_CODE_ESTIMATE_FAILED, result.details()[0].error_code());
+        assert_eq!(None, result.metadata().entropy());
+
+        let result = rule(f64::INFINITY).validate(&password("p4zrcv8n65"));
+        assert!(result.valid());
+        assert_eq!(Some(f64::INFINITY), result.metadata().entropy());
+    }
+
+    #[test]
+    fn test_long_random_password() {
+        let long = "p4zrcv8n65".repeat(50);
+        let result = random(80.0).validate(&password(&long));
+        assert!(result.valid());
+        assert_eq!(Some(500.0 * 36.0f64.log2()), result.metadata().entropy());
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases = vec![RulePasswordTestItem(
+            shannon(20.0),
+            password("hello"),
+            vec!["INSUFFICIENT_ENTROPY,\"entropy\": \"12.00\",\"minimumEntropy\": \"20.00\""],
+        )];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let validator =
+            PasswordValidator::new(vec![Box::new(LengthRule::new(8, 64)), shannon(20.0)]);
+        let result = validator.validate(&password("correcthorse"));
+        assert!(result.valid());
+        assert_eq!(Some(24.0), result.metadata().entropy());
+
+        let mut rule = LengthComplexityRule::default();
+        rule.add_rules(0..12, vec![shannon(30.0)]).unwrap();
+        rule.add_rules(
+            12..64,
+            vec![Box::new(AllowedCharacter::from_chars("abcdefghijklmnopqrstuvwxyz"))],
+        )
+        .unwrap();
+        let result = rule.validate(&password("hello"));
+        assert!(!result.valid());
+        assert_eq!(Some(12.0), result.metadata().entropy());
+        let result = rule.validate(&password("correcthorse"));
+        assert!(result.valid());
+        assert_eq!(None, result.metadata().entropy());
+    }
+}
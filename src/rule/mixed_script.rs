@@ -0,0 +1,235 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use std::collections::HashMap;
+pub use unicode_script::Script;
+use unicode_script::UnicodeScript;
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalMixedScript.as_str();
+pub const ERROR_CODE_CONFUSABLE: &str = ErrorCode::IllegalConfusable.as_str();
+pub const DEFAULT_MAX_SCRIPTS: usize = 1;
+
+/// A character of a password that looks like a character of the main script of the password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Confusable {
+    /// Index of the character in the password, counted in characters.
+    pub position: usize,
+    pub character: char,
+    pub script: Script,
+    /// The Unicode confusable skeleton of the character, e.g. `a` for the Cyrillic `а`.
+    pub skeleton: String,
+}
+
+/// Rule for determining if a password mixes Unicode scripts, e.g. Latin letters with Cyrillic look-alikes as in
+/// `pаssword`. Characters shared by all scripts, such as digits, punctuation and combining marks, belong to no script.
+///
+/// The password fails if it contains more than `max_scripts` scripts. With confusable detection, each character that
+/// is not of the main script of the password, i.e. the script of most of its characters, fails if its Unicode
+/// confusable skeleton consists of characters of the main script. Passwords written entirely in one script, like
+/// those of [CyrillicCharacterData](crate::rule::character_data::CyrillicCharacterData), are never confusable.
+///
+/// Scripts are counted individually, so Japanese text mixing Han, Hiragana and Katakana needs a maximum of 3.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::mixed_script::MixedScriptRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = MixedScriptRule::default();
+///  let result = rule.validate(&PasswordData::with_password("p\u{430}ssword".to_string()));
+///  assert!(!result.valid());
+///  let result = rule.validate(&PasswordData::with_password("пароль-2024".to_string()));
+///  assert!(result.valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MixedScriptRule {
+    max_scripts: usize,
+    detect_confusables: bool,
+}
+
+impl MixedScriptRule {
+    pub fn new(max_scripts: usize, detect_confusables: bool) -> Result<Self, String> {
+        if max_scripts == 0 {
+            return Err("maximum scripts must be greater than zero".to_string());
+        }
+        Ok(Self {
+            max_scripts,
+            detect_confusables,
+        })
+    }
+
+    pub fn max_scripts(&self) -> usize {
+        self.max_scripts
+    }
+
+    pub fn detect_confusables(&self) -> bool {
+        self.detect_confusables
+    }
+
+    /// Returns the scripts of the text in order of their first appearance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use passay_rs::rule::mixed_script::{MixedScriptRule, Script};
+    ///
+    ///  let scripts = MixedScriptRule::scripts("Мир peace 2024");
+    ///  assert_eq!(vec![Script::Cyrillic, Script::Latin], scripts);
+    /// ```
+    pub fn scripts(text: &str) -> Vec<Script> {
+        let mut scripts = vec![];
+        for script in text.chars().filter_map(script_of) {
+            if !scripts.contains(&script) {
+                scripts.push(script);
+            }
+        }
+        scripts
+    }
+
+    /// Returns the characters of the text that resemble characters of its main script.
+    pub fn confusables(text: &str) -> Vec<Confusable> {
+        let Some(main) = main_script(text) else {
+            return vec![];
+        };
+        text.chars()
+            .enumerate()
+            .filter_map(|(position, character)| {
+                let script = script_of(character).filter(|s| *s != main)?;
+                let skeleton: String = unicode_security::skeleton(&character.to_string()).collect();
+                let scripts: Vec<Option<Script>> = skeleton.chars().map(script_of).collect();
+                let resembles_main = scripts.contains(&Some(main))
+                    && scripts.iter().all(|s| s.is_none_or(|s| s == main));
+                resembles_main.then_some(Confusable {
+                    position,
+                    character,
+                    script,
+                    skeleton,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for MixedScriptRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SCRIPTS, true).unwrap()
+    }
+}
+
+/// Returns the script of the character, or `None` for characters used by all scripts.
+fn script_of(c: char) -> Option<Script> {
+    match c.script() {
+        Script::Common | Script::Inherited | Script::Unknown => None,
+        script => Some(script),
+    }
+}
+
+/// Returns the script of most characters of the text, the first one on a tie.
+fn main_script(text: &str) -> Option<Script> {
+    let scripts = MixedScriptRule::scripts(text);
+    let count = |s: &Script| text.chars().filter(|c| script_of(*c) == Some(*s)).count();
+    scripts.into_iter().rev().max_by_key(count)
+}
+
+impl Rule for MixedScriptRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let password = password_data.password();
+        let scripts = Self::scripts(password);
+        if scripts.len() > self.max_scripts {
+            let names: Vec<&str> = scripts.iter().map(|s| s.full_name()).collect();
+            let mut map = HashMap::with_capacity(3);
+            map.insert("scripts".to_string(), names.join(", "));
+            map.insert("count".to_string(), scripts.len().to_string());
+            map.insert("maximumScripts".to_string(), self.max_scripts.to_string());
+            result.add_error(ERROR_CODE, Some(map));
+        }
+        if self.detect_confusables {
+            for confusable in Self::confusables(password) {
+                let mut map = HashMap::with_capacity(3);
+                map.insert("character".to_string(), confusable.character.to_string());
+                map.insert(
+                    "script".to_string(),
+                    confusable.script.full_name().to_string(),
+                );
+                map.insert("skeleton".to_string(), confusable.skeleton);
+                result.add_error(ERROR_CODE_CONFUSABLE, Some(map));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::mixed_script::{
+        Confusable, ERROR_CODE, ERROR_CODE_CONFUSABLE, MixedScriptRule, Script,
+    };
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let rule = || Box::new(MixedScriptRule::default());
+        let scripts_only = || Box::new(MixedScriptRule::new(1, false).unwrap());
+        let three_scripts = || Box::new(MixedScriptRule::new(3, true).unwrap());
+        let test_cases = vec![
+            RulePasswordTestItem(rule(), password("p4zRcv8#n65"), vec![]),
+            RulePasswordTestItem(rule(), password("пароль-2024"), vec![]),
+            RulePasswordTestItem(rule(), password("\u{e9}clair!"), vec![]),
+            RulePasswordTestItem(
+                rule(),
+                password("p\u{430}ssw\u{43e}rd"),
+                vec![ERROR_CODE, ERROR_CODE_CONFUSABLE, ERROR_CODE_CONFUSABLE],
+            ),
+            RulePasswordTestItem(scripts_only(), password("p\u{430}ssword"), vec![ERROR_CODE]),
+            RulePasswordTestItem(scripts_only(), password("\u{3a9}mega"), vec![ERROR_CODE]),
+            RulePasswordTestItem(three_scripts(), password("日本語ひらがなカタカナ"), vec![]),
+            RulePasswordTestItem(
+                three_scripts(),
+                password("p\u{3b1}ssword"),
+                vec![ERROR_CODE_CONFUSABLE],
+            ),
+        ];
+        check_passwords(test_cases);
+        assert!(MixedScriptRule::new(0, true).is_err());
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases = vec![RulePasswordTestItem(
+            Box::new(MixedScriptRule::default()),
+            password("payp\u{430}l"),
+            vec![
+                "ILLEGAL_MIXED_SCRIPT,\"Latin, Cyrillic\",\"count\": \"2\"",
+                "ILLEGAL_CONFUSABLE,\"\u{430}\",\"Cyrillic\",\"skeleton\": \"a\"",
+            ],
+        )];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_scripts() {
+        assert_eq!(
+            vec![Script::Cyrillic, Script::Latin],
+            MixedScriptRule::scripts("Мир peace 2024")
+        );
+        assert!(MixedScriptRule::scripts("1234!?").is_empty());
+        assert_eq!(
+            vec![Confusable {
+                position: 5,
+                character: '\u{406}',
+                script: Script::Cyrillic,
+                skeleton: "l".to_string(),
+            }],
+            MixedScriptRule::confusables("paypa\u{406}")
+        );
+        assert!(MixedScriptRule::confusables("\u{3a9}mega").is_empty());
+    }
+}
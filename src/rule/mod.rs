@@ -1,35 +1,52 @@
 use crate::dictionary::Dictionary;
+use crate::rule::character_data::CharacterData;
+use crate::rule::date_pattern::Date;
 use crate::rule::reference::Reference;
 use crate::rule::rule_result::RuleResult;
+use std::any::Any;
 
 pub mod allowed_character;
 pub mod allowed_regex;
+pub mod breached_password;
 pub mod character;
 pub mod character_characteristics;
 pub mod character_data;
 pub mod character_occurrences;
-mod character_sequence;
+pub mod character_pattern;
+pub mod character_sequence;
+pub mod context_words;
+pub mod date_pattern;
 pub mod dictionary;
 pub mod dictionary_substring;
 mod digest_dictionary;
 pub mod digest_history;
 pub mod digest_source;
+pub mod error_code;
 pub mod history;
 pub mod illegal_character;
+pub mod illegal_code_point;
 pub mod illegal_regex;
 pub mod illegal_sequence;
+pub mod keyboard_walk;
 pub mod length;
 pub mod length_complexity;
 pub mod message_resolver;
+pub mod minimum_entropy;
+pub mod mixed_script;
 pub mod number_range;
+pub mod password_age;
+pub mod password_similarity;
 mod password_utils;
 pub mod password_validator;
 pub mod reference;
 pub mod repeat_character;
 pub mod repeat_characters;
+pub mod repeated_substring;
 pub mod rule_result;
 pub mod sequence_data;
 pub mod source;
+pub mod transforming_dictionary;
+pub mod unique_characters;
 pub mod username;
 pub mod whitespace;
 
@@ -41,10 +58,20 @@ pub trait Rule {
     fn as_dictionary_rule(&self) -> Option<&dyn DictionaryRuleTrait> {
         None
     }
+    /// Allows inspecting the concrete rule, e.g. to export its settings. Rules opt in by returning themselves.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 pub trait HasCharacters: Rule {
     fn characters(&self) -> String;
+
+    /// Returns the character data defined by a [category](CharacterData::category), whose characters are left out of
+    /// [characters](Self::characters).
+    fn categories(&self) -> Vec<&dyn CharacterData> {
+        Vec::new()
+    }
 }
 
 pub trait DictionaryRuleTrait: Rule {
@@ -57,6 +84,8 @@ pub struct PasswordData {
     password: String,
     username: Option<String>,
     password_references: Vec<Box<dyn Reference>>,
+    birthdate: Option<Date>,
+    current_password: Option<String>,
 }
 
 impl PasswordData {
@@ -65,6 +94,8 @@ impl PasswordData {
             password,
             username: None,
             password_references: Vec::new(),
+            birthdate: None,
+            current_password: None,
         }
     }
     pub fn with_password_and_user(password: String, username: Option<String>) -> Self {
@@ -72,6 +103,8 @@ impl PasswordData {
             password,
             username,
             password_references: Vec::new(),
+            birthdate: None,
+            current_password: None,
         }
     }
     pub fn new(
@@ -83,6 +116,8 @@ impl PasswordData {
             password,
             username,
             password_references,
+            birthdate: None,
+            current_password: None,
         }
     }
 
@@ -97,4 +132,21 @@ impl PasswordData {
     pub fn username(&self) -> Option<&str> {
         self.username.as_deref()
     }
+
+    pub fn birthdate(&self) -> Option<&Date> {
+        self.birthdate.as_ref()
+    }
+
+    pub fn set_birthdate(&mut self, birthdate: Option<Date>) {
+        self.birthdate = birthdate;
+    }
+
+    /// The password the user is replacing, if known.
+    pub fn current_password(&self) -> Option<&str> {
+        self.current_password.as_deref()
+    }
+
+    pub fn set_current_password(&mut self, current_password: Option<String>) {
+        self.current_password = current_password;
+    }
 }
@@ -1,11 +1,12 @@
 use crate::rule::allowed_character::MatchBehavior;
 use crate::rule::allowed_character::MatchBehavior::Contains;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use std::collections::HashMap;
 use std::ops::Range;
 
-pub const ERROR_CODE: &str = "ILLEGAL_NUMBER_RANGE";
+pub const ERROR_CODE: &str = ErrorCode::IllegalNumberRange.as_str();
 
 /// Rule for determining if a password contains any number within a defined range.
 /// # Example
@@ -0,0 +1,127 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::history::most_recent_references;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+pub const ERROR_CODE: &str = ErrorCode::PasswordTooRecent.as_str();
+
+/// Rule for determining if enough time has passed since the password was last changed. The age is measured from the
+/// creation time of the most recent [HistoricalReference](crate::rule::history::HistoricalReference), so users can not
+/// cycle through passwords to get around a [HistoryRule](crate::rule::history::HistoryRule) with a history depth.
+/// If no reference has a creation time, then passwords will meet this rule.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::history::HistoricalReference;
+///  use passay_rs::rule::password_age::MinimumPasswordAgeRule;
+///  use passay_rs::rule::reference::Reference;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///  use std::time::{Duration, SystemTime};
+///
+///  let rule = MinimumPasswordAgeRule::new(Duration::from_secs(24 * 60 * 60));
+///  let mut reference = HistoricalReference::with_password("Winter2024!".to_string());
+///  reference.set_created(Some(SystemTime::now() - Duration::from_secs(60 * 60)));
+///  let history: Vec<Box<dyn Reference>> = vec![Box::new(reference)];
+///  let password = PasswordData::new("Spring2025!".to_string(), None, history);
+///  assert!(!rule.validate(&password).valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinimumPasswordAgeRule {
+    min_age: Duration,
+}
+
+impl MinimumPasswordAgeRule {
+    pub fn new(min_age: Duration) -> Self {
+        Self { min_age }
+    }
+
+    pub fn min_age(&self) -> Duration {
+        self.min_age
+    }
+
+    fn create_rule_result_detail_parameters(&self, age: Duration) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(2);
+        map.insert("minimumAge".to_string(), self.min_age.as_secs().to_string());
+        map.insert("age".to_string(), age.as_secs().to_string());
+        map
+    }
+}
+
+impl Rule for MinimumPasswordAgeRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let newest = most_recent_references(password_data).into_iter().find_map(|rf| rf.created());
+        if let Some(created) = newest {
+            // a creation time in the future counts as just created
+            let age = SystemTime::now().duration_since(created).unwrap_or_default();
+            if age < self.min_age {
+                result.add_error(
+                    ERROR_CODE,
+                    Some(self.create_rule_result_detail_parameters(age)),
+                );
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::history::HistoricalReference;
+    use crate::rule::password_age::{ERROR_CODE, MinimumPasswordAgeRule};
+    use crate::rule::reference::Reference;
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+    use std::time::{Duration, SystemTime};
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn rule() -> Box<MinimumPasswordAgeRule> {
+        Box::new(MinimumPasswordAgeRule::new(Duration::from_secs(DAY)))
+    }
+
+    /// Creates password data with references changed the given number of seconds ago.
+    fn password(ages: &[Option<u64>]) -> PasswordData {
+        let history: Vec<Box<dyn Reference>> = ages
+            .iter()
+            .map(|age| {
+                let mut rf = HistoricalReference::with_password("Winter2024!".to_string());
+                rf.set_created(age.map(|age| SystemTime::now() - Duration::from_secs(age)));
+                Box::new(rf) as Box<dyn Reference>
+            })
+            .collect();
+        PasswordData::new("Spring2025!".to_string(), None, history)
+    }
+
+    #[test]
+    fn test_passwords() {
+        let test_cases = vec![
+            RulePasswordTestItem(rule(), password(&[]), vec![]),
+            RulePasswordTestItem(rule(), password(&[None, None]), vec![]),
+            RulePasswordTestItem(rule(), password(&[Some(2 * DAY)]), vec![]),
+            RulePasswordTestItem(rule(), password(&[Some(60)]), vec![ERROR_CODE]),
+            // the most recent reference counts, regardless of its position
+            RulePasswordTestItem(
+                rule(),
+                password(&[Some(60), Some(3 * DAY), None]),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(rule(), password(&[Some(3 * DAY), Some(2 * DAY)]), vec![]),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases = vec![RulePasswordTestItem(
+            rule(),
+            password(&[Some(2 * DAY), Some(DAY / 2)]),
+            vec!["PASSWORD_TOO_RECENT,\"minimumAge\": \"86400\",\"age\": \"432"],
+        )];
+        check_messages(test_cases);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::history::HistoricalReference;
+use crate::rule::reference::Reference;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+pub const ERROR_CODE: &str = ErrorCode::SimilarityViolation.as_str();
+pub const DEFAULT_MIN_DISTANCE: usize = 3;
+pub const DEFAULT_MAX_SIMILARITY: f64 = 0.8;
+/// Label reported for the current password of [PasswordData::current_password].
+pub const CURRENT_LABEL: &str = "current";
+/// Label reported for historical references without a label.
+pub const HISTORY_LABEL: &str = "history";
+
+/// Edit distances between two passwords, counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    /// Insertions, deletions and substitutions.
+    Levenshtein,
+    /// Insertions, deletions, substitutions and transpositions of adjacent characters, e.g. `2024` to `2042`.
+    Damerau,
+}
+
+impl DistanceMetric {
+    pub fn distance(&self, a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        match self {
+            DistanceMetric::Levenshtein => levenshtein(&a, &b),
+            DistanceMetric::Damerau => damerau(&a, &b),
+        }
+    }
+
+    /// Returns the similarity of two strings between 0 and 1, where 1 means equal.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        similarity(self.distance(a, b), a, b)
+    }
+}
+
+impl Display for DistanceMetric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DistanceMetric::Levenshtein => write!(f, "Levenshtein"),
+            DistanceMetric::Damerau => write!(f, "Damerau"),
+        }
+    }
+}
+
+fn similarity(distance: usize, a: &str, b: &str) -> f64 {
+    let length = a.chars().count().max(b.chars().count());
+    if length == 0 {
+        return 1.0;
+    }
+    1.0 - distance as f64 / length as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(ca != cb))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Optimal string alignment distance, transposed characters are not edited again.
+fn damerau(a: &[char], b: &[char]) -> usize {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j - 1] + cost).min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Rule for determining if a password is too similar to a previous password of the user. The password is compared
+/// with the cleartext [HistoricalReference]s and the current password of the [PasswordData], references with a salt
+/// or created by [HistoricalReference::with_digest] are skipped. All other references are treated as cleartext, so
+/// digests have to be created with [HistoricalReference::with_digest]. It fails when the edit distance to one of them
+/// is below the minimum distance, or the similarity is above the maximum similarity.
+///
+/// The similarity is `1 - distance / length` of the longer password. A minimum distance of 0 or a maximum similarity
+/// of 1 disables the respective check.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::history::HistoricalReference;
+///  use passay_rs::rule::password_similarity::PasswordSimilarityRule;
+///  use passay_rs::rule::reference::Reference;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = PasswordSimilarityRule::default();
+///  let history: Vec<Box<dyn Reference>> = vec![Box::new(HistoricalReference::with_password_label(
+///      "Winter2024!".to_string(),
+///      "2024-01".to_string(),
+///  ))];
+///  let password = PasswordData::new("Winter2025!".to_string(), None, history);
+///  assert!(!rule.validate(&password).valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PasswordSimilarityRule {
+    metric: DistanceMetric,
+    min_distance: usize,
+    max_similarity: f64,
+    report_all: bool,
+}
+
+impl PasswordSimilarityRule {
+    pub fn new(
+        metric: DistanceMetric,
+        min_distance: usize,
+        max_similarity: f64,
+        report_all: bool,
+    ) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&max_similarity) {
+            return Err("maximum similarity must be between 0 and 1".to_string());
+        }
+        Ok(Self {
+            metric,
+            min_distance,
+            max_similarity,
+            report_all,
+        })
+    }
+
+    pub fn with_min_distance(metric: DistanceMetric, min_distance: usize) -> Self {
+        Self::new(metric, min_distance, 1.0, true).unwrap()
+    }
+
+    pub fn with_max_similarity(
+        metric: DistanceMetric,
+        max_similarity: f64,
+    ) -> Result<Self, String> {
+        Self::new(metric, 0, max_similarity, true)
+    }
+
+    pub fn metric(&self) -> DistanceMetric {
+        self.metric
+    }
+
+    pub fn min_distance(&self) -> usize {
+        self.min_distance
+    }
+
+    pub fn max_similarity(&self) -> f64 {
+        self.max_similarity
+    }
+
+    fn create_rule_result_detail_parameters(
+        &self,
+        label: &str,
+        distance: usize,
+        similarity: f64,
+    ) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(6);
+        map.insert("label".to_string(), label.to_string());
+        map.insert("distance".to_string(), distance.to_string());
+        map.insert("similarity".to_string(), format!("{similarity:.2}"));
+        map.insert("minimumDistance".to_string(), self.min_distance.to_string());
+        map.insert(
+            "maximumSimilarity".to_string(),
+            format!("{:.2}", self.max_similarity),
+        );
+        map.insert("metric".to_string(), self.metric.to_string());
+        map
+    }
+}
+
+impl Default for PasswordSimilarityRule {
+    fn default() -> Self {
+        Self::new(
+            DistanceMetric::Damerau,
+            DEFAULT_MIN_DISTANCE,
+            DEFAULT_MAX_SIMILARITY,
+            true,
+        )
+        .unwrap()
+    }
+}
+
+impl Rule for PasswordSimilarityRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let history = password_data
+            .password_references()
+            .iter()
+            .filter_map(|rf| rf.as_any().downcast_ref::<HistoricalReference>())
+            // the distance to a salted or digested password says nothing about the password itself
+            .filter(|rf| rf.salt().is_none() && !rf.is_digest())
+            .map(|rf| (rf.label().unwrap_or(HISTORY_LABEL), rf.password()));
+        let current = password_data.current_password().map(|p| (CURRENT_LABEL, p));
+        for (label, previous) in current.into_iter().chain(history) {
+            let distance = self.metric.distance(password_data.password(), previous);
+            let similarity = similarity(distance, password_data.password(), previous);
+            if distance < self.min_distance || similarity > self.max_similarity {
+                result.add_error(
+                    ERROR_CODE,
+                    Some(self.create_rule_result_detail_parameters(label, distance, similarity)),
+                );
+                if !self.report_all {
+                    break;
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::history::HistoricalReference;
+    use crate::rule::password_similarity::{DistanceMetric, ERROR_CODE, PasswordSimilarityRule};
+    use crate::rule::reference::{Reference, Salt};
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    fn password(password: &str, current: Option<&str>) -> PasswordData {
+        let history: Vec<Box<dyn Reference>> = vec![
+            Box::new(HistoricalReference::with_password_label(
+                "Winter2024!".to_string(),
+                "2024-01".to_string(),
+            )),
+            Box::new(HistoricalReference::with_password(
+                "Spring2024!".to_string(),
+            )),
+        ];
+        let mut data = PasswordData::new(password.to_string(), None, history);
+        data.set_current_password(current.map(str::to_string));
+        data
+    }
+
+    #[test]
+    fn test_passwords() {
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::default()),
+                password("Winter2025!", None),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::default()),
+                password("Tr0ub4dor&3", None),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::default()),
+                password("Winter2024", Some("Winter2024?")),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(
+                    PasswordSimilarityRule::new(DistanceMetric::Damerau, 3, 1.0, false).unwrap(),
+                ),
+                password("Winter2024", Some("Winter2024?")),
+                vec![ERROR_CODE],
+            ),
+            // a transposition is one edit for Damerau but two for Levenshtein
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::with_min_distance(
+                    DistanceMetric::Damerau,
+                    2,
+                )),
+                password("Winter2042!", None),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::with_min_distance(
+                    DistanceMetric::Levenshtein,
+                    2,
+                )),
+                password("Winter2042!", None),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(
+                    PasswordSimilarityRule::with_max_similarity(DistanceMetric::Levenshtein, 0.5)
+                        .unwrap(),
+                ),
+                password("Winter-Sun", None),
+                vec![ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+        assert!(PasswordSimilarityRule::with_max_similarity(DistanceMetric::Damerau, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_protected_history() {
+        let history: Vec<Box<dyn Reference>> = vec![
+            Box::new(HistoricalReference::with_digest(
+                "Winter2024!".to_string(),
+                None,
+                None,
+            )),
+            Box::new(HistoricalReference::new(
+                "Spring2024!".to_string(),
+                None,
+                Some(Salt::prefix("xyz".to_string())),
+            )),
+        ];
+        let test_cases = vec![RulePasswordTestItem(
+            Box::new(PasswordSimilarityRule::default()),
+            PasswordData::new("Winter2024?".to_string(), None, history),
+            vec![],
+        )];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases = vec![
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::default()),
+                password("Winter2025!", None),
+                vec!["SIMILARITY_VIOLATION,\"2024-01\",\"distance\": \"1\",\"0.91\""],
+            ),
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::default()),
+                password("Spring2025", Some("correct horse")),
+                vec!["SIMILARITY_VIOLATION,\"history\",\"distance\": \"2\""],
+            ),
+            RulePasswordTestItem(
+                Box::new(PasswordSimilarityRule::default()),
+                password("correct horse!", Some("correct horse")),
+                vec!["SIMILARITY_VIOLATION,\"current\""],
+            ),
+        ];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_distance() {
+        let cases = [
+            ("", "", 0, 0),
+            ("abc", "", 3, 3),
+            ("kitten", "sitting", 3, 3),
+            ("ab", "ba", 2, 1),
+            ("ca", "abc", 3, 3),
+            ("påsswörd", "påswsörd", 2, 1),
+        ];
+        for (a, b, levenshtein, damerau) in cases {
+            assert_eq!(
+                levenshtein,
+                DistanceMetric::Levenshtein.distance(a, b),
+                "{a} {b}"
+            );
+            assert_eq!(damerau, DistanceMetric::Damerau.distance(a, b), "{a} {b}");
+        }
+        assert_eq!(1.0, DistanceMetric::Levenshtein.similarity("", ""));
+        assert_eq!(0.5, DistanceMetric::Levenshtein.similarity("abcd", "abxy"));
+    }
+}
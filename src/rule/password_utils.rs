@@ -1,13 +1,24 @@
+use crate::rule::character_data::CharacterData;
+
 /// Returns count of the characters in the input string that are also in the characters string.
 pub fn count_matching_characters(characters: &str, input: &str) -> usize {
     input.chars().filter(|&c| characters.contains(c)).count()
 }
 
-/// Returns all the characters in the input string that are also in the characters string.
-pub fn get_matching_characters(characters: &str, input: &str, maximum_len: usize) -> String {
+/// Returns count of the characters in the input string that belong to the character data.
+pub fn count_contained_characters(data: &dyn CharacterData, input: &str) -> usize {
+    input.chars().filter(|&c| data.contains(c)).count()
+}
+
+/// Returns all the characters in the input string that belong to the character data.
+pub fn get_matching_characters(
+    data: &dyn CharacterData,
+    input: &str,
+    maximum_len: usize,
+) -> String {
     input
         .chars()
-        .filter(|&c| characters.contains(c))
+        .filter(|&c| data.contains(c))
         .take(maximum_len)
         .collect::<String>()
 }
@@ -1,5 +1,6 @@
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
+use std::any::Any;
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -80,6 +81,9 @@ impl Rule for PasswordValidator {
         }
         result
     }
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
@@ -126,8 +130,8 @@ mod tests {
         const INVALID_PASS: &str = "aBcDeFgHiJk";
         let pass_data = PasswordData::with_password(INVALID_PASS.to_string());
         let rule_result = pv.validate(&pass_data);
-        assert_eq!(rule_result.valid(), false);
-        assert_eq!(rule_result.details().is_empty(), false);
+        assert!(!rule_result.valid());
+        assert!(!rule_result.details().is_empty());
 
         let mut rules = create_validate_rules();
         rules.push(Box::new(
@@ -149,7 +153,7 @@ mod tests {
 
         let pass_data =
             PasswordData::with_password_and_user(INVALID_PASS.to_string(), Some(USER.to_string()));
-        assert_eq!(pv.validate(&pass_data).valid(), false);
+        assert!(!pv.validate(&pass_data).valid());
     }
 
     fn create_validate_rules() -> Vec<Box<dyn Rule>> {
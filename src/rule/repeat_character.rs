@@ -1,8 +1,10 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
+use std::any::Any;
 use std::collections::{HashMap, HashSet};
 
-pub const ERROR_CODE: &str = "ILLEGAL_MATCH";
+pub const ERROR_CODE: &str = ErrorCode::IllegalMatch.as_str();
 const DEFAULT_SEQUENCE_LENGTH: usize = 5;
 const MINIMUM_SEQUENCE_LENGTH: usize = 3;
 
@@ -44,6 +46,10 @@ impl RepeatCharacterRule {
         Self::new(sequence_len, true)
     }
 
+    pub fn sequence_length(&self) -> usize {
+        self.sequence_length
+    }
+
     fn create_rule_result_detail_parameters(&self, match_str: &str) -> HashMap<String, String> {
         let mut map = HashMap::with_capacity(2);
         map.insert("match".to_string(), match_str.to_string());
@@ -109,6 +115,9 @@ impl Rule for RepeatCharacterRule {
 
         result
     }
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 impl Default for RepeatCharacterRule {
@@ -1,8 +1,9 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use std::collections::HashMap;
 
-const ERROR_CODE: &str = "ILLEGAL_REPEATED_CHARS";
+const ERROR_CODE: &str = ErrorCode::IllegalRepeatedChars.as_str();
 const DEFAULT_SEQUENCE_LENGTH: usize = 5;
 const DEFAULT_SEQUENCE_COUNT: usize = 1;
 
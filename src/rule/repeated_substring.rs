@@ -0,0 +1,207 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{PasswordData, Rule};
+use std::any::Any;
+use std::collections::HashMap;
+
+pub const ERROR_CODE: &str = ErrorCode::IllegalRepeatedSubstring.as_str();
+const DEFAULT_BLOCK_LENGTH: usize = 3;
+const DEFAULT_REPEAT_COUNT: usize = 2;
+const MINIMUM_REPEAT_COUNT: usize = 2;
+
+/// Rule for determining if a password contains a block of characters repeated several times in a row.
+/// By default blocks of at least 3 characters repeated at least twice are illegal.
+/// Sequences are of the form: 'abcabcabc' or 'Pass1Pass1'
+///
+/// For every position the shortest block is reported, together with the number of times it repeats.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::repeated_substring::RepeatedSubstringRule;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = RepeatedSubstringRule::default();
+///  let password = PasswordData::with_password("123123123".to_string());
+///  let result = rule.validate(&password);
+///  assert!(!result.valid());
+/// ```
+pub struct RepeatedSubstringRule {
+    block_length: usize,
+    repeat_count: usize,
+    report_all: bool,
+}
+
+impl RepeatedSubstringRule {
+    pub fn new(block_length: usize, repeat_count: usize, report_all: bool) -> Result<Self, String> {
+        if block_length == 0 {
+            return Err("block length must be >= 1".to_string());
+        }
+        if repeat_count < MINIMUM_REPEAT_COUNT {
+            return Err(format!("repeat count must be >= {MINIMUM_REPEAT_COUNT}"));
+        }
+        Ok(Self {
+            block_length,
+            repeat_count,
+            report_all,
+        })
+    }
+
+    pub fn block_length(&self) -> usize {
+        self.block_length
+    }
+
+    pub fn repeat_count(&self) -> usize {
+        self.repeat_count
+    }
+
+    fn create_rule_result_detail_parameters(
+        &self,
+        block: &str,
+        count: usize,
+        match_str: &str,
+    ) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(3);
+        map.insert("match".to_string(), match_str.to_string());
+        map.insert("block".to_string(), block.to_string());
+        map.insert("count".to_string(), count.to_string());
+        map
+    }
+}
+
+/// Returns how often the block of the given length starting at `start` repeats in a row.
+pub(crate) fn repeats(chars: &[char], start: usize, length: usize) -> usize {
+    let block = &chars[start..start + length];
+    chars[start..].chunks_exact(length).take_while(|chunk| *chunk == block).count()
+}
+
+impl Rule for RepeatedSubstringRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let chars: Vec<char> = password_data.password().chars().collect();
+        let mut start = 0;
+        while start < chars.len() {
+            let max_length = (chars.len() - start) / self.repeat_count;
+            let found = (self.block_length..=max_length)
+                .map(|length| (length, repeats(&chars, start, length)))
+                .find(|(_, count)| *count >= self.repeat_count);
+            let Some((length, count)) = found else {
+                start += 1;
+                continue;
+            };
+            let block: String = chars[start..start + length].iter().collect();
+            let match_str: String = chars[start..start + length * count].iter().collect();
+            result.add_error(
+                ERROR_CODE,
+                Some(self.create_rule_result_detail_parameters(&block, count, &match_str)),
+            );
+            if !self.report_all {
+                break;
+            }
+            start += length * count;
+        }
+        result
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+}
+
+impl Default for RepeatedSubstringRule {
+    fn default() -> Self {
+        RepeatedSubstringRule::new(DEFAULT_BLOCK_LENGTH, DEFAULT_REPEAT_COUNT, true).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::repeated_substring::{ERROR_CODE, RepeatedSubstringRule};
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    #[test]
+    fn test_passwords() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            // test valid password
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("p4zRcv8#n65".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("abcabcabc".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("Pass1Pass1".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("#123123123#".to_string()),
+                vec![ERROR_CODE],
+            ),
+            // test block shorter than the block length
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("x7y7z7".to_string()),
+                vec![],
+            ),
+            // test too few repeats
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::new(3, 3, true).unwrap()),
+                PasswordData::with_password("Pass1Pass1".to_string()),
+                vec![],
+            ),
+            // test multiple matches
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("abcabc-xyz-dogdog".to_string()),
+                vec![ERROR_CODE, ERROR_CODE],
+            ),
+            // test single match
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::new(3, 2, false).unwrap()),
+                PasswordData::with_password("abcabc-xyz-dogdog".to_string()),
+                vec![ERROR_CODE],
+            ),
+            // test utf8 characters
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("مجتبیمجتبی".to_string()),
+                vec![ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+
+        assert!(RepeatedSubstringRule::new(0, 2, true).is_err());
+        assert!(RepeatedSubstringRule::new(3, 1, true).is_err());
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("abcabcabc".to_string()),
+                vec!["ILLEGAL_REPEATED_SUBSTRING,\"abc\",\"3\",\"abcabcabc\""],
+            ),
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::default()),
+                PasswordData::with_password("!Pass1Pass1".to_string()),
+                vec!["ILLEGAL_REPEATED_SUBSTRING,\"Pass1\",\"2\""],
+            ),
+            // the shortest block is reported
+            RulePasswordTestItem(
+                Box::new(RepeatedSubstringRule::new(2, 2, true).unwrap()),
+                PasswordData::with_password("abababab".to_string()),
+                vec!["ILLEGAL_REPEATED_SUBSTRING,\"ab\",\"4\""],
+            ),
+        ];
+        check_messages(test_cases);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::rule::error_code::ErrorCode;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
@@ -21,9 +22,12 @@ impl RuleResult {
         }
     }
 
-    pub fn add_error(&mut self, code: &str, params: Option<HashMap<String, String>>) {
+    pub fn add_error(&mut self, code: impl AsRef<str>, params: Option<HashMap<String, String>>) {
         self.valid = false;
-        self.details.push(RuleResultDetail::new(vec![code.to_string()], params))
+        self.details.push(RuleResultDetail::new(
+            vec![code.as_ref().to_string()],
+            params,
+        ))
     }
 
     pub fn add_error_with_codes(
@@ -101,6 +105,11 @@ impl RuleResultDetail {
     pub fn error_codes(&self) -> &[String] {
         self.error_codes.as_slice()
     }
+
+    /// Returns the least-specific error code as [ErrorCode].
+    pub fn code(&self) -> ErrorCode {
+        ErrorCode::from(self.error_code())
+    }
 }
 
 impl Display for RuleResultDetail {
@@ -113,20 +122,40 @@ impl Display for RuleResultDetail {
 #[derive(Default, Debug)]
 pub struct RuleResultMetadata {
     counts: HashMap<CountCategory, usize>,
+    /// Estimated entropy bits of the password.
+    entropy: Option<f64>,
 }
 
 impl RuleResultMetadata {
     pub fn new(category: CountCategory, value: usize) -> Self {
         let mut counts = HashMap::new();
         counts.insert(category, value);
-        Self { counts }
+        Self {
+            counts,
+            entropy: None,
+        }
     }
+
+    pub fn with_entropy(entropy: f64) -> Self {
+        Self {
+            counts: HashMap::new(),
+            entropy: Some(entropy),
+        }
+    }
+
     pub fn get_count(&self, category: CountCategory) -> usize {
         self.counts.get(&category).copied().unwrap_or(0)
     }
 
+    pub fn entropy(&self) -> Option<f64> {
+        self.entropy
+    }
+
     pub fn merge(&mut self, other: &RuleResultMetadata) {
         self.counts.extend(other.counts.clone());
+        if other.entropy.is_some() {
+            self.entropy = other.entropy;
+        }
     }
 }
 
@@ -155,4 +184,7 @@ pub enum CountCategory {
 
     /// illegal characters.
     Illegal,
+
+    /// distinct characters.
+    Unique,
 }
@@ -1,4 +1,5 @@
 use crate::rule::character_sequence::CharacterSequence;
+use crate::rule::error_code::ErrorCode;
 
 /// Container for one or more CharacterSequence.
 ///
@@ -16,6 +17,16 @@ pub trait SequenceData {
     fn get_sequences(&self) -> Vec<CharacterSequence>;
 }
 
+impl<S: SequenceData + ?Sized> SequenceData for Box<S> {
+    fn error_code(&self) -> &str {
+        (**self).error_code()
+    }
+
+    fn get_sequences(&self) -> Vec<CharacterSequence> {
+        (**self).get_sequences()
+    }
+}
+
 /// English character sequences.
 ///
 /// # Author
@@ -30,9 +41,13 @@ pub enum EnglishSequenceData {
 impl SequenceData for EnglishSequenceData {
     fn error_code(&self) -> &str {
         match self {
-            EnglishSequenceData::Alphabetical => "ILLEGAL_ALPHABETICAL_SEQUENCE",
-            EnglishSequenceData::Numerical => "ILLEGAL_NUMERICAL_SEQUENCE",
-            EnglishSequenceData::USQwerty => "ILLEGAL_QWERTY_SEQUENCE",
+            EnglishSequenceData::Alphabetical => {
+                const { ErrorCode::IllegalAlphabeticalSequence.as_str() }
+            }
+            EnglishSequenceData::Numerical => {
+                const { ErrorCode::IllegalNumericalSequence.as_str() }
+            }
+            EnglishSequenceData::USQwerty => const { ErrorCode::IllegalQwertySequence.as_str() },
         }
     }
 
@@ -91,7 +106,9 @@ pub enum PolishSequenceData {
 impl SequenceData for PolishSequenceData {
     fn error_code(&self) -> &str {
         match self {
-            PolishSequenceData::Alphabetical => "ILLEGAL_ALPHABETICAL_SEQUENCE",
+            PolishSequenceData::Alphabetical => {
+                const { ErrorCode::IllegalAlphabeticalSequence.as_str() }
+            }
         }
     }
 
@@ -116,8 +133,10 @@ pub enum GermanSequenceData {
 impl SequenceData for GermanSequenceData {
     fn error_code(&self) -> &str {
         match self {
-            GermanSequenceData::Alphabetical => "ILLEGAL_ALPHABETICAL_SEQUENCE",
-            GermanSequenceData::DEQwertz => "ILLEGAL_QWERTY_SEQUENCE",
+            GermanSequenceData::Alphabetical => {
+                const { ErrorCode::IllegalAlphabeticalSequence.as_str() }
+            }
+            GermanSequenceData::DEQwertz => const { ErrorCode::IllegalQwertySequence.as_str() },
         }
     }
 
@@ -167,7 +186,9 @@ pub enum CzechSequenceData {
 impl SequenceData for CzechSequenceData {
     fn error_code(&self) -> &str {
         match self {
-            CzechSequenceData::Alphabetical => "ILLEGAL_ALPHABETICAL_SEQUENCE",
+            CzechSequenceData::Alphabetical => {
+                const { ErrorCode::IllegalAlphabeticalSequence.as_str() }
+            }
         }
     }
 
@@ -191,7 +212,9 @@ pub enum CyrillicSequenceData {
 impl SequenceData for CyrillicSequenceData {
     fn error_code(&self) -> &str {
         match self {
-            CyrillicSequenceData::Alphabetical => "ILLEGAL_ALPHABETICAL_SEQUENCE",
+            CyrillicSequenceData::Alphabetical => {
+                const { ErrorCode::IllegalAlphabeticalSequence.as_str() }
+            }
         }
     }
 
@@ -1,3 +1,4 @@
+use crate::rule::error_code::ErrorCode;
 use crate::rule::reference::{Reference, Salt};
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
@@ -5,7 +6,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 
-pub(super) const ERROR_CODE: &str = "SOURCE_VIOLATION";
+pub(super) const ERROR_CODE: &str = ErrorCode::SourceViolation.as_str();
 
 /// Rule for determining if a password matches a password from a different source. Useful for when separate systems
 /// cannot have matching passwords. If no source password reference has been set, then passwords will meet this rule.
@@ -0,0 +1,439 @@
+use crate::dictionary::Dictionary;
+use crate::rule::dictionary::{ERROR_CODE, ERROR_CODE_REVERSED};
+use crate::rule::dictionary_substring::search_substrings;
+use crate::rule::rule_result::RuleResult;
+use crate::rule::{DictionaryRuleTrait, PasswordData, Rule};
+use std::collections::{HashMap, HashSet};
+use std::iter;
+use std::ops::Range;
+
+/// Upper bound of the leet variants tried per password, characters with several readings multiply them.
+const MAX_LEET_VARIANTS: usize = 32;
+/// Upper bound of the lengths tried for stripping the leading and the trailing affix each.
+const MAX_AFFIX_VARIANTS: usize = 4;
+
+/// Transformations applied to a password before it is searched in a dictionary. Each enabled transformation adds
+/// candidates, the untransformed password is always the first one.
+///
+/// - `leet` maps characters to the letters they stand for, e.g. `@` to `a` or `1` to `i` and `l`.
+/// - `strip_affixes` removes leading and trailing runs of digits and symbols, e.g. `123!` in `password123!`.
+/// - `fold_case` lower-cases the candidates.
+/// - `reverse` adds the reversed candidates, which are reported with `ILLEGAL_WORD_REVERSED`.
+#[derive(Debug, Clone)]
+pub struct Transformations {
+    leet: HashMap<char, Vec<char>>,
+    strip_affixes: bool,
+    fold_case: bool,
+    reverse: bool,
+}
+
+/// A transformed password and the transformations that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub text: String,
+    pub transformations: Vec<&'static str>,
+}
+
+impl Candidate {
+    fn new(text: String, transformations: Vec<&'static str>) -> Self {
+        Self {
+            text,
+            transformations,
+        }
+    }
+
+    fn with(&self, text: String, transformation: &'static str) -> Self {
+        let mut transformations = self.transformations.clone();
+        transformations.push(transformation);
+        Self::new(text, transformations)
+    }
+
+    fn reversed(&self) -> bool {
+        self.transformations.contains(&"reverse")
+    }
+}
+
+impl Transformations {
+    pub fn new(
+        leet: HashMap<char, Vec<char>>,
+        strip_affixes: bool,
+        fold_case: bool,
+        reverse: bool,
+    ) -> Self {
+        Self {
+            leet,
+            strip_affixes,
+            fold_case,
+            reverse,
+        }
+    }
+
+    /// Returns the common leetspeak substitutions.
+    pub fn default_leet() -> HashMap<char, Vec<char>> {
+        [
+            ('@', vec!['a']),
+            ('4', vec!['a']),
+            ('8', vec!['b']),
+            ('(', vec!['c']),
+            ('3', vec!['e']),
+            ('6', vec!['g']),
+            ('9', vec!['g']),
+            ('#', vec!['h']),
+            ('1', vec!['i', 'l']),
+            ('!', vec!['i']),
+            ('|', vec!['i', 'l']),
+            ('0', vec!['o']),
+            ('$', vec!['s']),
+            ('5', vec!['s']),
+            ('7', vec!['t']),
+            ('+', vec!['t']),
+            ('%', vec!['x']),
+            ('2', vec!['z']),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    pub fn leet(&self) -> &HashMap<char, Vec<char>> {
+        &self.leet
+    }
+
+    /// Returns the distinct candidates for the password, in the order they are searched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use passay_rs::rule::transforming_dictionary::Transformations;
+    ///
+    ///  let candidates = Transformations::default().candidates("P@ssw0rd123!");
+    ///  let password = candidates.iter().find(|c| c.text == "password").unwrap();
+    ///  assert_eq!(vec!["strip", "leet", "lowercase"], password.transformations);
+    /// ```
+    pub fn candidates(&self, password: &str) -> Vec<Candidate> {
+        self.candidates_with_positions(password).into_iter().map(|(c, _)| c).collect()
+    }
+
+    /// Returns the candidates for the password together with the index of the password character each of their
+    /// characters was derived from.
+    pub(crate) fn candidates_with_positions(&self, password: &str) -> Vec<(Candidate, Vec<usize>)> {
+        let chars: Vec<char> = password.chars().collect();
+        let mut candidates = vec![(
+            Candidate::new(password.to_string(), vec![]),
+            (0..chars.len()).collect::<Vec<_>>(),
+        )];
+        if self.strip_affixes {
+            let stripped: Vec<(Candidate, Vec<usize>)> = strip_affixes(&chars)
+                .into_iter()
+                .map(|range| {
+                    let text = chars[range.clone()].iter().collect();
+                    (candidates[0].0.with(text, "strip"), range.collect())
+                })
+                .collect();
+            candidates.extend(stripped);
+        }
+        if !self.leet.is_empty() {
+            // every reading is a single character, so the positions stay the same
+            let leet: Vec<(Candidate, Vec<usize>)> = candidates
+                .iter()
+                .flat_map(|(c, positions)| {
+                    self.leet_variants(&c.text)
+                        .into_iter()
+                        .map(|text| (c.with(text, "leet"), positions.clone()))
+                })
+                .collect();
+            candidates.extend(leet);
+        }
+        if self.fold_case {
+            let folded: Vec<(Candidate, Vec<usize>)> = candidates
+                .iter()
+                .map(|(c, positions)| {
+                    // a character may lower-case to several characters, e.g. `İ`
+                    let positions = c
+                        .text
+                        .chars()
+                        .zip(positions)
+                        .flat_map(|(ch, p)| iter::repeat_n(*p, ch.to_lowercase().count()))
+                        .collect();
+                    (c.with(c.text.to_lowercase(), "lowercase"), positions)
+                })
+                .collect();
+            candidates.extend(folded);
+        }
+        if self.reverse {
+            let reversed: Vec<(Candidate, Vec<usize>)> = candidates
+                .iter()
+                .filter(|(c, _)| c.text.chars().count() > 1)
+                .map(|(c, positions)| {
+                    let text = c.text.chars().rev().collect();
+                    (
+                        c.with(text, "reverse"),
+                        positions.iter().rev().copied().collect(),
+                    )
+                })
+                .collect();
+            candidates.extend(reversed);
+        }
+        let mut seen = HashSet::new();
+        candidates.retain(|(c, _)| seen.insert(c.text.clone()));
+        candidates
+    }
+
+    /// Returns the strings with all leet characters substituted, one per combination of their readings.
+    fn leet_variants(&self, text: &str) -> Vec<String> {
+        if !text.chars().any(|c| self.leet.contains_key(&c)) {
+            return vec![];
+        }
+        let mut variants = vec![String::new()];
+        for c in text.chars() {
+            match self.leet.get(&c) {
+                Some(readings) if !readings.is_empty() => {
+                    variants = variants
+                        .iter()
+                        .flat_map(|v| readings.iter().map(move |r| format!("{v}{r}")))
+                        .take(MAX_LEET_VARIANTS)
+                        .collect();
+                }
+                _ => variants.iter_mut().for_each(|v| v.push(c)),
+            }
+        }
+        variants
+    }
+}
+
+impl Default for Transformations {
+    fn default() -> Self {
+        Self::new(Self::default_leet(), true, true, true)
+    }
+}
+
+/// Rule for determining if a password is a dictionary word after undoing common obfuscations. Each candidate produced
+/// by the [Transformations] is searched in the dictionary, either as a whole or, with `match_substrings`, for any
+/// contained word. The first matching candidate and the first matching reversed candidate are reported with the
+/// matched word, the candidate and its transformations.
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::dictionary::word_lists::word_list_dictionary::WordListDictionary;
+///  use passay_rs::dictionary::word_lists::ArrayWordList;
+///  use passay_rs::dictionary::word_lists::sort::SliceSort;
+///  use passay_rs::rule::transforming_dictionary::TransformingDictionaryRule;
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::Rule;
+///
+///  let words = ["password", "dragon"].map(String::from).to_vec();
+///  let word_list = ArrayWordList::with_sorter(words, true, Some(SliceSort));
+///  let rule = TransformingDictionaryRule::from_dictionary(WordListDictionary::new(word_list));
+///  let result = rule.validate(&PasswordData::with_password("P@ssw0rd123!".to_string()));
+///  assert!(!result.valid());
+/// ```
+pub struct TransformingDictionaryRule<D: Dictionary> {
+    dictionary: D,
+    transformations: Transformations,
+    match_substrings: bool,
+}
+
+impl<D: Dictionary> TransformingDictionaryRule<D> {
+    pub fn new(dictionary: D, transformations: Transformations, match_substrings: bool) -> Self {
+        Self {
+            dictionary,
+            transformations,
+            match_substrings,
+        }
+    }
+
+    pub fn from_dictionary(dictionary: D) -> Self {
+        Self::new(dictionary, Transformations::default(), false)
+    }
+
+    pub fn transformations(&self) -> &Transformations {
+        &self.transformations
+    }
+
+    fn do_word_search(&self, text: &str) -> Option<String> {
+        if self.match_substrings {
+            search_substrings(&self.dictionary, text)
+        } else {
+            self.dictionary.search(text).then(|| text.to_string())
+        }
+    }
+
+    fn create_rule_result_detail_parameters(
+        &self,
+        matching_word: &str,
+        candidate: &Candidate,
+    ) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(3);
+        map.insert("matchingWord".to_string(), matching_word.to_string());
+        map.insert("candidate".to_string(), candidate.text.clone());
+        map.insert(
+            "transformations".to_string(),
+            candidate.transformations.join(","),
+        );
+        map
+    }
+}
+
+impl<D: Dictionary> Rule for TransformingDictionaryRule<D> {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let candidates = self.transformations.candidates(password_data.password());
+        for reversed in [false, true] {
+            let matched = candidates
+                .iter()
+                .filter(|c| c.reversed() == reversed)
+                .find_map(|c| self.do_word_search(&c.text).map(|word| (word, c)));
+            if let Some((word, candidate)) = matched {
+                let code = if reversed {
+                    ERROR_CODE_REVERSED
+                } else {
+                    ERROR_CODE
+                };
+                result.add_error(
+                    code,
+                    Some(self.create_rule_result_detail_parameters(&word, candidate)),
+                );
+            }
+        }
+        result
+    }
+
+    fn as_dictionary_rule(&self) -> Option<&dyn DictionaryRuleTrait> {
+        Some(self)
+    }
+}
+
+impl<D: Dictionary> DictionaryRuleTrait for TransformingDictionaryRule<D> {
+    fn dictionary(&self) -> &dyn Dictionary {
+        &self.dictionary
+    }
+}
+
+/// Returns the ranges of the characters without parts of their leading and trailing runs of digits and symbols, the
+/// fully stripped range first. Partially stripped variants keep leet characters at the ends, e.g. `5` of `!!5ecret`.
+fn strip_affixes(chars: &[char]) -> Vec<Range<usize>> {
+    let leading = chars.iter().take_while(|c| !c.is_alphabetic()).count();
+    if leading == chars.len() {
+        return vec![];
+    }
+    let trailing = chars.iter().rev().take_while(|c| !c.is_alphabetic()).count();
+    let mut variants = vec![];
+    for start in (0..=leading).rev().take(MAX_AFFIX_VARIANTS) {
+        for end in (0..=trailing).rev().take(MAX_AFFIX_VARIANTS) {
+            if start > 0 || end > 0 {
+                variants.push(start..chars.len() - end);
+            }
+        }
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dictionary::word_lists::ArrayWordList;
+    use crate::dictionary::word_lists::sort::SliceSort;
+    use crate::dictionary::word_lists::word_list_dictionary::WordListDictionary;
+    use crate::rule::dictionary::{ERROR_CODE, ERROR_CODE_REVERSED};
+    use crate::rule::transforming_dictionary::{
+        Candidate, Transformations, TransformingDictionaryRule,
+    };
+    use crate::rule::{PasswordData, Rule};
+    use crate::test::{RulePasswordTestItem, check_passwords};
+    use std::collections::HashMap;
+
+    fn dictionary() -> WordListDictionary<ArrayWordList> {
+        let words = ["dragon", "monkey", "password", "sunshine"].map(String::from).to_vec();
+        WordListDictionary::new(ArrayWordList::with_sorter(words, true, Some(SliceSort)))
+    }
+
+    fn rule() -> Box<TransformingDictionaryRule<WordListDictionary<ArrayWordList>>> {
+        Box::new(TransformingDictionaryRule::from_dictionary(dictionary()))
+    }
+
+    fn password(password: &str) -> PasswordData {
+        PasswordData::with_password(password.to_string())
+    }
+
+    #[test]
+    fn test_passwords() {
+        let no_case = Transformations::new(Transformations::default_leet(), true, false, false);
+        let test_cases = vec![
+            RulePasswordTestItem(rule(), password("password"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("P@ssw0rd123!"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("!!5un5h1n3"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("DRAGON2024"), vec![ERROR_CODE]),
+            RulePasswordTestItem(rule(), password("#yeknom#"), vec![ERROR_CODE_REVERSED]),
+            RulePasswordTestItem(rule(), password("Tr0ub4dor&3"), vec![]),
+            RulePasswordTestItem(rule(), password("passwords"), vec![]),
+            RulePasswordTestItem(
+                Box::new(TransformingDictionaryRule::new(
+                    dictionary(),
+                    no_case.clone(),
+                    false,
+                )),
+                password("P@ssw0rd"),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(TransformingDictionaryRule::new(
+                    dictionary(),
+                    no_case,
+                    false,
+                )),
+                password("p@ssw0rd!"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(TransformingDictionaryRule::new(
+                    dictionary(),
+                    Transformations::default(),
+                    true,
+                )),
+                password("myP@55w0rdIsSafe"),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(TransformingDictionaryRule::new(
+                    dictionary(),
+                    Transformations::new(HashMap::new(), false, false, false),
+                    false,
+                )),
+                password("dragon!"),
+                vec![],
+            ),
+        ];
+        check_passwords(test_cases);
+    }
+
+    #[test]
+    fn test_detail() {
+        let result = rule().validate(&password("Dr@g0n!"));
+        let detail = result.details()[0].to_string();
+        assert!(detail.contains("\"matchingWord\": \"dragon\""), "{detail}");
+        assert!(detail.contains("\"candidate\": \"dragon\""), "{detail}");
+        assert!(
+            detail.contains("\"transformations\": \"strip,leet,lowercase\""),
+            "{detail}"
+        );
+    }
+
+    #[test]
+    fn test_candidates() {
+        let transformations = Transformations::default();
+        assert_eq!(
+            vec![
+                Candidate::new("ab1".to_string(), vec![]),
+                Candidate::new("ab".to_string(), vec!["strip"]),
+                Candidate::new("abi".to_string(), vec!["leet"]),
+                Candidate::new("abl".to_string(), vec!["leet"]),
+                Candidate::new("1ba".to_string(), vec!["reverse"]),
+                Candidate::new("ba".to_string(), vec!["strip", "reverse"]),
+                Candidate::new("iba".to_string(), vec!["leet", "reverse"]),
+                Candidate::new("lba".to_string(), vec!["leet", "reverse"]),
+            ],
+            transformations.candidates("ab1")
+        );
+        let variants = transformations.candidates("1111111111");
+        assert!(variants.len() < 4 * super::MAX_LEET_VARIANTS);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::rule::error_code::ErrorCode;
+use crate::rule::rule_result::{CountCategory, RuleResult, RuleResultMetadata};
+use crate::rule::{PasswordData, Rule};
+use std::collections::{HashMap, HashSet};
+
+pub const ERROR_CODE: &str = ErrorCode::InsufficientUniqueCharacters.as_str();
+
+/// Rule for determining if a password contains enough distinct characters, either as a minimum count or as a minimum
+/// ratio of distinct characters to all characters. Characters are counted per Unicode scalar value, so `é` written
+/// with a combining accent counts as two characters. The number of distinct characters is reported in the metadata as
+/// [CountCategory::Unique].
+///
+/// # Example
+///
+/// ```
+///  use passay_rs::rule::PasswordData;
+///  use passay_rs::rule::unique_characters::UniqueCharactersRule;
+///  use passay_rs::rule::Rule;
+///
+///  let rule = UniqueCharactersRule::with_min_unique(6);
+///  let password = PasswordData::with_password("aaaaaaaaaaaaB1!".to_string());
+///  let result = rule.validate(&password);
+///  assert!(!result.valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniqueCharactersRule {
+    min_unique: usize,
+    min_ratio: f64,
+}
+
+impl UniqueCharactersRule {
+    pub fn new(min_unique: usize, min_ratio: f64) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&min_ratio) {
+            return Err("minimum ratio must be between 0 and 1".to_string());
+        }
+        Ok(Self {
+            min_unique,
+            min_ratio,
+        })
+    }
+
+    pub fn with_min_unique(min_unique: usize) -> Self {
+        Self {
+            min_unique,
+            min_ratio: 0.0,
+        }
+    }
+
+    pub fn with_min_ratio(min_ratio: f64) -> Result<Self, String> {
+        Self::new(0, min_ratio)
+    }
+
+    pub fn min_unique(&self) -> usize {
+        self.min_unique
+    }
+
+    pub fn min_ratio(&self) -> f64 {
+        self.min_ratio
+    }
+
+    fn create_rule_result_detail_parameters(
+        &self,
+        unique: usize,
+        ratio: f64,
+    ) -> HashMap<String, String> {
+        let mut map = HashMap::with_capacity(4);
+        map.insert("uniqueCount".to_string(), unique.to_string());
+        map.insert("minimumUnique".to_string(), self.min_unique.to_string());
+        map.insert("uniqueRatio".to_string(), format!("{ratio:.2}"));
+        map.insert("minimumRatio".to_string(), format!("{:.2}", self.min_ratio));
+        map
+    }
+}
+
+impl Rule for UniqueCharactersRule {
+    fn validate(&self, password_data: &PasswordData) -> RuleResult {
+        let mut result = RuleResult::default();
+        let total = password_data.password().chars().count();
+        let unique = password_data.password().chars().collect::<HashSet<_>>().len();
+        let ratio = if total == 0 {
+            0.0
+        } else {
+            unique as f64 / total as f64
+        };
+        if unique < self.min_unique || ratio < self.min_ratio {
+            result.add_error(
+                ERROR_CODE,
+                Some(self.create_rule_result_detail_parameters(unique, ratio)),
+            );
+        }
+        result.set_metadata(RuleResultMetadata::new(CountCategory::Unique, unique));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rule::PasswordData;
+    use crate::rule::Rule;
+    use crate::rule::rule_result::CountCategory;
+    use crate::rule::unique_characters::{ERROR_CODE, UniqueCharactersRule};
+    use crate::test::{RulePasswordTestItem, check_messages, check_passwords};
+
+    #[test]
+    fn test_passwords() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![
+            RulePasswordTestItem(
+                Box::new(UniqueCharactersRule::with_min_unique(6)),
+                PasswordData::with_password("aaaaaaaaaaaaB1!".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(UniqueCharactersRule::with_min_unique(6)),
+                PasswordData::with_password("p4zRcv8#n65".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(UniqueCharactersRule::with_min_ratio(0.5).unwrap()),
+                PasswordData::with_password("abababab12".to_string()),
+                vec![ERROR_CODE],
+            ),
+            RulePasswordTestItem(
+                Box::new(UniqueCharactersRule::with_min_ratio(0.5).unwrap()),
+                PasswordData::with_password("abcdabcd".to_string()),
+                vec![],
+            ),
+            RulePasswordTestItem(
+                Box::new(UniqueCharactersRule::new(3, 0.5).unwrap()),
+                PasswordData::with_password("".to_string()),
+                vec![ERROR_CODE],
+            ),
+            // test utf8 characters
+            RulePasswordTestItem(
+                Box::new(UniqueCharactersRule::with_min_unique(4)),
+                PasswordData::with_password("ééééññññ😀😀".to_string()),
+                vec![ERROR_CODE],
+            ),
+        ];
+        check_passwords(test_cases);
+        assert!(UniqueCharactersRule::new(0, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_messages() {
+        let test_cases: Vec<RulePasswordTestItem> = vec![RulePasswordTestItem(
+            Box::new(UniqueCharactersRule::new(6, 0.5).unwrap()),
+            PasswordData::with_password("aaaaaaaaaaaaB1!".to_string()),
+            vec!["INSUFFICIENT_UNIQUE_CHARACTERS,\"uniqueCount\": \"4\",\"0.27\""],
+        )];
+        check_messages(test_cases);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let rule = UniqueCharactersRule::with_min_unique(2);
+        let result = rule.validate(&PasswordData::with_password("ééééññññ😀😀".to_string()));
+        assert!(result.valid());
+        assert_eq!(3, result.metadata().get_count(CountCategory::Unique));
+        // a combining accent is a separate scalar
+        let result = rule.validate(&PasswordData::with_password("e\u{301}e\u{301}".to_string()));
+        assert_eq!(2, result.metadata().get_count(CountCategory::Unique));
+    }
+}
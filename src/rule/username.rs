@@ -1,10 +1,11 @@
 use crate::rule::allowed_character::MatchBehavior;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::rule_result::RuleResult;
 use crate::rule::{PasswordData, Rule};
 use std::collections::HashMap;
 
-pub(crate) const ERROR_CODE: &str = "ILLEGAL_USERNAME";
-pub(crate) const ERROR_CODE_REVERSED: &str = "ILLEGAL_USERNAME_REVERSED";
+pub(crate) const ERROR_CODE: &str = ErrorCode::IllegalUsername.as_str();
+pub(crate) const ERROR_CODE_REVERSED: &str = ErrorCode::IllegalUsernameReversed.as_str();
 
 /// Rule for determining if a password contains the username associated with that password.
 /// This rule returns true if a supplied [PasswordData] returns a None or empty username.
@@ -1,11 +1,12 @@
 use crate::rule::allowed_character::MatchBehavior;
 use crate::rule::allowed_character::MatchBehavior::Contains;
+use crate::rule::error_code::ErrorCode;
 use crate::rule::password_utils::count_matching_characters;
 use crate::rule::rule_result::{CountCategory, RuleResult, RuleResultMetadata};
 use crate::rule::{PasswordData, Rule};
 use std::collections::HashMap;
 
-pub const ERROR_CODE: &str = "ILLEGAL_WHITESPACE";
+pub const ERROR_CODE: &str = ErrorCode::IllegalWhitespace.as_str();
 const WHITESPACES: &[char] =
     &['\u{0009}', '\u{000a}', '\u{000b}', '\u{000c}', '\u{000d}', '\u{0020}'];
 